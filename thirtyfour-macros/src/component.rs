@@ -1,6 +1,7 @@
 extern crate proc_macro;
 
 use crate::bail;
+use crate::selector::{validate_css, validate_name, validate_xpath};
 use proc_macro2::TokenStream;
 use proc_macro2::{Literal, Span};
 use quote::{format_ident, quote, ToTokens, TokenStreamExt};
@@ -8,7 +9,7 @@ use std::collections::HashSet;
 use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
 use syn::{
-    Data, Expr, ExprLit, Fields, GenericArgument, Lit, Meta, MetaNameValue, PathArguments,
+    Data, Expr, ExprLit, Fields, GenericArgument, Lit, LitStr, Meta, MetaNameValue, PathArguments,
     PathSegment, Token,
 };
 
@@ -268,6 +269,14 @@ enum ByToken {
     XPath(Literal),
     Name(Literal),
     ClassName(Literal),
+    Role(Literal),
+    /// The accessible name to match, together with `role`.
+    AccessibleName(Literal),
+    Label(Literal),
+    Placeholder(Literal),
+    Text(Literal),
+    TestId(Literal),
+    Js(Literal),
     Multi,
     /// NotEmpty is the default but can be specified to be explicit.
     NotEmpty,
@@ -294,7 +303,14 @@ impl ByToken {
             | ByToken::Css(_)
             | ByToken::XPath(_)
             | ByToken::Name(_)
-            | ByToken::ClassName(_) => "selector",
+            | ByToken::ClassName(_)
+            | ByToken::Role(_)
+            | ByToken::Label(_)
+            | ByToken::Placeholder(_)
+            | ByToken::Text(_)
+            | ByToken::TestId(_)
+            | ByToken::Js(_) => "selector",
+            ByToken::AccessibleName(_) => "accessible_name",
             ByToken::Multi => "multi",
             ByToken::NotEmpty => "not_empty",
             ByToken::AllowEmpty => "allow_empty",
@@ -317,7 +333,9 @@ impl ByToken {
             ByToken::First => vec!["multi", "custom", "single"],
             ByToken::NotEmpty => vec!["custom", "single", "first", "allow_empty"],
             ByToken::AllowEmpty => vec!["custom", "single", "first", "not_empty"],
-            ByToken::IgnoreErrors | ByToken::Description(_) => vec!["custom"],
+            ByToken::IgnoreErrors | ByToken::Description(_) | ByToken::AccessibleName(_) => {
+                vec!["custom"]
+            }
             ByToken::Wait(_) => vec!["custom", "nowait"],
             ByToken::NoWait => vec!["custom", "wait"],
             ByToken::CustomFn(_) => {
@@ -415,14 +433,16 @@ impl TryFrom<Meta> for ByToken {
                         lit: Lit::Str(v),
                         ..
                     }),
-                ) if k.is_ident("id") => Ok(ByToken::Id(v.token())),
+                ) if k.is_ident("id") => Ok(ByToken::Id(checked(&v, |x| validate_name("id", x))?)),
                 (
                     k,
                     Expr::Lit(ExprLit {
                         lit: Lit::Str(v),
                         ..
                     }),
-                ) if k.is_ident("tag") => Ok(ByToken::Tag(v.token())),
+                ) if k.is_ident("tag") => {
+                    Ok(ByToken::Tag(checked(&v, |x| validate_name("tag", x))?))
+                }
                 (
                     k,
                     Expr::Lit(ExprLit {
@@ -436,14 +456,14 @@ impl TryFrom<Meta> for ByToken {
                         lit: Lit::Str(v),
                         ..
                     }),
-                ) if k.is_ident("css") => Ok(ByToken::Css(v.token())),
+                ) if k.is_ident("css") => Ok(ByToken::Css(checked(&v, validate_css)?)),
                 (
                     k,
                     Expr::Lit(ExprLit {
                         lit: Lit::Str(v),
                         ..
                     }),
-                ) if k.is_ident("xpath") => Ok(ByToken::XPath(v.token())),
+                ) if k.is_ident("xpath") => Ok(ByToken::XPath(checked(&v, validate_xpath)?)),
                 (
                     k,
                     Expr::Lit(ExprLit {
@@ -457,7 +477,58 @@ impl TryFrom<Meta> for ByToken {
                         lit: Lit::Str(v),
                         ..
                     }),
-                ) if k.is_ident("class") => Ok(ByToken::ClassName(v.token())),
+                ) if k.is_ident("class") => {
+                    Ok(ByToken::ClassName(checked(&v, |x| validate_name("class", x))?))
+                }
+                (
+                    k,
+                    Expr::Lit(ExprLit {
+                        lit: Lit::Str(v),
+                        ..
+                    }),
+                ) if k.is_ident("role") => Ok(ByToken::Role(v.token())),
+                (
+                    k,
+                    Expr::Lit(ExprLit {
+                        lit: Lit::Str(v),
+                        ..
+                    }),
+                ) if k.is_ident("accessible_name") => Ok(ByToken::AccessibleName(v.token())),
+                (
+                    k,
+                    Expr::Lit(ExprLit {
+                        lit: Lit::Str(v),
+                        ..
+                    }),
+                ) if k.is_ident("label") => Ok(ByToken::Label(v.token())),
+                (
+                    k,
+                    Expr::Lit(ExprLit {
+                        lit: Lit::Str(v),
+                        ..
+                    }),
+                ) if k.is_ident("placeholder") => Ok(ByToken::Placeholder(v.token())),
+                (
+                    k,
+                    Expr::Lit(ExprLit {
+                        lit: Lit::Str(v),
+                        ..
+                    }),
+                ) if k.is_ident("text") => Ok(ByToken::Text(v.token())),
+                (
+                    k,
+                    Expr::Lit(ExprLit {
+                        lit: Lit::Str(v),
+                        ..
+                    }),
+                ) if k.is_ident("testid") => Ok(ByToken::TestId(v.token())),
+                (
+                    k,
+                    Expr::Lit(ExprLit {
+                        lit: Lit::Str(v),
+                        ..
+                    }),
+                ) if k.is_ident("js") => Ok(ByToken::Js(v.token())),
                 (
                     k,
                     Expr::Lit(ExprLit {
@@ -475,6 +546,12 @@ impl TryFrom<Meta> for ByToken {
     }
 }
 
+/// Check a selector at compile time, reporting any mistake at the attribute value.
+fn checked(value: &LitStr, check: impl FnOnce(&str) -> Result<(), String>) -> syn::Result<Literal> {
+    check(&value.value()).map_err(|e| syn::Error::new(value.span(), e))?;
+    Ok(value.token())
+}
+
 /// Wrapper for a list of tokens so we can add methods to it.
 struct ByTokens {
     tokens: Vec<ByToken>,
@@ -502,10 +579,17 @@ impl ByTokens {
                 }
             }
         }
+        if unique_tokens.contains("accessible_name") && !self.has_role() {
+            bail!(span, "cannot specify 'accessible_name' without 'role'")
+        }
 
         Ok(self)
     }
 
+    fn has_role(&self) -> bool {
+        self.tokens.iter().any(|x| matches!(&x, ByToken::Role(_)))
+    }
+
     /// Extract just the "By"-specific part of the tokens.
     ///
     /// For example, `name = "element-name"`.
@@ -515,6 +599,11 @@ impl ByTokens {
     /// This will also panic if more than one such token exists.
     pub fn take_by(&mut self) -> TokenStream {
         let mut ret = Vec::new();
+        let name = self.take_one(|x| match x {
+            ByToken::AccessibleName(n) => Some(quote! { ::thirtyfour::TextMatch::from(#n) }),
+            _ => None,
+        });
+        let name = name.unwrap_or_else(|| quote! { ::thirtyfour::TextMatch::any() });
         let tokens_in = std::mem::take(&mut self.tokens);
         for token in tokens_in.into_iter() {
             match token {
@@ -525,6 +614,12 @@ impl ByTokens {
                 ByToken::XPath(xpath) => ret.push(quote! { By::XPath(#xpath) }),
                 ByToken::Name(name) => ret.push(quote! { By::Name(#name) }),
                 ByToken::ClassName(class_name) => ret.push(quote! { By::ClassName(#class_name) }),
+                ByToken::Role(role) => ret.push(quote! { By::Role(#role, #name) }),
+                ByToken::Label(text) => ret.push(quote! { By::Label(#text) }),
+                ByToken::Placeholder(text) => ret.push(quote! { By::Placeholder(#text) }),
+                ByToken::Text(text) => ret.push(quote! { By::Text(#text) }),
+                ByToken::TestId(id) => ret.push(quote! { By::TestId(#id) }),
+                ByToken::Js(script) => ret.push(quote! { By::Js(#script, ::std::vec::Vec::new()) }),
                 t => self.tokens.push(t),
             }
         }
@@ -679,6 +774,13 @@ impl ToTokens for DebugByTokens {
                 | ByToken::XPath(lit)
                 | ByToken::Name(lit)
                 | ByToken::ClassName(lit)
+                | ByToken::Role(lit)
+                | ByToken::AccessibleName(lit)
+                | ByToken::Label(lit)
+                | ByToken::Placeholder(lit)
+                | ByToken::Text(lit)
+                | ByToken::TestId(lit)
+                | ByToken::Js(lit)
                 | ByToken::Description(lit) => lit.to_tokens(tokens),
                 // idents
                 ByToken::Multi
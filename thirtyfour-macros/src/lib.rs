@@ -9,6 +9,7 @@ use proc_macro::TokenStream;
 use syn::{parse_macro_input, DeriveInput};
 
 mod component;
+mod selector;
 
 /// Derive macro for a wrapped `Component`.
 ///
@@ -42,6 +43,16 @@ mod component;
 /// - `xpath = "..."`: Select element by XPath.
 /// - `name = "..."`: Select element by name.
 /// - `class = "..."`: Select element by class name.
+/// - `role = "..."`: Select element by ARIA role. Combine with `accessible_name = "..."`
+///   to also match the accessible name.
+/// - `label = "..."`: Select form control by the text of its label.
+/// - `placeholder = "..."`: Select element by placeholder text.
+/// - `text = "..."`: Select element by its visible text.
+/// - `testid = "..."`: Select element by its `data-testid` attribute.
+/// - `js = "..."`: Select elements using Javascript, with the base element as `this`.
+///
+/// CSS and XPath selectors are checked at compile time for mistakes such as unbalanced
+/// brackets or quotes, and `id`, `tag` and `class` must be a single name.
 ///
 /// Optional attributes available within `#[by(..)]` include:
 /// - `single`: (default, single element only) Return `NoSuchElement` if the number of elements
@@ -0,0 +1,121 @@
+//! Compile-time checks for the selectors in `#[by(..)]` attributes.
+//!
+//! These catch the mistakes that would otherwise only show up as an `InvalidSelector`
+//! error when the component is resolved, such as unbalanced brackets or quotes. They are
+//! not a full parser, so a selector that passes may still be rejected by the browser.
+
+/// Check a CSS selector, e.g. `#[by(css = "...")]`.
+pub fn validate_css(css: &str) -> Result<(), String> {
+    for part in split_top_level(css, true, ',')? {
+        let part = part.trim();
+        if part.is_empty() {
+            return Err(format!("invalid CSS selector '{css}': empty selector"));
+        }
+        let is_combinator = |c: char| matches!(c, '>' | '+' | '~');
+        if part.starts_with(is_combinator) || part.ends_with(is_combinator) {
+            return Err(format!(
+                "invalid CSS selector '{css}': '{part}' cannot start or end with a combinator"
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Check an XPath expression, e.g. `#[by(xpath = "...")]`.
+pub fn validate_xpath(xpath: &str) -> Result<(), String> {
+    for part in split_top_level(xpath, false, '|')? {
+        let part = part.trim();
+        if part.is_empty() {
+            return Err(format!("invalid XPath '{xpath}': empty expression"));
+        }
+        if part.len() > 1 && part.ends_with('/') {
+            return Err(format!("invalid XPath '{xpath}': '{part}' cannot end with '/'"));
+        }
+    }
+    Ok(())
+}
+
+/// Check a value that is used as a single name, e.g. `#[by(class = "...")]`.
+pub fn validate_name(kind: &str, name: &str) -> Result<(), String> {
+    if name.is_empty() {
+        return Err(format!("{kind} cannot be empty"));
+    }
+    if name.contains(char::is_whitespace) {
+        return Err(format!("{kind} '{name}' cannot contain whitespace"));
+    }
+    Ok(())
+}
+
+/// Split the selector at each `separator` that is outside brackets and quotes, checking
+/// that the brackets and quotes are balanced.
+///
+/// CSS strings may contain backslash escapes, while XPath strings may not.
+fn split_top_level(selector: &str, escapes: bool, separator: char) -> Result<Vec<&str>, String> {
+    let mut parts = Vec::new();
+    let mut brackets = Vec::new();
+    let mut quote = None;
+    let mut start = 0;
+    let mut chars = selector.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match (quote, c) {
+            (_, '\\') if escapes => {
+                chars.next();
+            }
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '(') => brackets.push(')'),
+            (None, '[') => brackets.push(']'),
+            (None, ')' | ']') if brackets.pop() != Some(c) => {
+                return Err(format!("invalid selector '{selector}': unexpected '{c}'"));
+            }
+            (None, c) if c == separator && brackets.is_empty() => {
+                parts.push(&selector[start..i]);
+                start = i + c.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    if let Some(q) = quote {
+        return Err(format!("invalid selector '{selector}': unterminated string, missing {q}"));
+    }
+    if let Some(b) = brackets.pop() {
+        return Err(format!("invalid selector '{selector}': missing '{b}'"));
+    }
+    parts.push(&selector[start..]);
+    Ok(parts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn css() {
+        assert!(validate_css("form#sign-up > input[name='a,b'], a:not(.x)").is_ok());
+        assert!(validate_css(r#"input[value="say \"hi\""]"#).is_ok());
+        assert!(validate_css("").is_err());
+        assert!(validate_css("div,").is_err());
+        assert!(validate_css("div >").is_err());
+        assert!(validate_css("input[name='a']]").is_err());
+        assert!(validate_css("input[name='a]").is_err());
+        assert!(validate_css("a:not(.x").is_err());
+    }
+
+    #[test]
+    fn xpath() {
+        assert!(validate_xpath("//tr[td[3][contains(., '[')]] | //th").is_ok());
+        assert!(validate_xpath("/").is_ok());
+        assert!(validate_xpath("//div/").is_err());
+        assert!(validate_xpath("//div[@id='a'").is_err());
+        assert!(validate_xpath("//div[@id=\"a]").is_err());
+        assert!(validate_xpath("//div | ").is_err());
+    }
+
+    #[test]
+    fn names() {
+        assert!(validate_name("class", "btn-primary").is_ok());
+        assert!(validate_name("class", "btn primary").is_err());
+        assert!(validate_name("id", "").is_err());
+    }
+}
@@ -0,0 +1,185 @@
+use std::sync::Arc;
+
+use tokio::runtime::Runtime;
+
+use super::{blocking_methods, WebElement};
+use crate::error::WebDriverResult;
+use crate::TypingData;
+
+/// The blocking equivalent of [`crate::action_chain::ActionChain`].
+///
+/// The actions are only sent to the browser when [`ActionChain::perform()`] is called.
+#[derive(Debug)]
+pub struct ActionChain {
+    inner: crate::action_chain::ActionChain,
+    rt: Arc<Runtime>,
+}
+
+impl ActionChain {
+    pub(super) fn new(inner: crate::action_chain::ActionChain, rt: Arc<Runtime>) -> Self {
+        Self {
+            inner,
+            rt,
+        }
+    }
+
+    fn map(
+        self,
+        f: impl FnOnce(crate::action_chain::ActionChain) -> crate::action_chain::ActionChain,
+    ) -> Self {
+        Self {
+            inner: f(self.inner),
+            rt: self.rt,
+        }
+    }
+
+    blocking_methods! {
+        /// Perform the actions in this chain.
+        fn perform(&self) -> ();
+        /// Release all keys and pointer buttons that are currently pressed.
+        fn reset_actions(&self) -> ();
+    }
+
+    /// Click and release the left mouse button.
+    pub fn click(self) -> Self {
+        self.map(|x| x.click())
+    }
+
+    /// Click on the specified element.
+    pub fn click_element(self, element: &WebElement) -> Self {
+        self.map(|x| x.click_element(element.as_async()))
+    }
+
+    /// Click the left mouse button and hold it down.
+    pub fn click_and_hold(self) -> Self {
+        self.map(|x| x.click_and_hold())
+    }
+
+    /// Click on the specified element and hold the left mouse button down.
+    pub fn click_and_hold_element(self, element: &WebElement) -> Self {
+        self.map(|x| x.click_and_hold_element(element.as_async()))
+    }
+
+    /// Click and release the right mouse button.
+    pub fn context_click(self) -> Self {
+        self.map(|x| x.context_click())
+    }
+
+    /// Right-click on the specified element.
+    pub fn context_click_element(self, element: &WebElement) -> Self {
+        self.map(|x| x.context_click_element(element.as_async()))
+    }
+
+    /// Double-click the left mouse button.
+    pub fn double_click(self) -> Self {
+        self.map(|x| x.double_click())
+    }
+
+    /// Double-click on the specified element.
+    pub fn double_click_element(self, element: &WebElement) -> Self {
+        self.map(|x| x.double_click_element(element.as_async()))
+    }
+
+    /// Drag the source element onto the target element.
+    pub fn drag_and_drop_element(self, source: &WebElement, target: &WebElement) -> Self {
+        self.map(|x| x.drag_and_drop_element(source.as_async(), target.as_async()))
+    }
+
+    /// Drag the mouse cursor by the specified offset.
+    pub fn drag_and_drop_by_offset(self, x_offset: i64, y_offset: i64) -> Self {
+        self.map(|x| x.drag_and_drop_by_offset(x_offset, y_offset))
+    }
+
+    /// Drag the specified element by the specified offset.
+    pub fn drag_and_drop_element_by_offset(
+        self,
+        element: &WebElement,
+        x_offset: i64,
+        y_offset: i64,
+    ) -> Self {
+        self.map(|x| x.drag_and_drop_element_by_offset(element.as_async(), x_offset, y_offset))
+    }
+
+    /// Press the specified key down.
+    pub fn key_down<T>(self, value: T) -> Self
+    where
+        T: Into<char>,
+    {
+        self.map(|x| x.key_down(value))
+    }
+
+    /// Click the specified element and then press the specified key down.
+    pub fn key_down_on_element<T>(self, element: &WebElement, value: T) -> Self
+    where
+        T: Into<char>,
+    {
+        self.map(|x| x.key_down_on_element(element.as_async(), value))
+    }
+
+    /// Release the specified key.
+    pub fn key_up<T>(self, value: T) -> Self
+    where
+        T: Into<char>,
+    {
+        self.map(|x| x.key_up(value))
+    }
+
+    /// Click the specified element and release the specified key.
+    pub fn key_up_on_element<T>(self, element: &WebElement, value: T) -> Self
+    where
+        T: Into<char>,
+    {
+        self.map(|x| x.key_up_on_element(element.as_async(), value))
+    }
+
+    /// Move the mouse cursor to the specified coordinates.
+    pub fn move_to(self, x: i64, y: i64) -> Self {
+        self.map(|chain| chain.move_to(x, y))
+    }
+
+    /// Move the mouse cursor by the specified offset.
+    pub fn move_by_offset(self, x_offset: i64, y_offset: i64) -> Self {
+        self.map(|x| x.move_by_offset(x_offset, y_offset))
+    }
+
+    /// Move the mouse cursor to the center of the specified element.
+    pub fn move_to_element_center(self, element: &WebElement) -> Self {
+        self.map(|x| x.move_to_element_center(element.as_async()))
+    }
+
+    /// Move the mouse cursor to the specified offset from the center of the element.
+    pub fn move_to_element_with_offset(
+        self,
+        element: &WebElement,
+        x_offset: i64,
+        y_offset: i64,
+    ) -> Self {
+        self.map(|x| x.move_to_element_with_offset(element.as_async(), x_offset, y_offset))
+    }
+
+    /// Release the left mouse button.
+    pub fn release(self) -> Self {
+        self.map(|x| x.release())
+    }
+
+    /// Move the mouse cursor to the specified element and release the left mouse button.
+    pub fn release_on_element(self, element: &WebElement) -> Self {
+        self.map(|x| x.release_on_element(element.as_async()))
+    }
+
+    /// Send the specified keystrokes to the active element.
+    pub fn send_keys<S>(self, text: S) -> Self
+    where
+        S: Into<TypingData>,
+    {
+        self.map(|x| x.send_keys(text))
+    }
+
+    /// Click on the specified element and send the specified keystrokes.
+    pub fn send_keys_to_element<S>(self, element: &WebElement, text: S) -> Self
+    where
+        S: Into<TypingData>,
+    {
+        self.map(|x| x.send_keys_to_element(element.as_async(), text))
+    }
+}
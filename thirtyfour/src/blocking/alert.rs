@@ -0,0 +1,46 @@
+use std::sync::Arc;
+
+use tokio::runtime::Runtime;
+
+use crate::error::WebDriverResult;
+use crate::session::handle::SessionHandle;
+use crate::TypingData;
+
+/// The blocking equivalent of [`crate::Alert`], for the alert that is currently open.
+///
+/// The same methods are also available on [`WebDriver`](super::WebDriver), e.g.
+/// `accept_alert()`.
+#[derive(Debug)]
+pub struct Alert {
+    handle: Arc<SessionHandle>,
+    rt: Arc<Runtime>,
+}
+
+impl Alert {
+    pub(super) fn new(handle: Arc<SessionHandle>, rt: Arc<Runtime>) -> Self {
+        Self {
+            handle,
+            rt,
+        }
+    }
+
+    /// Get the text of the alert.
+    pub fn text(&self) -> WebDriverResult<String> {
+        self.rt.block_on(self.handle.get_alert_text())
+    }
+
+    /// Dismiss the alert.
+    pub fn dismiss(&self) -> WebDriverResult<()> {
+        self.rt.block_on(self.handle.dismiss_alert())
+    }
+
+    /// Accept the alert.
+    pub fn accept(&self) -> WebDriverResult<()> {
+        self.rt.block_on(self.handle.accept_alert())
+    }
+
+    /// Send the specified keys to the alert.
+    pub fn send_keys(&self, keys: impl Into<TypingData>) -> WebDriverResult<()> {
+        self.rt.block_on(self.handle.send_alert_text(keys))
+    }
+}
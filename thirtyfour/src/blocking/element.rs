@@ -0,0 +1,146 @@
+use std::path::Path;
+use std::sync::Arc;
+
+use serde_json::Value;
+use tokio::runtime::Runtime;
+
+use super::{blocking_methods, ElementQuery, ShadowRoot};
+use crate::common::types::ElementRect;
+use crate::error::WebDriverResult;
+use crate::extensions::query::ElementQueryable;
+use crate::{By, ElementId, IntoArcStr, TypingData};
+
+/// The blocking equivalent of [`crate::WebElement`].
+#[derive(Debug, Clone)]
+pub struct WebElement {
+    inner: crate::WebElement,
+    rt: Arc<Runtime>,
+}
+
+impl PartialEq for WebElement {
+    fn eq(&self, other: &Self) -> bool {
+        self.inner == other.inner
+    }
+}
+
+impl Eq for WebElement {}
+
+impl WebElement {
+    pub(super) fn new(inner: crate::WebElement, rt: Arc<Runtime>) -> Self {
+        Self {
+            inner,
+            rt,
+        }
+    }
+
+    fn wrap(&self, element: crate::WebElement) -> Self {
+        Self::new(element, self.rt.clone())
+    }
+
+    /// The async `WebElement` wrapped by this one.
+    pub fn as_async(&self) -> &crate::WebElement {
+        &self.inner
+    }
+
+    /// Get the internal element id for this element.
+    pub fn element_id(&self) -> ElementId {
+        self.inner.element_id()
+    }
+
+    /// The description of the `ElementQuery` that found this element.
+    pub fn description(&self) -> Option<&str> {
+        self.inner.description()
+    }
+
+    /// Serialize this element to JSON, e.g. to pass it as an argument to a script.
+    pub fn to_json(&self) -> WebDriverResult<Value> {
+        self.inner.to_json()
+    }
+
+    /// Find the first child element matching the specified selector.
+    pub fn find(&self, by: By) -> WebDriverResult<WebElement> {
+        Ok(self.wrap(self.rt.block_on(self.inner.find(by))?))
+    }
+
+    /// Find all child elements matching the specified selector.
+    pub fn find_all(&self, by: By) -> WebDriverResult<Vec<WebElement>> {
+        let elements = self.rt.block_on(self.inner.find_all(by))?;
+        Ok(elements.into_iter().map(|x| self.wrap(x)).collect())
+    }
+
+    /// Start an element query for the children of this element.
+    pub fn query(&self, by: By) -> ElementQuery {
+        ElementQuery::new(self.inner.query(by), self.rt.clone())
+    }
+
+    /// Get the parent of this element.
+    pub fn parent(&self) -> WebDriverResult<WebElement> {
+        Ok(self.wrap(self.rt.block_on(self.inner.parent())?))
+    }
+
+    /// Get the shadow root of this element.
+    pub fn get_shadow_root(&self) -> WebDriverResult<ShadowRoot> {
+        let root = self.rt.block_on(self.inner.get_shadow_root())?;
+        Ok(ShadowRoot::new(root, self.rt.clone()))
+    }
+
+    /// Drag this element to the target element, using Javascript.
+    pub fn js_drag_to(&self, target: &Self) -> WebDriverResult<()> {
+        self.rt.block_on(self.inner.js_drag_to(&target.inner))
+    }
+
+    blocking_methods! {
+        /// Get the bounding rectangle for this element.
+        fn rect(&self) -> ElementRect;
+        /// Get the tag name for this element.
+        fn tag_name(&self) -> String;
+        /// Get the class name for this element.
+        fn class_name(&self) -> Option<String>;
+        /// Get the id for this element.
+        fn id(&self) -> Option<String>;
+        /// Get the text contents for this element.
+        fn text(&self) -> String;
+        /// Get the computed WAI-ARIA role of this element.
+        fn computed_role(&self) -> String;
+        /// Get the accessible name of this element, as computed by the browser.
+        fn computed_label(&self) -> String;
+        /// Get the value of this element, e.g. for an input.
+        fn value(&self) -> Option<String>;
+        /// Click this element.
+        fn click(&self) -> ();
+        /// Clear this element's contents.
+        fn clear(&self) -> ();
+        /// Get the specified property.
+        fn prop(&self, name: impl IntoArcStr) -> Option<String>;
+        /// Get the specified attribute.
+        fn attr(&self, name: impl IntoArcStr) -> Option<String>;
+        /// Get the specified CSS property.
+        fn css_value(&self, name: impl IntoArcStr) -> String;
+        /// Return true if this element is selected.
+        fn is_selected(&self) -> bool;
+        /// Return true if this element is displayed.
+        fn is_displayed(&self) -> bool;
+        /// Return true if this element is enabled.
+        fn is_enabled(&self) -> bool;
+        /// Return true if this element is displayed and enabled.
+        fn is_clickable(&self) -> bool;
+        /// Return true if this element is still present in the document.
+        fn is_present(&self) -> bool;
+        /// Send the specified input to this element.
+        fn send_keys(&self, keys: impl Into<TypingData>) -> ();
+        /// Select the file at the specified local path in this file input.
+        fn upload_file(&self, path: &Path) -> ();
+        /// Take a screenshot of this element and return it as PNG bytes.
+        fn screenshot_as_png(&self) -> Vec<u8>;
+        /// Take a screenshot of this element and write it to the specified filename.
+        fn screenshot(&self, path: &Path) -> ();
+        /// Focus this element, using Javascript.
+        fn focus(&self) -> ();
+        /// Scroll this element into view, using Javascript.
+        fn scroll_into_view(&self) -> ();
+        /// Get the innerHtml property of this element.
+        fn inner_html(&self) -> String;
+        /// Get the outerHtml property of this element.
+        fn outer_html(&self) -> String;
+    }
+}
@@ -0,0 +1,337 @@
+//! A synchronous API for `thirtyfour`, for code that does not use async/await.
+//!
+//! Each type here wraps the async type of the same name, and exposes the same methods
+//! without `async`. A blocking [`WebDriver`] owns a tokio runtime, which is shared by
+//! all elements, queries and action chains created from it. Commands run through the
+//! same `WebDriverConfig`, command layers and listeners as the async API, and return
+//! the same [`WebDriverError`](crate::error::WebDriverError).
+//!
+//! The blocking types must not be used from within an async runtime, because they
+//! block the current thread until each command has completed.
+//!
+//! # Example
+//! ```no_run
+//! use thirtyfour::blocking::WebDriver;
+//! use thirtyfour::prelude::*;
+//!
+//! fn main() -> WebDriverResult<()> {
+//!     let caps = DesiredCapabilities::chrome();
+//!     let driver = WebDriver::new("http://localhost:9515", caps)?;
+//!     driver.goto("https://wikipedia.org")?;
+//!
+//!     let elem_text = driver.find(By::Id("searchInput"))?;
+//!     elem_text.send_keys("selenium")?;
+//!     driver.query(By::Css("button[type='submit']")).first()?.click()?;
+//!
+//!     driver.query(By::ClassName("firstHeading")).first()?;
+//!     assert_eq!(driver.title()?, "Selenium - Wikipedia");
+//!     driver.quit()
+//! }
+//! ```
+
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde_json::Value;
+use tokio::runtime::Runtime;
+use url::Url;
+
+use crate::common::config::WebDriverConfig;
+use crate::common::types::Rect;
+use crate::error::WebDriverResult;
+use crate::extensions::query::ElementQueryable;
+use crate::session::http::HttpClient;
+use crate::session::scriptret::ScriptRet;
+use crate::{
+    By, Capabilities, Cookie, IntoArcStr, SessionId, TimeoutConfiguration, TypingData, WindowHandle,
+};
+
+mod action_chain;
+mod alert;
+mod element;
+mod query;
+mod shadow_root;
+
+pub use action_chain::ActionChain;
+pub use alert::Alert;
+pub use element::WebElement;
+pub use query::ElementQuery;
+pub use shadow_root::ShadowRoot;
+
+/// Generate blocking methods that run the async method of the same name on `self.inner`.
+macro_rules! blocking_methods {
+    ($($(#[$attr:meta])* fn $name:ident(&self $(, $arg:ident: $ty:ty)*) -> $ret:ty;)*) => {
+        $(
+            $(#[$attr])*
+            pub fn $name(&self $(, $arg: $ty)*) -> WebDriverResult<$ret> {
+                self.rt.block_on(self.inner.$name($($arg),*))
+            }
+        )*
+    };
+}
+use blocking_methods;
+
+/// Create the runtime that runs the commands for a blocking session.
+///
+/// A worker thread keeps driving the HTTP connections between commands, so that the
+/// session can still be quit when the last `WebDriver` is dropped.
+fn new_runtime() -> WebDriverResult<Arc<Runtime>> {
+    let rt = tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(1)
+        .thread_name("thirtyfour-blocking")
+        .enable_all()
+        .build()?;
+    Ok(Arc::new(rt))
+}
+
+/// The blocking equivalent of [`crate::WebDriver`].
+///
+/// See the [module documentation](self) for an example.
+#[derive(Debug, Clone)]
+pub struct WebDriver {
+    // Declared before the runtime, so that the session is quit while the runtime is alive.
+    inner: crate::WebDriver,
+    rt: Arc<Runtime>,
+}
+
+impl WebDriver {
+    /// Create a new WebDriver session. See [`crate::WebDriver::new()`].
+    #[cfg(feature = "reqwest")]
+    pub fn new<S, C>(server_url: S, capabilities: C) -> WebDriverResult<Self>
+    where
+        S: Into<String>,
+        C: Into<Capabilities>,
+    {
+        Self::new_with_config(server_url, capabilities, WebDriverConfig::default())
+    }
+
+    /// Create a new WebDriver session with the specified config.
+    ///
+    /// See [`crate::WebDriver::new_with_config()`].
+    #[cfg(feature = "reqwest")]
+    pub fn new_with_config<S, C>(
+        server_url: S,
+        capabilities: C,
+        config: WebDriverConfig,
+    ) -> WebDriverResult<Self>
+    where
+        S: Into<String>,
+        C: Into<Capabilities>,
+    {
+        let rt = new_runtime()?;
+        let inner =
+            rt.block_on(crate::WebDriver::new_with_config(server_url, capabilities, config))?;
+        Ok(Self::from_async(inner, rt))
+    }
+
+    /// Create a new WebDriver session with the specified config and HTTP client.
+    ///
+    /// See [`crate::WebDriver::new_with_config_and_client()`].
+    pub fn new_with_config_and_client<S, C>(
+        server_url: S,
+        capabilities: C,
+        config: WebDriverConfig,
+        client: impl HttpClient,
+    ) -> WebDriverResult<Self>
+    where
+        S: Into<String>,
+        C: Into<Capabilities>,
+    {
+        let rt = new_runtime()?;
+        let inner = rt.block_on(crate::WebDriver::new_with_config_and_client(
+            server_url,
+            capabilities,
+            config,
+            client,
+        ))?;
+        Ok(Self::from_async(inner, rt))
+    }
+
+    fn from_async(inner: crate::WebDriver, rt: Arc<Runtime>) -> Self {
+        Self {
+            inner,
+            rt,
+        }
+    }
+
+    /// The async `WebDriver` wrapped by this one.
+    pub fn as_async(&self) -> &crate::WebDriver {
+        &self.inner
+    }
+
+    /// Wrap an element returned by the async API, e.g. from [`ScriptRet::element()`].
+    pub fn wrap_element(&self, element: crate::WebElement) -> WebElement {
+        WebElement::new(element, self.rt.clone())
+    }
+
+    /// Wrap a shadow root returned by the async API, e.g. from [`ScriptRet::shadow_root()`].
+    pub fn wrap_shadow_root(&self, root: crate::ShadowRoot) -> ShadowRoot {
+        ShadowRoot::new(root, self.rt.clone())
+    }
+
+    /// The session id.
+    pub fn session_id(&self) -> &SessionId {
+        self.inner.session_id()
+    }
+
+    /// The id of the session that commands are currently sent to.
+    ///
+    /// See [`SessionHandle::current_session_id()`].
+    ///
+    /// [`SessionHandle::current_session_id()`]: crate::session::handle::SessionHandle::current_session_id
+    pub fn current_session_id(&self) -> SessionId {
+        self.inner.current_session_id()
+    }
+
+    /// The configuration used by this session.
+    pub fn config(&self) -> &WebDriverConfig {
+        self.inner.config()
+    }
+
+    /// End the session. See [`crate::WebDriver::quit()`].
+    pub fn quit(self) -> WebDriverResult<()> {
+        let Self {
+            inner,
+            rt,
+        } = self;
+        rt.block_on(inner.quit())
+    }
+
+    /// Find the first element matching the specified selector.
+    pub fn find(&self, by: By) -> WebDriverResult<WebElement> {
+        Ok(self.wrap_element(self.rt.block_on(self.inner.find(by))?))
+    }
+
+    /// Find all elements matching the specified selector.
+    pub fn find_all(&self, by: By) -> WebDriverResult<Vec<WebElement>> {
+        let elements = self.rt.block_on(self.inner.find_all(by))?;
+        Ok(elements.into_iter().map(|x| self.wrap_element(x)).collect())
+    }
+
+    /// Start an element query using the specified selector.
+    ///
+    /// See [`crate::extensions::query::ElementQuery`].
+    pub fn query(&self, by: By) -> ElementQuery {
+        ElementQuery::new(self.inner.query(by), self.rt.clone())
+    }
+
+    /// Get the active element for this session.
+    pub fn active_element(&self) -> WebDriverResult<WebElement> {
+        Ok(self.wrap_element(self.rt.block_on(self.inner.active_element())?))
+    }
+
+    /// Execute the specified Javascript synchronously and return the result.
+    ///
+    /// Elements passed as arguments can be serialized with [`WebElement::to_json()`].
+    pub fn execute(
+        &self,
+        script: impl IntoArcStr,
+        args: impl Into<Arc<[Value]>>,
+    ) -> WebDriverResult<ScriptRet> {
+        self.rt.block_on(self.inner.execute(script, args))
+    }
+
+    /// Execute the specified Javascript asynchronously and return the result.
+    pub fn execute_async(
+        &self,
+        script: impl IntoArcStr,
+        args: impl Into<Arc<[Value]>>,
+    ) -> WebDriverResult<ScriptRet> {
+        self.rt.block_on(self.inner.execute_async(script, args))
+    }
+
+    /// Create a new action chain for this session.
+    pub fn action_chain(&self) -> ActionChain {
+        ActionChain::new(self.inner.action_chain(), self.rt.clone())
+    }
+
+    /// Interact with the alert that is currently open.
+    pub fn alert(&self) -> Alert {
+        Alert::new(self.inner.handle.clone(), self.rt.clone())
+    }
+
+    /// Switch to the specified iframe element.
+    pub fn enter_frame_element(&self, frame_element: &WebElement) -> WebDriverResult<()> {
+        self.rt.block_on(frame_element.as_async().clone().enter_frame())
+    }
+
+    blocking_methods! {
+        /// Navigate to the specified URL.
+        fn goto(&self, url: impl IntoArcStr) -> ();
+        /// Get the current URL.
+        fn current_url(&self) -> Url;
+        /// Get the page source as a String.
+        fn source(&self) -> String;
+        /// Get the page title.
+        fn title(&self) -> String;
+        /// Go back. This is equivalent to clicking the browser's back button.
+        fn back(&self) -> ();
+        /// Go forward. This is equivalent to clicking the browser's forward button.
+        fn forward(&self) -> ();
+        /// Refresh the current page.
+        fn refresh(&self) -> ();
+        /// Close the current window or tab.
+        fn close_window(&self) -> ();
+        /// Get the current window handle.
+        fn window(&self) -> WindowHandle;
+        /// Get all window handles for the current session.
+        fn windows(&self) -> Vec<WindowHandle>;
+        /// Switch to the specified window.
+        fn switch_to_window(&self, handle: WindowHandle) -> ();
+        /// Switch to the window with the specified name.
+        fn switch_to_named_window(&self, name: &str) -> ();
+        /// Open a new window, and return its handle.
+        fn new_window(&self) -> WindowHandle;
+        /// Open a new tab, and return its handle.
+        fn new_tab(&self) -> WindowHandle;
+        /// Maximize the current window.
+        fn maximize_window(&self) -> ();
+        /// Minimize the current window.
+        fn minimize_window(&self) -> ();
+        /// Make the current window fullscreen.
+        fn fullscreen_window(&self) -> ();
+        /// Get the current window rectangle, in pixels.
+        fn get_window_rect(&self) -> Rect;
+        /// Set the current window rectangle, in pixels.
+        fn set_window_rect(&self, x: u32, y: u32, width: u32, height: u32) -> ();
+        /// Switch to the default frame.
+        fn enter_default_frame(&self) -> ();
+        /// Switch to the iframe with the specified index.
+        fn enter_frame(&self, frame_number: u16) -> ();
+        /// Switch to the parent frame.
+        fn enter_parent_frame(&self) -> ();
+        /// Get the timeouts for the current session.
+        fn get_timeouts(&self) -> TimeoutConfiguration;
+        /// Set the timeouts for the current session.
+        fn update_timeouts(&self, timeouts: TimeoutConfiguration) -> ();
+        /// Set the implicit wait timeout.
+        fn set_implicit_wait_timeout(&self, time_to_wait: Duration) -> ();
+        /// Set the script timeout.
+        fn set_script_timeout(&self, time_to_wait: Duration) -> ();
+        /// Set the page load timeout.
+        fn set_page_load_timeout(&self, time_to_wait: Duration) -> ();
+        /// Get all cookies.
+        fn get_all_cookies(&self) -> Vec<Cookie>;
+        /// Get the cookie with the specified name.
+        fn get_named_cookie(&self, name: impl IntoArcStr) -> Cookie;
+        /// Add the specified cookie.
+        fn add_cookie(&self, cookie: Cookie) -> ();
+        /// Delete the cookie with the specified name.
+        fn delete_cookie(&self, name: impl IntoArcStr) -> ();
+        /// Delete all cookies.
+        fn delete_all_cookies(&self) -> ();
+        /// Take a screenshot of the current window and return it as PNG bytes.
+        fn screenshot_as_png(&self) -> Vec<u8>;
+        /// Take a screenshot of the current window and write it to the specified filename.
+        fn screenshot(&self, path: &Path) -> ();
+        /// Get the text of the alert that is currently open.
+        fn get_alert_text(&self) -> String;
+        /// Dismiss the alert that is currently open.
+        fn dismiss_alert(&self) -> ();
+        /// Accept the alert that is currently open.
+        fn accept_alert(&self) -> ();
+        /// Send the specified keys to the alert that is currently open.
+        fn send_alert_text(&self, keys: impl Into<TypingData>) -> ();
+    }
+}
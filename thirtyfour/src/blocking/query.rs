@@ -0,0 +1,446 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use stringmatch::Needle;
+use tokio::runtime::Runtime;
+
+use super::WebElement;
+use crate::error::WebDriverResult;
+use crate::extensions::query::{ElementQueryOptions, IntoElementPoller};
+use crate::{By, ElementPredicate, IntoArcStr};
+
+/// The blocking equivalent of [`crate::extensions::query::ElementQuery`].
+///
+/// # Example:
+/// ```no_run
+/// # use thirtyfour::blocking::WebDriver;
+/// # use thirtyfour::prelude::*;
+/// #
+/// # fn main() -> WebDriverResult<()> {
+/// #     let caps = DesiredCapabilities::chrome();
+/// #     let driver = WebDriver::new("http://localhost:4444", caps)?;
+/// let elem = driver.query(By::Css("button")).with_text("Submit").and_clickable().first()?;
+/// elem.click()?;
+/// #     driver.quit()
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ElementQuery {
+    inner: crate::extensions::query::ElementQuery,
+    rt: Arc<Runtime>,
+}
+
+impl ElementQuery {
+    pub(super) fn new(inner: crate::extensions::query::ElementQuery, rt: Arc<Runtime>) -> Self {
+        Self {
+            inner,
+            rt,
+        }
+    }
+
+    fn map(
+        self,
+        f: impl FnOnce(crate::extensions::query::ElementQuery) -> crate::extensions::query::ElementQuery,
+    ) -> Self {
+        Self {
+            inner: f(self.inner),
+            rt: self.rt,
+        }
+    }
+
+    fn wrap(&self, elements: Vec<crate::WebElement>) -> Vec<WebElement> {
+        elements.into_iter().map(|x| WebElement::new(x, self.rt.clone())).collect()
+    }
+
+    //
+    // Retrievers
+    //
+
+    /// Return true if an element matches any selector (including filters), otherwise false.
+    pub fn exists(&self) -> WebDriverResult<bool> {
+        self.rt.block_on(self.inner.exists())
+    }
+
+    /// Return true if no element matches any selector (including filters), otherwise false.
+    pub fn not_exists(&self) -> WebDriverResult<bool> {
+        self.rt.block_on(self.inner.not_exists())
+    }
+
+    /// Return the first element that matches any selector (including filters).
+    ///
+    /// Returns None if no elements match.
+    pub fn first_opt(&self) -> WebDriverResult<Option<WebElement>> {
+        let element = self.rt.block_on(self.inner.first_opt())?;
+        Ok(self.wrap(element.into_iter().collect()).pop())
+    }
+
+    /// Return only the first element that matches any selector (including filters).
+    ///
+    /// Returns Err(WebDriverError::NoSuchElement) if no elements match.
+    pub fn first(&self) -> WebDriverResult<WebElement> {
+        let element = self.rt.block_on(self.inner.first())?;
+        Ok(WebElement::new(element, self.rt.clone()))
+    }
+
+    /// Return only a single element that matches any selector (including filters).
+    ///
+    /// Returns Err(WebDriverError::NoSuchElement) unless exactly one element matches.
+    pub fn single(&self) -> WebDriverResult<WebElement> {
+        let element = self.rt.block_on(self.inner.single())?;
+        Ok(WebElement::new(element, self.rt.clone()))
+    }
+
+    /// Return all elements that match any selector (including filters).
+    ///
+    /// Returns an empty Vec if no elements match.
+    pub fn any(&self) -> WebDriverResult<Vec<WebElement>> {
+        Ok(self.wrap(self.rt.block_on(self.inner.any())?))
+    }
+
+    /// Return all elements that match any selector (including filters).
+    ///
+    /// Returns Err(WebDriverError::NoSuchElement) if no elements match.
+    pub fn any_required(&self) -> WebDriverResult<Vec<WebElement>> {
+        Ok(self.wrap(self.rt.block_on(self.inner.any_required())?))
+    }
+
+    /// Return all elements that match a single selector (including filters).
+    ///
+    /// Returns an empty Vec if no elements match.
+    pub fn all_from_selector(&self) -> WebDriverResult<Vec<WebElement>> {
+        Ok(self.wrap(self.rt.block_on(self.inner.all_from_selector())?))
+    }
+
+    /// Return all elements that match a single selector (including filters).
+    ///
+    /// Returns Err(WebDriverError::NoSuchElement) if no elements match.
+    pub fn all_from_selector_required(&self) -> WebDriverResult<Vec<WebElement>> {
+        Ok(self.wrap(self.rt.block_on(self.inner.all_from_selector_required())?))
+    }
+
+    //
+    // Options
+    //
+
+    /// Provide the options to use with this query.
+    pub fn options(self, options: ElementQueryOptions) -> Self {
+        self.map(|x| x.options(options))
+    }
+
+    /// Provide a name that will be included in the error message if the query was not
+    /// successful.
+    pub fn desc(self, description: &str) -> Self {
+        self.map(|x| x.desc(description))
+    }
+
+    /// Set whether to return early if an error occurs while polling for the element(s).
+    pub fn ignore_errors(self, ignore: bool) -> Self {
+        self.map(|x| x.ignore_errors(ignore))
+    }
+
+    /// Use the specified ElementPoller for this query.
+    pub fn with_poller(self, poller: Arc<dyn IntoElementPoller + Send + Sync>) -> Self {
+        self.map(|x| x.with_poller(poller))
+    }
+
+    /// Wait for the specified timeout, polling once after each interval.
+    pub fn wait(self, timeout: Duration, interval: Duration) -> Self {
+        self.map(|x| x.wait(timeout, interval))
+    }
+
+    /// Do not wait for the element(s).
+    pub fn nowait(self) -> Self {
+        self.map(|x| x.nowait())
+    }
+
+    /// Add a new selector to this query.
+    pub fn or(self, by: By) -> Self {
+        self.map(|x| x.or(by))
+    }
+
+    //
+    // Filters
+    //
+
+    /// Add the specified ElementPredicate to the last selector.
+    pub fn with_filter(self, f: impl ElementPredicate + 'static) -> Self {
+        self.map(|x| x.with_filter(f))
+    }
+
+    /// Only match elements that are enabled.
+    pub fn and_enabled(self) -> Self {
+        self.map(|x| x.and_enabled())
+    }
+
+    /// Only match elements that are NOT enabled.
+    pub fn and_not_enabled(self) -> Self {
+        self.map(|x| x.and_not_enabled())
+    }
+
+    /// Only match elements that are selected.
+    pub fn and_selected(self) -> Self {
+        self.map(|x| x.and_selected())
+    }
+
+    /// Only match elements that are NOT selected.
+    pub fn and_not_selected(self) -> Self {
+        self.map(|x| x.and_not_selected())
+    }
+
+    /// Only match elements that are displayed.
+    pub fn and_displayed(self) -> Self {
+        self.map(|x| x.and_displayed())
+    }
+
+    /// Only match elements that are NOT displayed.
+    pub fn and_not_displayed(self) -> Self {
+        self.map(|x| x.and_not_displayed())
+    }
+
+    /// Only match elements that are clickable.
+    pub fn and_clickable(self) -> Self {
+        self.map(|x| x.and_clickable())
+    }
+
+    /// Only match elements that are NOT clickable.
+    pub fn and_not_clickable(self) -> Self {
+        self.map(|x| x.and_not_clickable())
+    }
+
+    /// Only match elements that have the specified text.
+    pub fn with_text<N>(self, text: N) -> Self
+    where
+        N: Needle + Clone + Send + Sync + 'static,
+    {
+        self.map(|x| x.with_text(text))
+    }
+
+    /// Only match elements that do not have the specified text.
+    pub fn without_text<N>(self, text: N) -> Self
+    where
+        N: Needle + Clone + Send + Sync + 'static,
+    {
+        self.map(|x| x.without_text(text))
+    }
+
+    /// Only match elements that have the specified id.
+    pub fn with_id<N>(self, id: N) -> Self
+    where
+        N: Needle + Clone + Send + Sync + 'static,
+    {
+        self.map(|x| x.with_id(id))
+    }
+
+    /// Only match elements that do not have the specified id.
+    pub fn without_id<N>(self, id: N) -> Self
+    where
+        N: Needle + Clone + Send + Sync + 'static,
+    {
+        self.map(|x| x.without_id(id))
+    }
+
+    /// Only match elements that have the specified class name.
+    pub fn with_class<N>(self, class_name: N) -> Self
+    where
+        N: Needle + Clone + Send + Sync + 'static,
+    {
+        self.map(|x| x.with_class(class_name))
+    }
+
+    /// Only match elements that do not have the specified class name.
+    pub fn without_class<N>(self, class_name: N) -> Self
+    where
+        N: Needle + Clone + Send + Sync + 'static,
+    {
+        self.map(|x| x.without_class(class_name))
+    }
+
+    /// Only match elements that have the specified tag.
+    pub fn with_tag<N>(self, tag_name: N) -> Self
+    where
+        N: Needle + Clone + Send + Sync + 'static,
+    {
+        self.map(|x| x.with_tag(tag_name))
+    }
+
+    /// Only match elements that do not have the specified tag.
+    pub fn without_tag<N>(self, tag_name: N) -> Self
+    where
+        N: Needle + Clone + Send + Sync + 'static,
+    {
+        self.map(|x| x.without_tag(tag_name))
+    }
+
+    /// Only match elements that have the specified computed WAI-ARIA role.
+    pub fn with_role<N>(self, role: N) -> Self
+    where
+        N: Needle + Clone + Send + Sync + 'static,
+    {
+        self.map(|x| x.with_role(role))
+    }
+
+    /// Only match elements that do not have the specified computed WAI-ARIA role.
+    pub fn without_role<N>(self, role: N) -> Self
+    where
+        N: Needle + Clone + Send + Sync + 'static,
+    {
+        self.map(|x| x.without_role(role))
+    }
+
+    /// Only match elements that have the specified accessible name.
+    pub fn with_accessible_name<N>(self, name: N) -> Self
+    where
+        N: Needle + Clone + Send + Sync + 'static,
+    {
+        self.map(|x| x.with_accessible_name(name))
+    }
+
+    /// Only match elements that do not have the specified accessible name.
+    pub fn without_accessible_name<N>(self, name: N) -> Self
+    where
+        N: Needle + Clone + Send + Sync + 'static,
+    {
+        self.map(|x| x.without_accessible_name(name))
+    }
+
+    /// Only match elements that have the specified value.
+    pub fn with_value<N>(self, value: N) -> Self
+    where
+        N: Needle + Clone + Send + Sync + 'static,
+    {
+        self.map(|x| x.with_value(value))
+    }
+
+    /// Only match elements that do not have the specified value.
+    pub fn without_value<N>(self, value: N) -> Self
+    where
+        N: Needle + Clone + Send + Sync + 'static,
+    {
+        self.map(|x| x.without_value(value))
+    }
+
+    /// Only match elements that have the specified attribute with the specified value.
+    pub fn with_attribute<S, N>(self, attribute_name: S, value: N) -> Self
+    where
+        S: IntoArcStr,
+        N: Needle + Clone + Send + Sync + 'static,
+    {
+        self.map(|x| x.with_attribute(attribute_name, value))
+    }
+
+    /// Only match elements that do not have the specified attribute with the specified value.
+    pub fn without_attribute<S, N>(self, attribute_name: S, value: N) -> Self
+    where
+        S: IntoArcStr,
+        N: Needle + Clone + Send + Sync + 'static,
+    {
+        self.map(|x| x.without_attribute(attribute_name, value))
+    }
+
+    /// Only match elements that have all the specified attributes with the specified values.
+    pub fn with_attributes<S, N>(self, desired_attributes: impl IntoIterator<Item = (S, N)>) -> Self
+    where
+        S: IntoArcStr,
+        N: Needle + Send + Sync + 'static,
+    {
+        self.map(|x| x.with_attributes(desired_attributes))
+    }
+
+    /// Only match elements that do not have any of the specified attributes with the
+    /// specified values.
+    pub fn without_attributes<S, N>(
+        self,
+        desired_attributes: impl IntoIterator<Item = (S, N)>,
+    ) -> Self
+    where
+        S: IntoArcStr,
+        N: Needle + Send + Sync + 'static,
+    {
+        self.map(|x| x.without_attributes(desired_attributes))
+    }
+
+    /// Only match elements that have the specified property with the specified value.
+    pub fn with_property<S, N>(self, property_name: S, value: N) -> Self
+    where
+        S: IntoArcStr,
+        N: Needle + Clone + Send + Sync + 'static,
+    {
+        self.map(|x| x.with_property(property_name, value))
+    }
+
+    /// Only match elements that do not have the specified property with the specified value.
+    pub fn without_property<S, N>(self, property_name: S, value: N) -> Self
+    where
+        S: IntoArcStr,
+        N: Needle + Clone + Send + Sync + 'static,
+    {
+        self.map(|x| x.without_property(property_name, value))
+    }
+
+    /// Only match elements that have all the specified properties with the specified values.
+    pub fn with_properties<S, N>(self, desired_properties: impl IntoIterator<Item = (S, N)>) -> Self
+    where
+        S: IntoArcStr,
+        N: Needle + Send + Sync + 'static,
+    {
+        self.map(|x| x.with_properties(desired_properties))
+    }
+
+    /// Only match elements that do not have any of the specified properties with the
+    /// specified values.
+    pub fn without_properties<S, N>(
+        self,
+        desired_properties: impl IntoIterator<Item = (S, N)>,
+    ) -> Self
+    where
+        S: IntoArcStr,
+        N: Needle + Send + Sync + 'static,
+    {
+        self.map(|x| x.without_properties(desired_properties))
+    }
+
+    /// Only match elements that have the specified CSS property with the specified value.
+    pub fn with_css_property<S, N>(self, css_property_name: S, value: N) -> Self
+    where
+        S: IntoArcStr,
+        N: Needle + Clone + Send + Sync + 'static,
+    {
+        self.map(|x| x.with_css_property(css_property_name, value))
+    }
+
+    /// Only match elements that do not have the specified CSS property with the
+    /// specified value.
+    pub fn without_css_property<S, N>(self, css_property_name: S, value: N) -> Self
+    where
+        S: IntoArcStr,
+        N: Needle + Clone + Send + Sync + 'static,
+    {
+        self.map(|x| x.without_css_property(css_property_name, value))
+    }
+
+    /// Only match elements that have all the specified CSS properties with the
+    /// specified values.
+    pub fn with_css_properties<S, N>(
+        self,
+        desired_css_properties: impl IntoIterator<Item = (S, N)>,
+    ) -> Self
+    where
+        S: IntoArcStr,
+        N: Needle + Send + Sync + 'static,
+    {
+        self.map(|x| x.with_css_properties(desired_css_properties))
+    }
+
+    /// Only match elements that do not have any of the specified CSS properties with the
+    /// specified values.
+    pub fn without_css_properties<S, N>(
+        self,
+        desired_css_properties: impl IntoIterator<Item = (S, N)>,
+    ) -> Self
+    where
+        S: IntoArcStr,
+        N: Needle + Send + Sync + 'static,
+    {
+        self.map(|x| x.without_css_properties(desired_css_properties))
+    }
+}
@@ -0,0 +1,68 @@
+use std::sync::Arc;
+
+use serde_json::Value;
+use tokio::runtime::Runtime;
+
+use super::{ElementQuery, WebElement};
+use crate::error::WebDriverResult;
+use crate::extensions::query::ElementQueryable;
+use crate::{By, ShadowRootId};
+
+/// The blocking equivalent of [`crate::ShadowRoot`].
+#[derive(Debug, Clone)]
+pub struct ShadowRoot {
+    inner: crate::ShadowRoot,
+    rt: Arc<Runtime>,
+}
+
+impl PartialEq for ShadowRoot {
+    fn eq(&self, other: &Self) -> bool {
+        self.inner == other.inner
+    }
+}
+
+impl Eq for ShadowRoot {}
+
+impl ShadowRoot {
+    pub(super) fn new(inner: crate::ShadowRoot, rt: Arc<Runtime>) -> Self {
+        Self {
+            inner,
+            rt,
+        }
+    }
+
+    fn wrap(&self, element: crate::WebElement) -> WebElement {
+        WebElement::new(element, self.rt.clone())
+    }
+
+    /// The async `ShadowRoot` wrapped by this one.
+    pub fn as_async(&self) -> &crate::ShadowRoot {
+        &self.inner
+    }
+
+    /// Get the internal id for this shadow root.
+    pub fn shadow_root_id(&self) -> ShadowRootId {
+        self.inner.shadow_root_id()
+    }
+
+    /// Serialize this shadow root to JSON, e.g. to pass it as an argument to a script.
+    pub fn to_json(&self) -> WebDriverResult<Value> {
+        self.inner.to_json()
+    }
+
+    /// Find the first element within this shadow root matching the specified selector.
+    pub fn find(&self, by: By) -> WebDriverResult<WebElement> {
+        Ok(self.wrap(self.rt.block_on(self.inner.find(by))?))
+    }
+
+    /// Find all elements within this shadow root matching the specified selector.
+    pub fn find_all(&self, by: By) -> WebDriverResult<Vec<WebElement>> {
+        let elements = self.rt.block_on(self.inner.find_all(by))?;
+        Ok(elements.into_iter().map(|x| self.wrap(x)).collect())
+    }
+
+    /// Start an element query for the elements within this shadow root.
+    pub fn query(&self, by: By) -> ElementQuery {
+        ElementQuery::new(self.inner.query(by), self.rt.clone())
+    }
+}
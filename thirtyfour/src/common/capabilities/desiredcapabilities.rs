@@ -25,6 +25,7 @@ const W3C_CAPABILITY_NAMES: &[&str] = &[
     "timeouts",
     "unhandledPromptBehavior",
     "strictFileInteractability",
+    "webSocketUrl",
 ];
 
 const OSS_W3C_CONVERSION: &[(&str, &str)] = &[
@@ -177,6 +178,22 @@ pub trait CapabilitiesHelper {
         self.set_base_capability("acceptInsecureCerts", enabled)
     }
 
+    /// Set whether the remote end should provide a WebDriver BiDi WebSocket URL.
+    ///
+    /// When enabled, the `webSocketUrl` capability returned from the new session
+    /// can be used to open a BiDi connection.
+    fn set_web_socket_url(&mut self, enabled: bool) -> WebDriverResult<()> {
+        self.set_base_capability("webSocketUrl", enabled)
+    }
+
+    /// Set whether Selenium Grid should keep the files downloaded by the browser, so that
+    /// they can be fetched with `WebDriver::download_file()`.
+    ///
+    /// This requires Selenium Grid 4, with the node started with `--enable-managed-downloads`.
+    fn set_downloads_enabled(&mut self, enabled: bool) -> WebDriverResult<()> {
+        self.set_base_capability("se:downloadsEnabled", enabled)
+    }
+
     /// Set whether the session can rotate the current page's layout between portrait and landscape
     /// orientations. Only applies to mobile platforms.
     fn set_rotatable(&mut self, enabled: bool) -> WebDriverResult<()> {
@@ -14,3 +14,5 @@ pub mod ie;
 pub mod opera;
 /// Capabilities for Safari.
 pub mod safari;
+/// Capabilities negotiated for a running session.
+pub mod session;
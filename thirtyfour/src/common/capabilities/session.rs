@@ -0,0 +1,114 @@
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::Capabilities;
+
+/// The capabilities negotiated for a session, as returned from the WebDriver server.
+///
+/// Unlike the capabilities that were requested, these describe what the driver
+/// actually granted, e.g. the exact browser version or the debugger address of the
+/// browser that was launched.
+///
+/// # Example:
+/// ```no_run
+/// # use thirtyfour::prelude::*;
+/// # use thirtyfour::support::block_on;
+/// #
+/// # fn main() -> WebDriverResult<()> {
+/// #     block_on(async {
+/// let caps = DesiredCapabilities::chrome();
+/// let driver = WebDriver::new("http://localhost:4444", caps).await?;
+/// let caps = driver.capabilities();
+/// assert_eq!(caps.browser_name(), Some("chrome"));
+/// println!("running {:?} on {:?}", caps.browser_version(), caps.platform_name());
+/// #         driver.quit().await?;
+/// #         Ok(())
+/// #     })
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SessionCapabilities {
+    capabilities: Capabilities,
+}
+
+impl SessionCapabilities {
+    /// Create a new `SessionCapabilities` from the capabilities returned by the server.
+    pub fn new(capabilities: Capabilities) -> Self {
+        Self {
+            capabilities,
+        }
+    }
+
+    /// The raw capabilities JSON.
+    pub fn raw(&self) -> &Capabilities {
+        &self.capabilities
+    }
+
+    /// Get the capability with the specified key, as a JSON value.
+    pub fn get_json(&self, key: &str) -> Option<&Value> {
+        self.capabilities.get(key)
+    }
+
+    /// Get the capability with the specified key, deserialized into `T`.
+    ///
+    /// Returns `None` if the capability is missing or has a different type.
+    pub fn get<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        self.get_json(key).and_then(|v| serde_json::from_value(v.clone()).ok())
+    }
+
+    fn get_str(&self, key: &str) -> Option<&str> {
+        self.get_json(key).and_then(Value::as_str)
+    }
+
+    /// The name of the browser, e.g. `chrome`, `firefox` or `MicrosoftEdge`.
+    pub fn browser_name(&self) -> Option<&str> {
+        self.get_str("browserName")
+    }
+
+    /// The version of the browser.
+    pub fn browser_version(&self) -> Option<&str> {
+        self.get_str("browserVersion")
+    }
+
+    /// The name of the platform the browser is running on, e.g. `linux` or `windows`.
+    pub fn platform_name(&self) -> Option<&str> {
+        self.get_str("platformName")
+    }
+
+    /// Whether the session accepts insecure TLS certificates.
+    pub fn accept_insecure_certs(&self) -> Option<bool> {
+        self.get_json("acceptInsecureCerts").and_then(Value::as_bool)
+    }
+
+    /// The WebDriver BiDi WebSocket URL, if BiDi was requested and is supported.
+    ///
+    /// See [`CapabilitiesHelper::set_web_socket_url()`].
+    ///
+    /// [`CapabilitiesHelper::set_web_socket_url()`]: crate::CapabilitiesHelper::set_web_socket_url
+    pub fn web_socket_url(&self) -> Option<&str> {
+        self.get_str("webSocketUrl")
+    }
+
+    /// The Chrome DevTools debugger address (`goog:chromeOptions.debuggerAddress`).
+    pub fn chrome_debugger_address(&self) -> Option<&str> {
+        self.get_json("goog:chromeOptions")
+            .and_then(|x| x.get("debuggerAddress"))
+            .and_then(Value::as_str)
+    }
+
+    /// The Firefox remote debugger address (`moz:debuggerAddress`).
+    pub fn firefox_debugger_address(&self) -> Option<&str> {
+        self.get_str("moz:debuggerAddress")
+    }
+
+    /// The Selenium Grid CDP endpoint (`se:cdp`).
+    pub fn se_cdp(&self) -> Option<&str> {
+        self.get_str("se:cdp")
+    }
+}
+
+impl From<Capabilities> for SessionCapabilities {
+    fn from(capabilities: Capabilities) -> Self {
+        Self::new(capabilities)
+    }
+}
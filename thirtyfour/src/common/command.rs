@@ -1,18 +1,22 @@
 use http::Method;
 use serde_json::{json, Value};
 
+use crate::common::selector::css_string;
 use crate::common::{
     capabilities::desiredcapabilities::make_w3c_caps,
     cookie::Cookie,
     keys::TypingData,
     print::PrintParameters,
-    types::{ElementId, OptionRect, SessionId, TimeoutConfiguration, WindowHandle},
+    types::{ElementId, OptionRect, SessionId, ShadowRootId, TimeoutConfiguration, WindowHandle},
 };
-use crate::IntoArcStr;
+use crate::error::{WebDriverError, WebDriverErrorInfo, WebDriverResult};
+use crate::locator::{deep_css_parts, role_candidates, text_candidates, Refine, LABEL_CANDIDATES};
 use crate::RequestData;
+use crate::{IntoArcStr, WebElement};
 use std::fmt;
 use std::fmt::Debug;
 use std::sync::Arc;
+use stringmatch::{Needle, StringMatch};
 
 /// The W3C element identifier key.
 pub const MAGIC_ELEMENTID: &str = "element-6066-11e4-a52e-4f735466cecf";
@@ -48,6 +52,7 @@ impl Selector {
 
 /// Element Selector representation.
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub enum BySelector {
     /// Select an element by id.
     Id(Arc<str>),
@@ -65,6 +70,146 @@ pub enum BySelector {
     ClassName(Arc<str>),
     /// Select an element by CSS.
     Css(Arc<str>),
+    /// Select an element by CSS, entering shadow roots at each `>>>`.
+    DeepCss(Arc<str>),
+    /// Select an element by its computed role and accessible name.
+    Role(Arc<str>, TextMatch),
+    /// Select a form control by its accessible name.
+    Label(TextMatch),
+    /// Select an element by its rendered text.
+    Text(TextMatch),
+    /// Select elements using a script, with the specified arguments.
+    Js(Arc<str>, Arc<[Value]>),
+    /// Select an element by its position relative to other elements.
+    Relative(Box<BySelector>, Vec<Relation>),
+}
+
+/// The position of an element relative to another element, for [`By::relative()`].
+///
+/// Positions are compared using the rect of each element, as returned by
+/// [`WebElement::rect()`].
+#[derive(Debug, Clone)]
+pub enum Relation {
+    /// The element ends above the top edge of the specified element.
+    Above(WebElement),
+    /// The element starts below the bottom edge of the specified element.
+    Below(WebElement),
+    /// The element ends left of the left edge of the specified element.
+    LeftOf(WebElement),
+    /// The element starts right of the right edge of the specified element.
+    RightOf(WebElement),
+    /// The element is within the specified number of pixels of the specified element.
+    Near(WebElement, u32),
+}
+
+impl Relation {
+    /// The element that this relation is relative to.
+    pub fn element(&self) -> &WebElement {
+        match self {
+            Relation::Above(x)
+            | Relation::Below(x)
+            | Relation::LeftOf(x)
+            | Relation::RightOf(x)
+            | Relation::Near(x, _) => x,
+        }
+    }
+}
+
+impl fmt::Display for Relation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Relation::Above(x) => write!(f, "above {}", x),
+            Relation::Below(x) => write!(f, "below {}", x),
+            Relation::LeftOf(x) => write!(f, "left of {}", x),
+            Relation::RightOf(x) => write!(f, "right of {}", x),
+            Relation::Near(x, px) => write!(f, "within {}px of {}", px, x),
+        }
+    }
+}
+
+/// The text to match for the [`By::Role`], [`By::Label`] and [`By::Text`] selectors.
+///
+/// Strings must match the whole text, ignoring differences in whitespace. Use a
+/// [`StringMatch`] for partial, whole-word or case-insensitive matches, or
+/// [`TextMatch::any()`] to match any text.
+#[derive(Debug, Clone)]
+pub struct TextMatch {
+    kind: TextMatchKind,
+}
+
+#[derive(Debug, Clone)]
+enum TextMatchKind {
+    Any,
+    Exact(Arc<str>),
+    Needle(StringMatch),
+}
+
+impl TextMatch {
+    /// Match any text.
+    pub fn any() -> Self {
+        Self {
+            kind: TextMatchKind::Any,
+        }
+    }
+
+    /// Return true if the specified text matches, after collapsing whitespace.
+    pub fn is_match(&self, text: &str) -> bool {
+        let text = collapse_whitespace(text);
+        match &self.kind {
+            TextMatchKind::Any => true,
+            TextMatchKind::Exact(x) => **x == text,
+            TextMatchKind::Needle(x) => x.is_match(&text),
+        }
+    }
+
+    /// Whether this matches any text.
+    pub(crate) fn is_any(&self) -> bool {
+        matches!(self.kind, TextMatchKind::Any)
+    }
+
+    /// The exact text to match, if this was created from a string.
+    pub(crate) fn exact(&self) -> Option<&str> {
+        match &self.kind {
+            TextMatchKind::Exact(x) => Some(x),
+            _ => None,
+        }
+    }
+}
+
+impl From<&str> for TextMatch {
+    fn from(text: &str) -> Self {
+        Self {
+            kind: TextMatchKind::Exact(collapse_whitespace(text).into()),
+        }
+    }
+}
+
+impl From<String> for TextMatch {
+    fn from(text: String) -> Self {
+        Self::from(text.as_str())
+    }
+}
+
+impl From<StringMatch> for TextMatch {
+    fn from(needle: StringMatch) -> Self {
+        Self {
+            kind: TextMatchKind::Needle(needle),
+        }
+    }
+}
+
+impl fmt::Display for TextMatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            TextMatchKind::Any => write!(f, "any"),
+            TextMatchKind::Exact(x) => write!(f, "{:?}", x),
+            TextMatchKind::Needle(x) => write!(f, "{:?}", x),
+        }
+    }
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
 }
 
 /// Element Selector struct providing a convenient way to specify selectors.
@@ -103,6 +248,35 @@ impl By {
         }
     }
 
+    /// Select element by CSS, entering shadow roots at each `>>>`.
+    ///
+    /// The selector is split at each `>>>` outside brackets and quotes, so attribute values
+    /// and pseudo-class arguments may contain `>>>`. The first part is matched as usual, and
+    /// each following part is matched within the shadow roots of the elements matched so far.
+    /// Elements without a shadow root are skipped. The parts are matched one request at a
+    /// time, so this works with any WebDriver implementation that supports shadow roots.
+    ///
+    /// # Example:
+    /// ```no_run
+    /// # use thirtyfour::prelude::*;
+    /// # use thirtyfour::support::block_on;
+    /// #
+    /// # fn main() -> WebDriverResult<()> {
+    /// #     block_on(async {
+    /// #         let caps = DesiredCapabilities::chrome();
+    /// #         let driver = WebDriver::new("http://localhost:4444", caps).await?;
+    /// let button = driver.query(By::DeepCss("app-shell >>> nav-menu >>> button.save")).first().await?;
+    /// #         driver.quit().await?;
+    /// #         Ok(())
+    /// #     })
+    /// # }
+    /// ```
+    pub fn DeepCss(css: impl IntoArcStr) -> Self {
+        Self {
+            selector: BySelector::DeepCss(css.into()),
+        }
+    }
+
     /// Select element by XPath.
     pub fn XPath(x: impl IntoArcStr) -> Self {
         Self {
@@ -113,7 +287,7 @@ impl By {
     /// Select element by name.
     pub fn Name(name: impl IntoArcStr) -> Self {
         Self {
-            selector: BySelector::Css(format!(r#"[name="{}"]"#, name.into()).into()),
+            selector: BySelector::Css(format!("[name={}]", css_string(&name.into())).into()),
         }
     }
 
@@ -130,6 +304,225 @@ impl By {
             selector: BySelector::Css(format!(".{}", name.into()).into()),
         }
     }
+
+    /// Select element by its WAI-ARIA role and accessible name, as computed by the browser.
+    ///
+    /// The role can be explicit, e.g. `role="tab"`, or implicit, e.g. `button` for a
+    /// `<button>` element. Candidate elements are found with CSS, and then checked with
+    /// [`WebElement::computed_role()`] and [`WebElement::computed_label()`], so this matches
+    /// what assistive technologies see. Use [`TextMatch::any()`] to match any name.
+    ///
+    /// # Example:
+    /// ```no_run
+    /// # use thirtyfour::prelude::*;
+    /// # use thirtyfour::support::block_on;
+    /// #
+    /// # fn main() -> WebDriverResult<()> {
+    /// #     block_on(async {
+    /// #         let caps = DesiredCapabilities::chrome();
+    /// #         let driver = WebDriver::new("http://localhost:4444", caps).await?;
+    /// driver.query(By::Role("button", "Save")).first().await?.click().await?;
+    /// let dialog = driver.query(By::Role("dialog", TextMatch::any())).single().await?;
+    /// #         driver.quit().await?;
+    /// #         Ok(())
+    /// #     })
+    /// # }
+    /// ```
+    ///
+    /// [`WebElement::computed_role()`]: crate::WebElement::computed_role
+    /// [`WebElement::computed_label()`]: crate::WebElement::computed_label
+    pub fn Role(role: impl IntoArcStr, name: impl Into<TextMatch>) -> Self {
+        Self {
+            selector: BySelector::Role(role.into(), name.into()),
+        }
+    }
+
+    /// Select a form control by its accessible name, e.g. the text of its `<label>`,
+    /// its `aria-label` or the text of the elements in its `aria-labelledby`.
+    ///
+    /// The names of all the candidate elements are computed with a single script.
+    pub fn Label(text: impl Into<TextMatch>) -> Self {
+        Self {
+            selector: BySelector::Label(text.into()),
+        }
+    }
+
+    /// Select element by its rendered text.
+    ///
+    /// Only elements that have text of their own are matched, rather than all of their
+    /// ancestors. An exact string is compared with the whole rendered text of the element,
+    /// so `"Total: 42"` matches the `<div>` in `<div>Total: <span>42</span></div>`. Other
+    /// matches, such as a [`StringMatch`], only look at the element's own text, so a partial
+    /// match for `"42"` matches the `<span>` alone.
+    ///
+    /// Candidate elements are found with XPath, which most WebDriver implementations do not
+    /// support within a shadow root. The text of all the candidates is fetched with a single
+    /// script.
+    pub fn Text(text: impl Into<TextMatch>) -> Self {
+        Self {
+            selector: BySelector::Text(text.into()),
+        }
+    }
+
+    /// Select element by its `placeholder` attribute.
+    pub fn Placeholder(text: impl IntoArcStr) -> Self {
+        Self {
+            selector: BySelector::Css(format!("[placeholder={}]", css_string(&text.into())).into()),
+        }
+    }
+
+    /// Select element by its `data-testid` attribute.
+    pub fn TestId(id: impl IntoArcStr) -> Self {
+        Self {
+            selector: BySelector::Css(format!("[data-testid={}]", css_string(&id.into())).into()),
+        }
+    }
+
+    /// Select elements using the specified Javascript, for queries that CSS and XPath
+    /// cannot express.
+    ///
+    /// The script is run like [`WebDriver::execute()`], without notifying listeners, and must
+    /// return an element, an array of elements or `null`. The arguments are available as
+    /// `arguments`, and the element or shadow root being searched is available as `this`
+    /// (the `document` when searching from the driver).
+    ///
+    /// # Example:
+    /// ```no_run
+    /// # use thirtyfour::prelude::*;
+    /// # use thirtyfour::support::block_on;
+    /// #
+    /// # fn main() -> WebDriverResult<()> {
+    /// #     block_on(async {
+    /// #         let caps = DesiredCapabilities::chrome();
+    /// #         let driver = WebDriver::new("http://localhost:4444", caps).await?;
+    /// let table = driver.find(By::Id("orders")).await?;
+    /// let script = r#"
+    ///     return [...this.querySelectorAll("tr")]
+    ///         .filter(row => new Date(row.cells[2]?.textContent) > new Date(arguments[0]));
+    /// "#;
+    /// let rows = table.query(By::Js(script, vec!["2024-01-01".into()])).all_from_selector().await?;
+    /// #         driver.quit().await?;
+    /// #         Ok(())
+    /// #     })
+    /// # }
+    /// ```
+    ///
+    /// [`WebDriver::execute()`]: crate::session::handle::SessionHandle::execute
+    pub fn Js(script: impl IntoArcStr, args: impl Into<Arc<[Value]>>) -> Self {
+        Self {
+            selector: BySelector::Js(script.into(), args.into()),
+        }
+    }
+
+    /// Select the elements matched by the specified selector, by their position relative
+    /// to other elements.
+    ///
+    /// Add one or more relations with [`By::above()`], [`By::below()`], [`By::left_of()`],
+    /// [`By::right_of()`] and [`By::near()`]. Matching elements are sorted by their distance
+    /// from the element in the first relation, so `find()` and `first()` return the
+    /// closest one.
+    ///
+    /// # Example:
+    /// ```no_run
+    /// # use thirtyfour::prelude::*;
+    /// # use thirtyfour::support::block_on;
+    /// #
+    /// # fn main() -> WebDriverResult<()> {
+    /// #     block_on(async {
+    /// #         let caps = DesiredCapabilities::chrome();
+    /// #         let driver = WebDriver::new("http://localhost:4444", caps).await?;
+    /// let label = driver.find(By::Text("Customer number")).await?;
+    /// let input = driver.query(By::relative(By::Tag("input")).right_of(&label)).first().await?;
+    /// input.send_keys("1234").await?;
+    /// #         driver.quit().await?;
+    /// #         Ok(())
+    /// #     })
+    /// # }
+    /// ```
+    pub fn relative(by: By) -> Self {
+        match by.selector {
+            BySelector::Relative(..) => by,
+            selector => Self {
+                selector: BySelector::Relative(Box::new(selector), Vec::new()),
+            },
+        }
+    }
+
+    /// Only match elements above the specified element. See [`By::relative()`].
+    pub fn above(self, element: &WebElement) -> Self {
+        self.relation(Relation::Above(element.clone()))
+    }
+
+    /// Only match elements below the specified element. See [`By::relative()`].
+    pub fn below(self, element: &WebElement) -> Self {
+        self.relation(Relation::Below(element.clone()))
+    }
+
+    /// Only match elements to the left of the specified element. See [`By::relative()`].
+    pub fn left_of(self, element: &WebElement) -> Self {
+        self.relation(Relation::LeftOf(element.clone()))
+    }
+
+    /// Only match elements to the right of the specified element. See [`By::relative()`].
+    pub fn right_of(self, element: &WebElement) -> Self {
+        self.relation(Relation::RightOf(element.clone()))
+    }
+
+    /// Only match elements within `distance_px` pixels of the specified element.
+    /// See [`By::relative()`].
+    pub fn near(self, element: &WebElement, distance_px: u32) -> Self {
+        self.relation(Relation::Near(element.clone(), distance_px))
+    }
+
+    fn relation(self, relation: Relation) -> Self {
+        let mut by = Self::relative(self);
+        if let BySelector::Relative(_, relations) = &mut by.selector {
+            relations.push(relation);
+        }
+        by
+    }
+
+    /// Whether the elements returned by the WebDriver must be refined by thirtyfour.
+    pub(crate) fn is_refined(&self) -> bool {
+        self.selector.is_refined()
+    }
+
+    /// The selector sent to the WebDriver. For a refined selector, this matches the candidate
+    /// elements that thirtyfour then refines.
+    pub(crate) fn candidates(self) -> Selector {
+        self.selector.candidates()
+    }
+
+    /// The script and arguments that find the candidate elements, for [`By::Js()`].
+    pub(crate) fn script(&self) -> Option<(&str, &[Value])> {
+        let mut selector = &self.selector;
+        while let BySelector::Relative(inner, _) = selector {
+            selector = inner;
+        }
+        match selector {
+            BySelector::Js(script, args) => Some((script, args)),
+            _ => None,
+        }
+    }
+
+    /// The step that thirtyfour applies to the elements returned by the WebDriver.
+    pub(crate) fn refine(&self) -> Refine {
+        match &self.selector {
+            BySelector::DeepCss(css) => {
+                Refine::Deep(deep_css_parts(css).into_iter().skip(1).map(Arc::from).collect())
+            }
+            BySelector::Role(role, name) => Refine::Role(role.clone(), name.clone()),
+            BySelector::Label(text) => Refine::Label(text.clone()),
+            BySelector::Text(text) => Refine::Text(text.clone()),
+            BySelector::Relative(selector, relations) => {
+                let by = Self {
+                    selector: (**selector).clone(),
+                };
+                Refine::Relative(Box::new(by.refine()), relations.clone())
+            }
+            _ => Refine::None,
+        }
+    }
 }
 
 impl fmt::Display for BySelector {
@@ -143,6 +536,18 @@ impl fmt::Display for BySelector {
             BySelector::Tag(tag) => write!(f, "Tag({})", tag),
             BySelector::ClassName(cname) => write!(f, "Class({})", cname),
             BySelector::Css(css) => write!(f, "CSS({})", css),
+            BySelector::DeepCss(css) => write!(f, "Deep CSS({})", css),
+            BySelector::Role(role, name) => write!(f, "Role({}, name: {})", role, name),
+            BySelector::Label(text) => write!(f, "Label({})", text),
+            BySelector::Text(text) => write!(f, "Text({})", text),
+            BySelector::Js(script, _) => write!(f, "Js({})", script.trim()),
+            BySelector::Relative(selector, relations) => {
+                write!(f, "Relative({}", selector)?;
+                for relation in relations {
+                    write!(f, ", {}", relation)?;
+                }
+                write!(f, ")")
+            }
         }
     }
 }
@@ -153,24 +558,67 @@ impl fmt::Display for By {
     }
 }
 
-impl From<BySelector> for Selector {
-    fn from(by: BySelector) -> Self {
-        match by {
-            BySelector::Id(x) => Selector::new("css selector", format!("[id=\"{}\"]", x)),
+impl BySelector {
+    fn is_refined(&self) -> bool {
+        matches!(
+            self,
+            BySelector::DeepCss(_)
+                | BySelector::Role(..)
+                | BySelector::Label(_)
+                | BySelector::Text(_)
+                | BySelector::Js(..)
+                | BySelector::Relative(..)
+        )
+    }
+
+    fn candidates(self) -> Selector {
+        match self {
+            BySelector::Id(x) => Selector::new("css selector", format!("[id={}]", css_string(&x))),
             BySelector::XPath(x) => Selector::new("xpath", x),
             BySelector::LinkText(x) => Selector::new("link text", x),
             BySelector::PartialLinkText(x) => Selector::new("partial link text", x),
-            BySelector::Name(x) => Selector::new("css selector", format!("[name=\"{}\"]", x)),
+            BySelector::Name(x) => {
+                Selector::new("css selector", format!("[name={}]", css_string(&x)))
+            }
             BySelector::Tag(x) => Selector::new("css selector", x),
             BySelector::ClassName(x) => Selector::new("css selector", format!(".{}", x)),
             BySelector::Css(x) => Selector::new("css selector", x),
+            BySelector::DeepCss(x) => Selector::new("css selector", deep_css_parts(&x)[0]),
+            BySelector::Role(role, _) => Selector::new("css selector", role_candidates(&role)),
+            BySelector::Label(_) => Selector::new("css selector", LABEL_CANDIDATES),
+            BySelector::Text(text) => Selector::new("xpath", text_candidates(&text)),
+            // Scripts are run by thirtyfour, and are not a WebDriver locator strategy.
+            BySelector::Js(x, _) => Selector::new("script", x),
+            BySelector::Relative(selector, _) => selector.candidates(),
+        }
+    }
+}
+
+/// Converts a selector to a W3C locator strategy and value.
+///
+/// Returns [`WebDriverError::InvalidSelector`] for selectors that are resolved by thirtyfour
+/// rather than by the WebDriver, such as [`By::DeepCss()`], [`By::Role()`] or [`By::Js()`].
+impl TryFrom<BySelector> for Selector {
+    type Error = WebDriverError;
+
+    fn try_from(by: BySelector) -> WebDriverResult<Self> {
+        match by.is_refined() {
+            true => Err(WebDriverError::InvalidSelector(WebDriverErrorInfo::new(format!(
+                "{by} is resolved by thirtyfour and has no WebDriver equivalent"
+            )))),
+            false => Ok(by.candidates()),
         }
     }
 }
 
-impl From<By> for Selector {
-    fn from(by: By) -> Self {
-        by.selector.into()
+/// Converts a selector to a W3C locator strategy and value.
+///
+/// See the conversion from [`BySelector`] for the errors.
+impl TryFrom<By> for Selector {
+    type Error = WebDriverError;
+
+    fn try_from(by: By) -> WebDriverResult<Self> {
+        by.selector.try_into()
     }
 }
 
@@ -223,6 +671,9 @@ pub enum Command {
     FindElements(Selector),
     FindElementFromElement(ElementId, Selector),
     FindElementsFromElement(ElementId, Selector),
+    GetElementShadowRoot(ElementId),
+    FindElementFromShadowRoot(ShadowRootId, Selector),
+    FindElementsFromShadowRoot(ShadowRootId, Selector),
     IsElementSelected(ElementId),
     IsElementDisplayed(ElementId),
     GetElementAttribute(ElementId, Arc<str>),
@@ -230,6 +681,8 @@ pub enum Command {
     GetElementCssValue(ElementId, Arc<str>),
     GetElementText(ElementId),
     GetElementTagName(ElementId),
+    GetComputedRole(ElementId),
+    GetComputedLabel(ElementId),
     GetElementRect(ElementId),
     IsElementEnabled(ElementId),
     ElementClick(ElementId),
@@ -255,15 +708,111 @@ pub enum Command {
     ExtensionCommand(Box<dyn ExtensionCommand + Send + Sync>),
 }
 
+impl Command {
+    /// The name of the command variant, e.g. `FindElement`.
+    fn name(&self) -> &'static str {
+        match self {
+            Command::NewSession(..) => "NewSession",
+            Command::DeleteSession => "DeleteSession",
+            Command::Status => "Status",
+            Command::GetTimeouts => "GetTimeouts",
+            Command::SetTimeouts(..) => "SetTimeouts",
+            Command::NavigateTo(..) => "NavigateTo",
+            Command::GetCurrentUrl => "GetCurrentUrl",
+            Command::Back => "Back",
+            Command::Forward => "Forward",
+            Command::Refresh => "Refresh",
+            Command::GetTitle => "GetTitle",
+            Command::GetWindowHandle => "GetWindowHandle",
+            Command::CloseWindow => "CloseWindow",
+            Command::SwitchToWindow(..) => "SwitchToWindow",
+            Command::GetWindowHandles => "GetWindowHandles",
+            Command::NewWindow => "NewWindow",
+            Command::NewTab => "NewTab",
+            Command::SwitchToFrameDefault => "SwitchToFrameDefault",
+            Command::SwitchToFrameNumber(..) => "SwitchToFrameNumber",
+            Command::SwitchToFrameElement(..) => "SwitchToFrameElement",
+            Command::SwitchToParentFrame => "SwitchToParentFrame",
+            Command::GetWindowRect => "GetWindowRect",
+            Command::SetWindowRect(..) => "SetWindowRect",
+            Command::MaximizeWindow => "MaximizeWindow",
+            Command::MinimizeWindow => "MinimizeWindow",
+            Command::FullscreenWindow => "FullscreenWindow",
+            Command::GetActiveElement => "GetActiveElement",
+            Command::FindElement(..) => "FindElement",
+            Command::FindElements(..) => "FindElements",
+            Command::FindElementFromElement(..) => "FindElementFromElement",
+            Command::FindElementsFromElement(..) => "FindElementsFromElement",
+            Command::GetElementShadowRoot(..) => "GetElementShadowRoot",
+            Command::FindElementFromShadowRoot(..) => "FindElementFromShadowRoot",
+            Command::FindElementsFromShadowRoot(..) => "FindElementsFromShadowRoot",
+            Command::IsElementSelected(..) => "IsElementSelected",
+            Command::IsElementDisplayed(..) => "IsElementDisplayed",
+            Command::GetElementAttribute(..) => "GetElementAttribute",
+            Command::GetElementProperty(..) => "GetElementProperty",
+            Command::GetElementCssValue(..) => "GetElementCssValue",
+            Command::GetElementText(..) => "GetElementText",
+            Command::GetElementTagName(..) => "GetElementTagName",
+            Command::GetComputedRole(..) => "GetComputedRole",
+            Command::GetComputedLabel(..) => "GetComputedLabel",
+            Command::GetElementRect(..) => "GetElementRect",
+            Command::IsElementEnabled(..) => "IsElementEnabled",
+            Command::ElementClick(..) => "ElementClick",
+            Command::ElementClear(..) => "ElementClear",
+            Command::ElementSendKeys(..) => "ElementSendKeys",
+            Command::GetPageSource => "GetPageSource",
+            Command::ExecuteScript(..) => "ExecuteScript",
+            Command::ExecuteAsyncScript(..) => "ExecuteAsyncScript",
+            Command::GetAllCookies => "GetAllCookies",
+            Command::GetNamedCookie(..) => "GetNamedCookie",
+            Command::AddCookie(..) => "AddCookie",
+            Command::DeleteCookie(..) => "DeleteCookie",
+            Command::DeleteAllCookies => "DeleteAllCookies",
+            Command::PerformActions(..) => "PerformActions",
+            Command::ReleaseActions => "ReleaseActions",
+            Command::DismissAlert => "DismissAlert",
+            Command::AcceptAlert => "AcceptAlert",
+            Command::GetAlertText => "GetAlertText",
+            Command::SendAlertText(..) => "SendAlertText",
+            Command::PrintPage(..) => "PrintPage",
+            Command::TakeScreenshot => "TakeScreenshot",
+            Command::TakeElementScreenshot(..) => "TakeElementScreenshot",
+            Command::ExtensionCommand(..) => "ExtensionCommand",
+        }
+    }
+}
+
 /// Trait for formatting a WebDriver command into a `RequestData` struct.
 pub trait FormatRequestData: Debug {
     /// Format the command into a `RequestData` struct.
     fn format_request(&self, session_id: &SessionId) -> RequestData;
+
+    /// The name of the command, used in tracing spans and `CommandStats`.
+    ///
+    /// Defaults to the name of the enum variant or struct, taken from the `Debug` output.
+    fn command_name(&self) -> Arc<str> {
+        debug_name(self)
+    }
+}
+
+/// The leading identifier of the `Debug` output, e.g. `FindElement` for
+/// `FindElement(Selector { .. })`.
+pub(crate) fn debug_name(value: &(impl Debug + ?Sized)) -> Arc<str> {
+    let debug = format!("{value:?}");
+    let end = debug.find(|c: char| !c.is_alphanumeric() && c != '_').unwrap_or(debug.len());
+    Arc::from(&debug[..end])
 }
 
 impl FormatRequestData for Command {
-    fn format_request(&self, session_id: &SessionId) -> RequestData {
+    fn command_name(&self) -> Arc<str> {
         match self {
+            Command::ExtensionCommand(command) => debug_name(command),
+            _ => Arc::from(self.name()),
+        }
+    }
+
+    fn format_request(&self, session_id: &SessionId) -> RequestData {
+        let request_data = match self {
             Command::NewSession(caps) => {
                 let w3c_caps = make_w3c_caps(caps);
                 RequestData::new(Method::POST, "/session").add_body(json!({
@@ -385,6 +934,20 @@ impl FormatRequestData for Command {
                 format!("/session/{}/element/{}/elements", session_id, element_id),
             )
             .add_body(json!({"using": selector.name, "value": selector.query})),
+            Command::GetElementShadowRoot(element_id) => RequestData::new(
+                Method::GET,
+                format!("/session/{}/element/{}/shadow", session_id, element_id),
+            ),
+            Command::FindElementFromShadowRoot(shadow_root_id, selector) => RequestData::new(
+                Method::POST,
+                format!("/session/{}/shadow/{}/element", session_id, shadow_root_id),
+            )
+            .add_body(json!({"using": selector.name, "value": selector.query})),
+            Command::FindElementsFromShadowRoot(shadow_root_id, selector) => RequestData::new(
+                Method::POST,
+                format!("/session/{}/shadow/{}/elements", session_id, shadow_root_id),
+            )
+            .add_body(json!({"using": selector.name, "value": selector.query})),
             Command::IsElementSelected(element_id) => RequestData::new(
                 Method::GET,
                 format!("/session/{}/element/{}/selected", session_id, element_id),
@@ -419,6 +982,14 @@ impl FormatRequestData for Command {
                 Method::GET,
                 format!("/session/{}/element/{}/name", session_id, element_id),
             ),
+            Command::GetComputedRole(element_id) => RequestData::new(
+                Method::GET,
+                format!("/session/{}/element/{}/computedrole", session_id, element_id),
+            ),
+            Command::GetComputedLabel(element_id) => RequestData::new(
+                Method::GET,
+                format!("/session/{}/element/{}/computedlabel", session_id, element_id),
+            ),
             Command::GetElementRect(element_id) => RequestData::new(
                 Method::GET,
                 format!("/session/{}/element/{}/rect", session_id, element_id),
@@ -518,6 +1089,7 @@ impl FormatRequestData for Command {
                     None => request_data,
                 }
             }
-        }
+        };
+        request_data.with_command_name(self.command_name())
     }
 }
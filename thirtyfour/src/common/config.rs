@@ -1,11 +1,17 @@
 use crate::error::WebDriverError;
+use crate::session::layer::CommandLayer;
+use crate::session::listener::WebDriverListener;
+use crate::session::upload::{FileDetector, LocalFileDetector};
 use crate::{
     extensions::query::{ElementPollerWithTimeout, IntoElementPoller},
     prelude::WebDriverResult,
 };
 use const_format::formatcp;
-use http::HeaderValue;
+use http::header::AUTHORIZATION;
+use http::{HeaderMap, HeaderName, HeaderValue};
+use std::fmt::{Debug, Formatter};
 use std::sync::Arc;
+use std::time::Duration;
 
 /// Configuration options used by a `WebDriver` instance and the related `SessionHandle`.
 ///
@@ -19,6 +25,23 @@ pub struct WebDriverConfig {
     pub poller: Arc<dyn IntoElementPoller + Send + Sync>,
     /// The user agent to use when sending commands to the webdriver server.
     pub user_agent: HeaderValue,
+    /// Extra headers to send with every command.
+    ///
+    /// These take precedence over the default headers, including the basic auth
+    /// header derived from the server URL.
+    pub headers: HeaderMap,
+    /// Controls how starting a new session is retried.
+    pub session_retry_policy: SessionRetryPolicy,
+    /// Middleware layers wrapping every command, outermost first.
+    pub layers: Vec<Arc<dyn CommandLayer>>,
+    /// Hooks called before and after high-level browser actions, in order.
+    pub listeners: Vec<Arc<dyn WebDriverListener>>,
+    /// Decides whether files are uploaded to the WebDriver server before use.
+    pub file_detector: Arc<dyn FileDetector>,
+    /// What to do when the last `WebDriver` for a session is dropped without calling `quit()`.
+    pub drop_policy: DropPolicy,
+    /// Controls how the session is replaced after the browser crashes. Disabled by default.
+    pub recovery_policy: Option<RecoveryPolicy>,
 }
 
 impl Default for WebDriverConfig {
@@ -62,12 +85,240 @@ impl WebDriverConfig {
     }
 }
 
+/// What to do when the last `WebDriver` for a session is dropped without calling `quit()`.
+///
+/// Rust does not have async destructors, so quitting on drop blocks the current thread
+/// until the session has been deleted. Prefer calling `WebDriver::quit()`, or running
+/// the session with `WebDriver::scope()`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DropPolicy {
+    /// Quit the session, blocking the current thread until it has been deleted.
+    #[default]
+    Quit,
+    /// Leave the session running.
+    Leak,
+    /// Leave the session running, and log a warning.
+    Warn,
+}
+
+type RetryPredicate = Arc<dyn Fn(&WebDriverError) -> bool + Send + Sync>;
+
+/// Policy controlling how starting a new session is retried.
+///
+/// The default policy retries once, without delay, if the server responds with
+/// an HTTP 500 "unknown error". Selenium sometimes returns this when the browser
+/// failed to start.
+///
+/// # Example
+/// ```
+/// # use std::time::Duration;
+/// # use thirtyfour::common::config::SessionRetryPolicy;
+/// # use thirtyfour::prelude::*;
+/// // Retry up to 3 times, waiting 2 seconds between attempts, on any error
+/// // other than invalid capabilities.
+/// let policy = SessionRetryPolicy::new(3)
+///     .delay(Duration::from_secs(2))
+///     .retry_if(|e| !matches!(e, WebDriverError::InvalidArgument(_)));
+/// ```
+#[derive(Clone)]
+pub struct SessionRetryPolicy {
+    max_retries: u32,
+    delay: Duration,
+    predicate: Option<RetryPredicate>,
+}
+
+impl Debug for SessionRetryPolicy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SessionRetryPolicy")
+            .field("max_retries", &self.max_retries)
+            .field("delay", &self.delay)
+            .field("custom_predicate", &self.predicate.is_some())
+            .finish()
+    }
+}
+
+impl Default for SessionRetryPolicy {
+    fn default() -> Self {
+        Self::new(1)
+    }
+}
+
+impl SessionRetryPolicy {
+    /// Create a new policy that retries up to `max_retries` times after the first attempt.
+    pub fn new(max_retries: u32) -> Self {
+        Self {
+            max_retries,
+            delay: Duration::ZERO,
+            predicate: None,
+        }
+    }
+
+    /// Create a policy that never retries.
+    pub fn none() -> Self {
+        Self::new(0)
+    }
+
+    /// Set the delay between attempts.
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Only retry if the specified function returns true for the error.
+    ///
+    /// By default, only HTTP 500 "unknown error" responses are retried.
+    pub fn retry_if<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&WebDriverError) -> bool + Send + Sync + 'static,
+    {
+        self.predicate = Some(Arc::new(f));
+        self
+    }
+
+    /// The maximum number of retries after the first attempt.
+    pub fn max_retries(&self) -> u32 {
+        self.max_retries
+    }
+
+    /// The delay between attempts.
+    pub fn retry_delay(&self) -> Duration {
+        self.delay
+    }
+
+    /// Return true if the specified error should be retried.
+    pub fn should_retry(&self, error: &WebDriverError) -> bool {
+        match &self.predicate {
+            Some(f) => f(error),
+            None => matches!(error, WebDriverError::UnknownError(x) if x.status == 500),
+        }
+    }
+}
+
+/// Policy controlling how a session is replaced after the browser crashes.
+///
+/// When a command fails with an error matching the policy (by default `invalid session id`,
+/// or an `unknown error` after which the session no longer responds), a new session is
+/// started with the capabilities that created the original one. The failed command then
+/// returns [`WebDriverError::SessionRecovered`], so that the caller can decide whether
+/// to retry it, and listeners are notified via
+/// [`WebDriverListener::on_session_recovered()`].
+///
+/// Elements found in the previous session cannot be used in the new one, and return
+/// [`WebDriverError::StaleSession`]. Recovery is only available for sessions started by
+/// `WebDriver`, not for attached sessions.
+///
+/// # Example
+/// ```
+/// # use thirtyfour::common::config::{RecoveryPolicy, WebDriverConfig};
+/// # use thirtyfour::prelude::*;
+/// # fn main() -> WebDriverResult<()> {
+/// // Recover up to 3 times, returning to the last URL with the same cookies.
+/// let policy = RecoveryPolicy::new(3).restore_url(true).restore_cookies(true);
+/// let config = WebDriverConfig::builder().recovery_policy(policy).build()?;
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct RecoveryPolicy {
+    max_recoveries: u32,
+    restore_url: bool,
+    restore_cookies: bool,
+    predicate: Option<RetryPredicate>,
+}
+
+impl Debug for RecoveryPolicy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RecoveryPolicy")
+            .field("max_recoveries", &self.max_recoveries)
+            .field("restore_url", &self.restore_url)
+            .field("restore_cookies", &self.restore_cookies)
+            .field("custom_predicate", &self.predicate.is_some())
+            .finish()
+    }
+}
+
+impl Default for RecoveryPolicy {
+    fn default() -> Self {
+        Self::new(1)
+    }
+}
+
+impl RecoveryPolicy {
+    /// Create a new policy that replaces the session up to `max_recoveries` times.
+    pub fn new(max_recoveries: u32) -> Self {
+        Self {
+            max_recoveries,
+            restore_url: false,
+            restore_cookies: false,
+            predicate: None,
+        }
+    }
+
+    /// Navigate the new session to the last URL loaded with `goto()`.
+    pub fn restore_url(mut self, restore_url: bool) -> Self {
+        self.restore_url = restore_url;
+        self
+    }
+
+    /// Add the cookies of the previous session to the new session.
+    ///
+    /// The cookies are read after each call to `goto()`, and kept up to date by
+    /// `add_cookie()` and `delete_cookie()`. Since cookies can only be added for the current
+    /// page, this also navigates to the last URL.
+    pub fn restore_cookies(mut self, restore_cookies: bool) -> Self {
+        self.restore_cookies = restore_cookies;
+        self
+    }
+
+    /// Only recover if the specified function returns true for the error.
+    pub fn recover_if<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&WebDriverError) -> bool + Send + Sync + 'static,
+    {
+        self.predicate = Some(Arc::new(f));
+        self
+    }
+
+    /// The maximum number of times the session is replaced.
+    pub fn max_recoveries(&self) -> u32 {
+        self.max_recoveries
+    }
+
+    /// Return true if the new session is navigated to the last URL.
+    pub fn restores_url(&self) -> bool {
+        self.restore_url || self.restore_cookies
+    }
+
+    /// Return true if the cookies of the previous session are restored.
+    pub fn restores_cookies(&self) -> bool {
+        self.restore_cookies
+    }
+
+    /// Return true if the specified error may mean that the browser has crashed.
+    pub fn should_recover(&self, error: &WebDriverError) -> bool {
+        match &self.predicate {
+            Some(f) => f(error),
+            None => matches!(
+                error,
+                WebDriverError::InvalidSessionId(_) | WebDriverError::UnknownError(_)
+            ),
+        }
+    }
+}
+
 /// Builder for `WebDriverConfig`.
 #[derive(Debug)]
 pub struct WebDriverConfigBuilder {
     keep_alive: bool,
     poller: Option<Arc<dyn IntoElementPoller + Send + Sync>>,
     user_agent: Option<WebDriverResult<HeaderValue>>,
+    headers: Vec<WebDriverResult<(HeaderName, HeaderValue)>>,
+    session_retry_policy: SessionRetryPolicy,
+    layers: Vec<Arc<dyn CommandLayer>>,
+    listeners: Vec<Arc<dyn WebDriverListener>>,
+    file_detector: Option<Arc<dyn FileDetector>>,
+    drop_policy: DropPolicy,
+    recovery_policy: Option<RecoveryPolicy>,
 }
 
 impl Default for WebDriverConfigBuilder {
@@ -83,6 +334,13 @@ impl WebDriverConfigBuilder {
             keep_alive: true,
             poller: None,
             user_agent: None,
+            headers: Vec::new(),
+            session_retry_policy: SessionRetryPolicy::default(),
+            layers: Vec::new(),
+            listeners: Vec::new(),
+            file_detector: None,
+            drop_policy: DropPolicy::default(),
+            recovery_policy: None,
         }
     }
 
@@ -108,12 +366,100 @@ impl WebDriverConfigBuilder {
         self
     }
 
+    /// Add a header to send with every command.
+    ///
+    /// Adding the same header more than once will send all of the values.
+    pub fn header<K, V>(mut self, name: K, value: V) -> Self
+    where
+        HeaderName: TryFrom<K>,
+        <HeaderName as TryFrom<K>>::Error: Into<WebDriverError>,
+        HeaderValue: TryFrom<V>,
+        <HeaderValue as TryFrom<V>>::Error: Into<WebDriverError>,
+    {
+        let header = HeaderName::try_from(name)
+            .map_err(Into::into)
+            .and_then(|name| Ok((name, HeaderValue::try_from(value).map_err(Into::into)?)));
+        self.headers.push(header);
+        self
+    }
+
+    /// Send the specified bearer token in the `Authorization` header of every command.
+    ///
+    /// This replaces the basic auth header derived from the server URL, if any.
+    pub fn bearer_auth(mut self, token: impl std::fmt::Display) -> Self {
+        let header = HeaderValue::try_from(format!("Bearer {token}")).map_err(Into::into).map(
+            |mut value| {
+                value.set_sensitive(true);
+                (AUTHORIZATION, value)
+            },
+        );
+        self.headers.push(header);
+        self
+    }
+
+    /// Set the policy for retrying failed attempts to start a new session.
+    pub fn session_retry_policy(mut self, policy: SessionRetryPolicy) -> Self {
+        self.session_retry_policy = policy;
+        self
+    }
+
+    /// Add a middleware layer that wraps every command.
+    ///
+    /// Layers run in the order they are added, so the first layer added sees each
+    /// request first and each response last.
+    pub fn layer(mut self, layer: impl CommandLayer) -> Self {
+        self.layers.push(Arc::new(layer));
+        self
+    }
+
+    /// Add a listener that is called before and after high-level browser actions,
+    /// such as clicking an element or navigating to a URL.
+    ///
+    /// Listeners are called in the order they are added.
+    pub fn listener(mut self, listener: impl WebDriverListener) -> Self {
+        self.listeners.push(Arc::new(listener));
+        self
+    }
+
+    /// Set the `FileDetector` that decides whether files are uploaded to the WebDriver
+    /// server before use. By default files are never uploaded.
+    pub fn file_detector(mut self, detector: impl FileDetector + 'static) -> Self {
+        self.file_detector = Some(Arc::new(detector));
+        self
+    }
+
+    /// Set what to do when the last `WebDriver` for a session is dropped without
+    /// calling `quit()`.
+    pub fn drop_policy(mut self, policy: DropPolicy) -> Self {
+        self.drop_policy = policy;
+        self
+    }
+
+    /// Replace the session after the browser crashes, using the specified policy.
+    pub fn recovery_policy(mut self, policy: RecoveryPolicy) -> Self {
+        self.recovery_policy = Some(policy);
+        self
+    }
+
     /// Build `WebDriverConfig` using builder options.
     pub fn build(self) -> WebDriverResult<WebDriverConfig> {
+        let mut headers = HeaderMap::new();
+        for header in self.headers {
+            let (name, value) = header?;
+            headers.append(name, value);
+        }
+
         Ok(WebDriverConfig {
             keep_alive: self.keep_alive,
             poller: self.poller.unwrap_or_else(|| Arc::new(ElementPollerWithTimeout::default())),
             user_agent: self.user_agent.transpose()?.unwrap_or(WebDriverConfig::DEFAULT_USER_AGENT),
+            headers,
+            session_retry_policy: self.session_retry_policy,
+            layers: self.layers,
+            listeners: self.listeners,
+            file_detector: self.file_detector.unwrap_or_else(|| Arc::new(LocalFileDetector)),
+            drop_policy: self.drop_policy,
+            recovery_policy: self.recovery_policy,
         })
     }
 }
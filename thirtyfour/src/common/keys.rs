@@ -149,7 +149,7 @@ impl From<Key> for char {
 }
 
 /// TypingData is a wrapper around a `Vec<char>` that can be used to send Key to the browser.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct TypingData {
     data: Vec<char>,
 }
@@ -14,5 +14,6 @@ pub mod keys;
 pub mod print;
 /// Type for request method and body.
 pub mod requestdata;
+pub mod selector;
 /// Common types used within thirtyfour.
 pub mod types;
@@ -2,7 +2,7 @@ use std::fmt::Display;
 use std::sync::Arc;
 
 use crate::IntoArcStr;
-use http::Method;
+use http::{HeaderMap, HeaderName, HeaderValue, Method};
 
 /// RequestData is a wrapper around the data required to make an HTTP request.
 #[derive(Debug, Clone)]
@@ -13,6 +13,14 @@ pub struct RequestData {
     pub uri: Arc<str>,
     /// The request body.
     pub body: Option<serde_json::Value>,
+    /// Extra headers for this request only.
+    ///
+    /// These take precedence over the headers in the `WebDriverConfig`.
+    pub headers: HeaderMap,
+    /// If true, the body is not included when displaying this request (e.g. in logs).
+    pub sensitive: bool,
+    /// The name of the command this request was formatted from, e.g. `FindElement`.
+    pub command_name: Option<Arc<str>>,
 }
 
 impl RequestData {
@@ -22,6 +30,9 @@ impl RequestData {
             method,
             uri: uri.into(),
             body: None,
+            headers: HeaderMap::new(),
+            sensitive: false,
+            command_name: None,
         }
     }
 
@@ -30,11 +41,30 @@ impl RequestData {
         self.body = Some(body);
         self
     }
+
+    /// Add a header for this request.
+    pub fn add_header(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self.headers.append(name, value);
+        self
+    }
+
+    /// Set the name of the command this request was formatted from.
+    pub fn with_command_name(mut self, name: impl IntoArcStr) -> Self {
+        self.command_name = Some(name.into());
+        self
+    }
+
+    /// Mark the body of this request as sensitive, so that it is redacted when displayed.
+    pub fn set_sensitive(&mut self, sensitive: bool) {
+        self.sensitive = sensitive;
+    }
 }
 
 impl Display for RequestData {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        if let Some(body) = &self.body {
+        if self.sensitive && self.body.is_some() {
+            write!(f, "{} {} <redacted>", self.method, self.uri)
+        } else if let Some(body) = &self.body {
             write!(
                 f,
                 "{} {} {}",
@@ -0,0 +1,410 @@
+//! A builder for CSS and XPath selectors.
+//!
+//! Selectors written by hand are easy to get wrong, and a quoting mistake only shows up
+//! when the WebDriver returns [`WebDriverError::InvalidSelector`]. An [`ElementSelector`]
+//! is composed from tag, attribute, text, position and descendant conditions, and escapes
+//! each value as it is converted to CSS or XPath.
+//!
+//! # Example
+//! ```no_run
+//! # use thirtyfour::prelude::*;
+//! # use thirtyfour::support::block_on;
+//! use thirtyfour::common::selector::ElementSelector;
+//!
+//! # fn main() -> WebDriverResult<()> {
+//! #     block_on(async {
+//! #         let caps = DesiredCapabilities::chrome();
+//! #         let driver = WebDriver::new("http://localhost:4444", caps).await?;
+//! let input = ElementSelector::tag("input").attr("name", r#"customer's "id""#);
+//! driver.find(input.css()?).await?.send_keys("1234").await?;
+//!
+//! let row = ElementSelector::tag("table")
+//!     .id("orders")
+//!     .descendant(ElementSelector::tag("td").nth_child(3).text_contains("2024"));
+//! driver.query(row.xpath()?).first().await?;
+//! #         driver.quit().await?;
+//! #         Ok(())
+//! #     })
+//! # }
+//! ```
+
+use std::fmt::Write;
+use std::sync::Arc;
+
+use crate::components::escape_string;
+use crate::error::{WebDriverError, WebDriverErrorInfo, WebDriverResult};
+use crate::{By, IntoArcStr};
+
+/// A CSS or XPath selector, composed from conditions that are escaped when the selector is
+/// converted. See the [module documentation](self) for an example.
+///
+/// Each condition applies to the element matched by the last step. Use
+/// [`ElementSelector::child()`] or [`ElementSelector::descendant()`] to add a step that
+/// matches elements within it.
+#[derive(Debug, Clone)]
+pub struct ElementSelector {
+    steps: Vec<Step>,
+}
+
+#[derive(Debug, Clone)]
+struct Step {
+    /// Whether the element must be a child (rather than any descendant) of the previous step.
+    child: bool,
+    tag: Option<Arc<str>>,
+    conditions: Vec<Condition>,
+}
+
+#[derive(Debug, Clone)]
+enum Condition {
+    Id(Arc<str>),
+    Class(Arc<str>),
+    HasAttr(Arc<str>),
+    Attr(Arc<str>, AttrOp, Arc<str>),
+    Text(Arc<str>),
+    TextContains(Arc<str>),
+    NthChild(usize),
+}
+
+#[derive(Debug, Clone, Copy)]
+enum AttrOp {
+    Equals,
+    Contains,
+    StartsWith,
+}
+
+impl ElementSelector {
+    /// Select elements with the specified tag name.
+    pub fn tag(tag: impl IntoArcStr) -> Self {
+        Self::step(Some(tag.into()))
+    }
+
+    /// Select elements with any tag name.
+    pub fn any() -> Self {
+        Self::step(None)
+    }
+
+    fn step(tag: Option<Arc<str>>) -> Self {
+        Self {
+            steps: vec![Step {
+                child: false,
+                tag,
+                conditions: Vec::new(),
+            }],
+        }
+    }
+
+    fn with(mut self, condition: Condition) -> Self {
+        if let Some(step) = self.steps.last_mut() {
+            step.conditions.push(condition);
+        }
+        self
+    }
+
+    /// Only match elements with the specified id.
+    pub fn id(self, id: impl IntoArcStr) -> Self {
+        self.with(Condition::Id(id.into()))
+    }
+
+    /// Only match elements with the specified class.
+    pub fn class(self, class: impl IntoArcStr) -> Self {
+        self.with(Condition::Class(class.into()))
+    }
+
+    /// Only match elements that have the specified attribute.
+    pub fn has_attr(self, name: impl IntoArcStr) -> Self {
+        self.with(Condition::HasAttr(name.into()))
+    }
+
+    /// Only match elements where the specified attribute has the specified value.
+    pub fn attr(self, name: impl IntoArcStr, value: impl IntoArcStr) -> Self {
+        self.with(Condition::Attr(name.into(), AttrOp::Equals, value.into()))
+    }
+
+    /// Only match elements where the specified attribute contains the specified value.
+    pub fn attr_contains(self, name: impl IntoArcStr, value: impl IntoArcStr) -> Self {
+        self.with(Condition::Attr(name.into(), AttrOp::Contains, value.into()))
+    }
+
+    /// Only match elements where the specified attribute starts with the specified value.
+    pub fn attr_starts_with(self, name: impl IntoArcStr, value: impl IntoArcStr) -> Self {
+        self.with(Condition::Attr(name.into(), AttrOp::StartsWith, value.into()))
+    }
+
+    /// Only match elements whose text is the specified text, ignoring leading and trailing
+    /// whitespace. This is only supported by XPath.
+    pub fn text(self, text: impl IntoArcStr) -> Self {
+        self.with(Condition::Text(text.into()))
+    }
+
+    /// Only match elements whose text contains the specified text. This is only supported
+    /// by XPath.
+    pub fn text_contains(self, text: impl IntoArcStr) -> Self {
+        self.with(Condition::TextContains(text.into()))
+    }
+
+    /// Only match elements that are the nth child of their parent, starting from 1.
+    pub fn nth_child(self, n: usize) -> Self {
+        self.with(Condition::NthChild(n))
+    }
+
+    /// Select the elements matched by `selector` that are children of the elements matched
+    /// so far.
+    pub fn child(self, selector: ElementSelector) -> Self {
+        self.join(true, selector)
+    }
+
+    /// Select the elements matched by `selector` that are descendants of the elements
+    /// matched so far.
+    pub fn descendant(self, selector: ElementSelector) -> Self {
+        self.join(false, selector)
+    }
+
+    fn join(mut self, child: bool, selector: ElementSelector) -> Self {
+        let mut steps = selector.steps.into_iter();
+        if let Some(first) = steps.next() {
+            self.steps.push(Step {
+                child,
+                ..first
+            });
+        }
+        self.steps.extend(steps);
+        self
+    }
+
+    /// Convert this selector to CSS.
+    ///
+    /// Returns [`WebDriverError::InvalidSelector`] if the selector matches text, which CSS
+    /// does not support, or if a value is invalid.
+    pub fn to_css(&self) -> WebDriverResult<String> {
+        let mut css = String::new();
+        for (i, step) in self.steps.iter().enumerate() {
+            if i > 0 {
+                let combinator = if step.child {
+                    " > "
+                } else {
+                    " "
+                };
+                css.push_str(combinator);
+            }
+            match &step.tag {
+                Some(tag) => css.push_str(&css_ident(non_empty("tag", tag)?)),
+                None => css.push('*'),
+            }
+            for condition in &step.conditions {
+                match condition {
+                    Condition::Id(id) => write!(css, "#{}", css_ident(non_empty("id", id)?)),
+                    Condition::Class(class) => {
+                        write!(css, ".{}", css_ident(non_empty("class", class)?))
+                    }
+                    Condition::HasAttr(name) => {
+                        write!(css, "[{}]", css_ident(non_empty("attribute", name)?))
+                    }
+                    Condition::Attr(name, op, value) => {
+                        let op = match op {
+                            AttrOp::Equals => "=",
+                            AttrOp::Contains => "*=",
+                            AttrOp::StartsWith => "^=",
+                        };
+                        let name = css_ident(non_empty("attribute", name)?);
+                        write!(css, "[{}{}{}]", name, op, css_string(value))
+                    }
+                    Condition::Text(_) | Condition::TextContains(_) => {
+                        return Err(invalid_selector(
+                            "CSS selectors cannot match text, use to_xpath() instead".to_string(),
+                        ));
+                    }
+                    Condition::NthChild(n) => write!(css, ":nth-child({})", position(*n)?),
+                }
+                .expect("writing to a String cannot fail");
+            }
+        }
+        Ok(css)
+    }
+
+    /// Convert this selector to XPath, relative to the element being searched.
+    ///
+    /// Returns [`WebDriverError::InvalidSelector`] if a tag or attribute name is not a
+    /// valid XPath name, or if a value is invalid.
+    pub fn to_xpath(&self) -> WebDriverResult<String> {
+        let mut xpath = String::from(".");
+        for step in &self.steps {
+            xpath.push_str(if step.child {
+                "/"
+            } else {
+                "//"
+            });
+            match &step.tag {
+                Some(tag) => xpath.push_str(xpath_name("tag", tag)?),
+                None => xpath.push('*'),
+            }
+            for condition in &step.conditions {
+                let predicate = match condition {
+                    Condition::Id(id) => format!("@id={}", escape_string(non_empty("id", id)?)),
+                    Condition::Class(class) => format!(
+                        "contains(concat(' ', normalize-space(@class), ' '), {})",
+                        escape_string(&format!(" {} ", non_empty("class", class)?))
+                    ),
+                    Condition::HasAttr(name) => format!("@{}", xpath_name("attribute", name)?),
+                    Condition::Attr(name, op, value) => {
+                        let name = xpath_name("attribute", name)?;
+                        let value = escape_string(value);
+                        match op {
+                            AttrOp::Equals => format!("@{}={}", name, value),
+                            AttrOp::Contains => format!("contains(@{}, {})", name, value),
+                            AttrOp::StartsWith => format!("starts-with(@{}, {})", name, value),
+                        }
+                    }
+                    Condition::Text(text) => {
+                        format!("normalize-space(.)=normalize-space({})", escape_string(text))
+                    }
+                    Condition::TextContains(text) => {
+                        format!("contains(., {})", escape_string(text))
+                    }
+                    Condition::NthChild(n) => {
+                        format!("count(preceding-sibling::*)={}", position(*n)? - 1)
+                    }
+                };
+                write!(xpath, "[{}]", predicate).expect("writing to a String cannot fail");
+            }
+        }
+        Ok(xpath)
+    }
+
+    /// Convert this selector to a [`By::Css`] selector. See [`ElementSelector::to_css()`].
+    pub fn css(&self) -> WebDriverResult<By> {
+        Ok(By::Css(self.to_css()?))
+    }
+
+    /// Convert this selector to a [`By::XPath`] selector. See [`ElementSelector::to_xpath()`].
+    pub fn xpath(&self) -> WebDriverResult<By> {
+        Ok(By::XPath(self.to_xpath()?))
+    }
+}
+
+/// Escape the specified value for use as a CSS identifier, e.g. a tag name, id or class.
+///
+/// This follows the `CSS.escape()` algorithm.
+pub fn css_ident(value: &str) -> String {
+    let mut ident = String::with_capacity(value.len());
+    let chars: Vec<char> = value.chars().collect();
+    for (i, &c) in chars.iter().enumerate() {
+        match c {
+            '\0' => ident.push(char::REPLACEMENT_CHARACTER),
+            '\u{1}'..='\u{1f}' | '\u{7f}' => write_hex(&mut ident, c),
+            '0'..='9' if i == 0 || (i == 1 && chars[0] == '-') => write_hex(&mut ident, c),
+            '-' if i == 0 && chars.len() == 1 => ident.push_str("\\-"),
+            c if c.is_ascii_alphanumeric() || c == '-' || c == '_' || !c.is_ascii() => {
+                ident.push(c)
+            }
+            c => {
+                ident.push('\\');
+                ident.push(c);
+            }
+        }
+    }
+    ident
+}
+
+/// Quote and escape the specified value for use as a CSS string, e.g. an attribute value.
+pub fn css_string(value: &str) -> String {
+    let mut string = String::with_capacity(value.len() + 2);
+    string.push('"');
+    for c in value.chars() {
+        match c {
+            '\0' => string.push(char::REPLACEMENT_CHARACTER),
+            '\u{1}'..='\u{1f}' | '\u{7f}' => write_hex(&mut string, c),
+            '"' | '\\' => {
+                string.push('\\');
+                string.push(c);
+            }
+            c => string.push(c),
+        }
+    }
+    string.push('"');
+    string
+}
+
+/// Write a CSS hex escape for the specified character.
+fn write_hex(s: &mut String, c: char) {
+    write!(s, "\\{:x} ", c as u32).expect("writing to a String cannot fail");
+}
+
+fn invalid_selector(message: String) -> WebDriverError {
+    WebDriverError::InvalidSelector(WebDriverErrorInfo::new(message))
+}
+
+fn non_empty<'a>(kind: &str, value: &'a str) -> WebDriverResult<&'a str> {
+    match value.is_empty() {
+        true => Err(invalid_selector(format!("the {kind} in a selector cannot be empty"))),
+        false => Ok(value),
+    }
+}
+
+/// Check that the specified tag or attribute name can be used in XPath without escaping.
+fn xpath_name<'a>(kind: &str, name: &'a str) -> WebDriverResult<&'a str> {
+    let mut chars = name.chars();
+    let valid = chars.next().is_some_and(|c| c.is_alphabetic() || c == '_')
+        && chars.all(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    match valid {
+        true => Ok(name),
+        false => Err(invalid_selector(format!("'{name}' is not a valid XPath {kind} name"))),
+    }
+}
+
+fn position(n: usize) -> WebDriverResult<usize> {
+    match n {
+        0 => Err(invalid_selector("nth_child() positions start from 1".to_string())),
+        n => Ok(n),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escaping() {
+        assert_eq!(css_ident("main-nav"), "main-nav");
+        assert_eq!(css_ident("1st"), "\\31 st");
+        assert_eq!(css_ident("-2"), "-\\32 ");
+        assert_eq!(css_ident("-"), "\\-");
+        assert_eq!(css_ident("a.b:c"), "a\\.b\\:c");
+        assert_eq!(css_string(r#"say "hi"\"#), r#""say \"hi\"\\""#);
+        assert_eq!(css_string("a\nb"), "\"a\\a b\"");
+    }
+
+    #[test]
+    fn selectors() {
+        let selector = ElementSelector::tag("form")
+            .id("sign-up")
+            .child(ElementSelector::any().class("field").nth_child(2))
+            .descendant(ElementSelector::tag("input").attr("name", "it's \"quoted\""));
+        assert_eq!(
+            selector.to_css().unwrap(),
+            r#"form#sign-up > *.field:nth-child(2) input[name="it's \"quoted\""]"#
+        );
+        assert_eq!(
+            selector.to_xpath().unwrap(),
+            ".//form[@id=\"sign-up\"]/*[contains(concat(' ', normalize-space(@class), ' '), \
+             \" field \")][count(preceding-sibling::*)=1]//input[@name=concat(\"it's \", '\"', \
+             \"quoted\", '\"', \"\")]"
+        );
+
+        let link = ElementSelector::tag("a").attr_starts_with("href", "/docs").text("Guide");
+        assert_eq!(
+            link.to_xpath().unwrap(),
+            ".//a[starts-with(@href, \"/docs\")][normalize-space(.)=normalize-space(\"Guide\")]"
+        );
+        assert!(matches!(link.to_css(), Err(WebDriverError::InvalidSelector(_))));
+    }
+
+    #[test]
+    fn invalid() {
+        let invalid =
+            |x: WebDriverResult<String>| matches!(x, Err(WebDriverError::InvalidSelector(_)));
+        assert!(invalid(ElementSelector::tag("").to_css()));
+        assert!(invalid(ElementSelector::tag("li").nth_child(0).to_css()));
+        assert!(invalid(ElementSelector::any().has_attr("@click").to_xpath()));
+        assert_eq!(ElementSelector::any().has_attr("@click").to_css().unwrap(), "*[\\@click]");
+    }
+}
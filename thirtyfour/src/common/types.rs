@@ -210,6 +210,30 @@ impl fmt::Display for ElementId {
     }
 }
 
+/// New-type for the shadow root id.
+#[derive(Debug, Clone, Serialize, Deserialize, Hash, Eq, PartialEq)]
+#[serde(transparent)]
+pub struct ShadowRootId {
+    id: Arc<str>,
+}
+
+impl<S> From<S> for ShadowRootId
+where
+    S: IntoArcStr,
+{
+    fn from(value: S) -> Self {
+        ShadowRootId {
+            id: value.into(),
+        }
+    }
+}
+
+impl fmt::Display for ShadowRootId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.id)
+    }
+}
+
 /// New-type for the window handle.
 #[derive(Debug, Clone, Serialize, Deserialize, Hash, Eq, PartialEq)]
 pub struct WindowHandle {
@@ -41,9 +41,6 @@ impl Display for Escaped<'_> {
             }
             write!(f, "\"{}\"", substring)?;
         }
-        if self.0.ends_with('\"') {
-            f.write_str(", '\"'")?;
-        }
         Ok(())
     }
 }
@@ -347,3 +344,17 @@ impl SelectElement {
         self.set_selection_by_partial_text(text, false).await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_string() {
+        assert_eq!(escape_string("plain"), "\"plain\"");
+        assert_eq!(escape_string("it's"), "\"it's\"");
+        assert_eq!(escape_string("say \"hi\""), "'say \"hi\"'");
+        assert_eq!(escape_string("it's \"a\""), r#"concat("it's ", '"', "a", '"', "")"#);
+        assert_eq!(escape_string("\"it's\""), r#"concat("", '"', "it's", '"', "")"#);
+    }
+}
@@ -135,6 +135,8 @@ pub enum WebDriverError {
     HttpError(String),
     #[error("The WebDriver response does not conform to the W3C WebDriver spec: {0}")]
     NotInSpec(WebDriverErrorInfo),
+    #[error("The shadow root is no longer attached to the document: {0}")]
+    DetachedShadowRoot(WebDriverErrorInfo),
     #[error("The click event was intercepted by another element: {0}")]
     ElementClickIntercepted(WebDriverErrorInfo),
     #[error("The element is not interactable: {0}")]
@@ -165,6 +167,8 @@ pub enum WebDriverError {
     NoSuchElement(WebDriverErrorInfo),
     #[error("Frame not found: {0}")]
     NoSuchFrame(WebDriverErrorInfo),
+    #[error("Shadow root not found: {0}")]
+    NoSuchShadowRoot(WebDriverErrorInfo),
     #[error("Window not found: {0}")]
     NoSuchWindow(WebDriverErrorInfo),
     #[error("The Javascript code did not complete within the script timeout (see WebDriver::set_script_timeout()): {0}")]
@@ -197,9 +201,67 @@ pub enum WebDriverError {
     CommandSendError(String),
     #[error("Could not create session: {0}")]
     SessionCreateError(String),
+    #[error("The WebDriver server process failed: {0}")]
+    DriverServiceError(String),
+    #[error("The browser crashed, and the session was replaced: {0}")]
+    SessionRecovered(String),
+    #[error("The element belongs to a session that has been replaced: {0}")]
+    StaleSession(String),
 }
 
 impl WebDriverError {
+    /// The name of the error variant, e.g. `NoSuchElement`.
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            WebDriverError::UnknownResponse(..) => "UnknownResponse",
+            WebDriverError::RequestFailed(..) => "RequestFailed",
+            WebDriverError::NotFound(..) => "NotFound",
+            WebDriverError::ParseError(..) => "ParseError",
+            WebDriverError::Timeout(..) => "Timeout",
+            WebDriverError::Json(..) => "Json",
+            WebDriverError::DecodeError(..) => "DecodeError",
+            WebDriverError::IoError(..) => "IoError",
+            WebDriverError::HttpError(..) => "HttpError",
+            WebDriverError::NotInSpec(..) => "NotInSpec",
+            WebDriverError::DetachedShadowRoot(..) => "DetachedShadowRoot",
+            WebDriverError::ElementClickIntercepted(..) => "ElementClickIntercepted",
+            WebDriverError::ElementNotInteractable(..) => "ElementNotInteractable",
+            WebDriverError::InsecureCertificate(..) => "InsecureCertificate",
+            WebDriverError::InvalidArgument(..) => "InvalidArgument",
+            WebDriverError::InvalidUrl(..) => "InvalidUrl",
+            WebDriverError::InvalidCookieDomain(..) => "InvalidCookieDomain",
+            WebDriverError::InvalidElementState(..) => "InvalidElementState",
+            WebDriverError::InvalidSelector(..) => "InvalidSelector",
+            WebDriverError::InvalidSessionId(..) => "InvalidSessionId",
+            WebDriverError::JavascriptError(..) => "JavascriptError",
+            WebDriverError::MoveTargetOutOfBounds(..) => "MoveTargetOutOfBounds",
+            WebDriverError::NoSuchAlert(..) => "NoSuchAlert",
+            WebDriverError::NoSuchCookie(..) => "NoSuchCookie",
+            WebDriverError::NoSuchElement(..) => "NoSuchElement",
+            WebDriverError::NoSuchFrame(..) => "NoSuchFrame",
+            WebDriverError::NoSuchShadowRoot(..) => "NoSuchShadowRoot",
+            WebDriverError::NoSuchWindow(..) => "NoSuchWindow",
+            WebDriverError::ScriptTimeout(..) => "ScriptTimeout",
+            WebDriverError::SessionNotCreated(..) => "SessionNotCreated",
+            WebDriverError::StaleElementReference(..) => "StaleElementReference",
+            WebDriverError::WebDriverTimeout(..) => "WebDriverTimeout",
+            WebDriverError::UnableToSetCookie(..) => "UnableToSetCookie",
+            WebDriverError::UnableToCaptureScreen(..) => "UnableToCaptureScreen",
+            WebDriverError::UnexpectedAlertOpen(..) => "UnexpectedAlertOpen",
+            WebDriverError::UnknownCommand(..) => "UnknownCommand",
+            WebDriverError::UnknownError(..) => "UnknownError",
+            WebDriverError::UnknownMethod(..) => "UnknownMethod",
+            WebDriverError::UnsupportedOperation(..) => "UnsupportedOperation",
+            WebDriverError::FatalError(..) => "FatalError",
+            WebDriverError::CommandRecvError(..) => "CommandRecvError",
+            WebDriverError::CommandSendError(..) => "CommandSendError",
+            WebDriverError::SessionCreateError(..) => "SessionCreateError",
+            WebDriverError::DriverServiceError(..) => "DriverServiceError",
+            WebDriverError::SessionRecovered(..) => "SessionRecovered",
+            WebDriverError::StaleSession(..) => "StaleSession",
+        }
+    }
+
     /// Create a new WebDriverError by parsing the response from the WebDriver server.
     pub fn parse(status: u16, body: String) -> Self {
         let body_json = match serde_json::from_str(&body) {
@@ -223,7 +285,13 @@ impl WebDriverError {
             }
         }
 
-        match error.as_str() {
+        Self::from_error_code(&error, payload)
+    }
+
+    /// Map a W3C error code (e.g. `no such element`) to the matching error variant.
+    pub(crate) fn from_error_code(error: &str, payload: WebDriverErrorInfo) -> Self {
+        match error {
+            "detached shadow root" => WebDriverError::DetachedShadowRoot(payload),
             "element click intercepted" => WebDriverError::ElementClickIntercepted(payload),
             "element not interactable" => WebDriverError::ElementNotInteractable(payload),
             "insecure certificate" => WebDriverError::InsecureCertificate(payload),
@@ -238,6 +306,7 @@ impl WebDriverError {
             "no such cookie" => WebDriverError::NoSuchCookie(payload),
             "no such element" => WebDriverError::NoSuchElement(payload),
             "no such frame" => WebDriverError::NoSuchFrame(payload),
+            "no such shadow root" => WebDriverError::NoSuchShadowRoot(payload),
             "no such window" => WebDriverError::NoSuchWindow(payload),
             "script timeout" => WebDriverError::ScriptTimeout(payload),
             "session not created" => WebDriverError::SessionNotCreated(payload),
@@ -276,6 +345,30 @@ impl From<reqwest::Error> for WebDriverError {
     }
 }
 
+impl From<http::header::InvalidHeaderName> for WebDriverError {
+    fn from(err: http::header::InvalidHeaderName) -> Self {
+        WebDriverError::ParseError(format!("invalid header name: {err}"))
+    }
+}
+
+impl From<http::header::InvalidHeaderValue> for WebDriverError {
+    fn from(err: http::header::InvalidHeaderValue) -> Self {
+        WebDriverError::ParseError(format!("invalid header value: {err}"))
+    }
+}
+
+impl From<zip::result::ZipError> for WebDriverError {
+    fn from(err: zip::result::ZipError) -> Self {
+        WebDriverError::ParseError(format!("invalid zip archive: {err}"))
+    }
+}
+
+impl From<std::convert::Infallible> for WebDriverError {
+    fn from(err: std::convert::Infallible) -> Self {
+        match err {}
+    }
+}
+
 impl From<serde_json::Error> for WebDriverError {
     fn from(err: serde_json::Error) -> Self {
         WebDriverError::Json(err.to_string())
@@ -0,0 +1,107 @@
+use std::borrow::Cow;
+use std::fmt::Debug;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+/// Trait for formatting a WebDriver BiDi command into its method name and parameters.
+///
+/// This is the BiDi equivalent of [`FormatRequestData`], and can be implemented for
+/// custom commands that are not covered by [`BiDiCommand`].
+///
+/// [`FormatRequestData`]: crate::common::command::FormatRequestData
+pub trait FormatBiDiCommand: Debug {
+    /// The BiDi method name, e.g. `session.subscribe`.
+    fn method(&self) -> Cow<'_, str>;
+
+    /// The command parameters.
+    fn params(&self) -> Value;
+}
+
+/// The events (and optionally the browsing contexts) to subscribe to.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct SubscriptionRequest {
+    /// The event names, e.g. `log.entryAdded`, or module names, e.g. `network`.
+    pub events: Vec<String>,
+    /// Restrict the subscription to these browsing contexts.
+    /// An empty list subscribes globally.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub contexts: Vec<String>,
+}
+
+impl SubscriptionRequest {
+    /// Create a new global subscription request for the specified events.
+    pub fn new<I, S>(events: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            events: events.into_iter().map(Into::into).collect(),
+            contexts: Vec::new(),
+        }
+    }
+
+    /// Restrict the subscription to the specified browsing contexts.
+    pub fn contexts<I, S>(mut self, contexts: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.contexts = contexts.into_iter().map(Into::into).collect();
+        self
+    }
+}
+
+impl<S: Into<String>> From<S> for SubscriptionRequest {
+    fn from(event: S) -> Self {
+        Self::new([event])
+    }
+}
+
+/// The result of a `session.subscribe` command.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct Subscription {
+    /// The subscription id, if the remote end supports subscription ids.
+    #[serde(default)]
+    pub subscription: Option<String>,
+}
+
+/// Commands in the WebDriver BiDi `session` module.
+#[derive(Debug, Clone)]
+pub enum BiDiCommand {
+    /// Get the status of the remote end.
+    SessionStatus,
+    /// Subscribe to the specified events.
+    SessionSubscribe(SubscriptionRequest),
+    /// Unsubscribe from the specified events.
+    SessionUnsubscribe(SubscriptionRequest),
+    /// Unsubscribe using the ids returned from `session.subscribe`.
+    SessionUnsubscribeById(Vec<String>),
+    /// Any other BiDi command, as method name and parameters.
+    Custom(String, Value),
+}
+
+impl FormatBiDiCommand for BiDiCommand {
+    fn method(&self) -> Cow<'_, str> {
+        match self {
+            BiDiCommand::SessionStatus => Cow::Borrowed("session.status"),
+            BiDiCommand::SessionSubscribe(_) => Cow::Borrowed("session.subscribe"),
+            BiDiCommand::SessionUnsubscribe(_) | BiDiCommand::SessionUnsubscribeById(_) => {
+                Cow::Borrowed("session.unsubscribe")
+            }
+            BiDiCommand::Custom(method, _) => Cow::Borrowed(method),
+        }
+    }
+
+    fn params(&self) -> Value {
+        match self {
+            BiDiCommand::SessionStatus => json!({}),
+            BiDiCommand::SessionSubscribe(request) | BiDiCommand::SessionUnsubscribe(request) => {
+                json!(request)
+            }
+            BiDiCommand::SessionUnsubscribeById(ids) => json!({ "subscriptions": ids }),
+            BiDiCommand::Custom(_, params) => params.clone(),
+        }
+    }
+}
@@ -0,0 +1,365 @@
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use futures_util::stream::BoxStream;
+use futures_util::{SinkExt, StreamExt};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tokio::net::TcpStream;
+use tokio::sync::{broadcast, mpsc, oneshot};
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+use url::Url;
+
+use super::{BiDiCommand, BiDiEvent, FormatBiDiCommand, Subscription, SubscriptionRequest};
+use crate::error::{WebDriverError, WebDriverErrorInfo, WebDriverErrorValue, WebDriverResult};
+use crate::IntoUrl;
+
+/// The number of events buffered for each event stream before the oldest are dropped.
+const EVENT_BUFFER_SIZE: usize = 1024;
+
+/// A stream of events received over a WebDriver BiDi connection.
+pub type BiDiEventStream = BoxStream<'static, BiDiEvent>;
+
+type PendingCommands = Arc<Mutex<HashMap<u64, oneshot::Sender<WebDriverResult<Value>>>>>;
+
+/// A WebDriver BiDi connection over WebSocket.
+///
+/// The connection is cheap to clone. All clones share the same underlying WebSocket,
+/// which is closed once the last clone is dropped.
+///
+/// Commands and responses are matched by id, so commands may be sent concurrently.
+/// Events are broadcast to every stream returned by [`BiDiConnection::events()`].
+///
+/// # Example:
+/// ```no_run
+/// # use futures_util::StreamExt;
+/// # use thirtyfour::prelude::*;
+/// # use thirtyfour::extensions::bidi::BiDiEvent;
+/// # use thirtyfour::support::block_on;
+/// #
+/// # fn main() -> WebDriverResult<()> {
+/// #     block_on(async {
+/// let mut caps = DesiredCapabilities::chrome();
+/// caps.set_web_socket_url(true)?;
+/// let driver = WebDriver::new("http://localhost:4444", caps).await?;
+///
+/// let bidi = driver.bidi().await?;
+/// let mut events = bidi.events();
+/// bidi.subscribe(BiDiEvent::LOG_ENTRY_ADDED).await?;
+///
+/// driver.execute("console.log('hello')", Vec::new()).await?;
+/// if let Some(BiDiEvent::LogEntryAdded(entry)) = events.next().await {
+///     assert_eq!(entry.text.as_deref(), Some("hello"));
+/// }
+/// #         driver.quit().await?;
+/// #         Ok(())
+/// #     })
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct BiDiConnection {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    url: Url,
+    next_id: AtomicU64,
+    outgoing: mpsc::UnboundedSender<String>,
+    pending: PendingCommands,
+    events: broadcast::Sender<BiDiEvent>,
+}
+
+impl Debug for BiDiConnection {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BiDiConnection").field("url", &self.inner.url).finish()
+    }
+}
+
+impl BiDiConnection {
+    /// Connect to the specified BiDi WebSocket URL.
+    ///
+    /// This is usually the `webSocketUrl` capability returned from the new session.
+    /// See [`SessionHandle::bidi()`] for connecting to the BiDi endpoint of a session.
+    ///
+    /// [`SessionHandle::bidi()`]: crate::session::handle::SessionHandle::bidi
+    pub async fn connect(url: impl IntoUrl) -> WebDriverResult<Self> {
+        let url = url.into_url()?;
+        let (stream, _) = tokio_tungstenite::connect_async(url.as_str()).await.map_err(|e| {
+            WebDriverError::RequestFailed(format!("unable to connect to BiDi url {url}: {e}"))
+        })?;
+
+        let (outgoing, outgoing_rx) = mpsc::unbounded_channel();
+        let pending = PendingCommands::default();
+        let (events, _) = broadcast::channel(EVENT_BUFFER_SIZE);
+        tokio::spawn(run_socket(stream, outgoing_rx, pending.clone(), events.clone()));
+
+        Ok(Self {
+            inner: Arc::new(Inner {
+                url,
+                next_id: AtomicU64::new(1),
+                outgoing,
+                pending,
+                events,
+            }),
+        })
+    }
+
+    /// The URL of the WebSocket.
+    pub fn url(&self) -> &Url {
+        &self.inner.url
+    }
+
+    /// Send the specified command and return the `result` of the response.
+    pub async fn cmd(&self, command: impl FormatBiDiCommand) -> WebDriverResult<Value> {
+        let id = self.inner.next_id.fetch_add(1, Ordering::Relaxed);
+        let message = json!({
+            "id": id,
+            "method": command.method(),
+            "params": command.params(),
+        });
+        tracing::debug!("bidi command: {message}");
+
+        let (tx, rx) = oneshot::channel();
+        self.inner.pending.lock().unwrap().insert(id, tx);
+        if self.inner.outgoing.send(message.to_string()).is_err() {
+            self.inner.pending.lock().unwrap().remove(&id);
+            return Err(WebDriverError::CommandSendError(
+                "the BiDi connection is closed".to_string(),
+            ));
+        }
+
+        rx.await.map_err(|_| {
+            WebDriverError::CommandRecvError(
+                "the BiDi connection closed before the command completed".to_string(),
+            )
+        })?
+    }
+
+    /// Subscribe to the specified events.
+    ///
+    /// Events are only delivered to streams that already exist, so call
+    /// [`BiDiConnection::events()`] before subscribing to avoid missing any.
+    pub async fn subscribe(
+        &self,
+        request: impl Into<SubscriptionRequest>,
+    ) -> WebDriverResult<Subscription> {
+        let result = self.cmd(BiDiCommand::SessionSubscribe(request.into())).await?;
+        Subscription::deserialize(&result).map_err(|e| {
+            WebDriverError::ParseError(format!("invalid session.subscribe result: {e}"))
+        })
+    }
+
+    /// Unsubscribe from the specified events.
+    pub async fn unsubscribe(
+        &self,
+        request: impl Into<SubscriptionRequest>,
+    ) -> WebDriverResult<()> {
+        self.cmd(BiDiCommand::SessionUnsubscribe(request.into())).await?;
+        Ok(())
+    }
+
+    /// Return a new stream of all events received from now on.
+    ///
+    /// If the stream falls too far behind, the oldest events are skipped.
+    /// The stream ends when the connection is closed.
+    pub fn events(&self) -> BiDiEventStream {
+        let rx = self.inner.events.subscribe();
+        futures_util::stream::unfold(rx, |mut rx| async move {
+            loop {
+                match rx.recv().await {
+                    Ok(event) => return Some((event, rx)),
+                    Err(broadcast::error::RecvError::Lagged(n)) => {
+                        tracing::warn!("BiDi event stream lagged, skipped {n} events");
+                    }
+                    Err(broadcast::error::RecvError::Closed) => return None,
+                }
+            }
+        })
+        .boxed()
+    }
+}
+
+async fn run_socket(
+    stream: WebSocketStream<MaybeTlsStream<TcpStream>>,
+    mut outgoing: mpsc::UnboundedReceiver<String>,
+    pending: PendingCommands,
+    events: broadcast::Sender<BiDiEvent>,
+) {
+    let (mut sink, mut source) = stream.split();
+    loop {
+        tokio::select! {
+            message = outgoing.recv() => match message {
+                Some(text) => {
+                    if let Err(e) = sink.send(Message::Text(text)).await {
+                        tracing::error!("unable to send BiDi message: {e}");
+                        break;
+                    }
+                }
+                None => {
+                    let _ = sink.close().await;
+                    break;
+                }
+            },
+            message = source.next() => match message {
+                Some(Ok(Message::Text(text))) => dispatch(&text, &pending, &events),
+                Some(Ok(Message::Close(_))) | None => break,
+                Some(Ok(_)) => {}
+                Some(Err(e)) => {
+                    tracing::error!("BiDi connection failed: {e}");
+                    break;
+                }
+            },
+        }
+    }
+
+    // Dropping the senders fails any commands still waiting for a response.
+    pending.lock().unwrap().clear();
+}
+
+fn dispatch(text: &str, pending: &PendingCommands, events: &broadcast::Sender<BiDiEvent>) {
+    tracing::debug!("bidi message: {text}");
+    let mut message: Value = match serde_json::from_str(text) {
+        Ok(x) => x,
+        Err(e) => {
+            tracing::warn!("unable to parse BiDi message: {e}");
+            return;
+        }
+    };
+
+    let kind = message["type"].as_str().unwrap_or_default().to_string();
+    match (kind.as_str(), message["id"].as_u64()) {
+        ("event", _) => {
+            let method = message["method"].as_str().unwrap_or_default().to_string();
+            let params = message["params"].take();
+            // Sending only fails if there are no streams, in which case nobody is listening.
+            let _ = events.send(BiDiEvent::from_raw(method, params));
+        }
+        (_, Some(id)) => {
+            let Some(tx) = pending.lock().unwrap().remove(&id) else {
+                tracing::warn!("received BiDi response for unknown command id {id}");
+                return;
+            };
+            let result = match kind.as_str() {
+                "error" => Err(parse_error(message)),
+                _ => Ok(message["result"].take()),
+            };
+            let _ = tx.send(result);
+        }
+        _ => tracing::warn!("unexpected BiDi message: {text}"),
+    }
+}
+
+fn parse_error(message: Value) -> WebDriverError {
+    #[derive(Debug, Deserialize)]
+    struct ErrorResponse {
+        #[serde(default)]
+        error: String,
+        #[serde(default)]
+        message: String,
+        #[serde(default)]
+        stacktrace: Option<String>,
+    }
+
+    let resp: ErrorResponse = match serde_json::from_value(message) {
+        Ok(x) => x,
+        Err(e) => return WebDriverError::Json(format!("invalid BiDi error response: {e}")),
+    };
+    let payload = WebDriverErrorInfo {
+        status: 0,
+        error: resp.error.clone(),
+        value: WebDriverErrorValue {
+            message: resp.message,
+            error: Some(resp.error.clone()),
+            stacktrace: resp.stacktrace,
+            data: None,
+        },
+    };
+    WebDriverError::from_error_code(&resp.error, payload)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_matches::assert_matches;
+    use tokio::net::TcpListener;
+
+    /// Start a WebSocket stand-in that answers every command the way a BiDi remote end
+    /// would, emitting a `log.entryAdded` event after each successful subscription.
+    async fn start_server() -> Url {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+            while let Some(Ok(Message::Text(text))) = ws.next().await {
+                let command: Value = serde_json::from_str(&text).unwrap();
+                let id = &command["id"];
+                let reply = match command["method"].as_str().unwrap() {
+                    "session.subscribe" if command["params"]["events"][0] == "malformed" => {
+                        json!({ "type": "success", "id": id, "result": { "subscription": 1 } })
+                    }
+                    "session.subscribe" => {
+                        let reply = json!({ "type": "success", "id": id, "result": {
+                            "subscription": "sub-1"
+                        }});
+                        ws.send(Message::Text(reply.to_string())).await.unwrap();
+                        json!({ "type": "event", "method": "log.entryAdded", "params": {
+                            "type": "console",
+                            "level": "info",
+                            "text": "hello",
+                            "timestamp": 1,
+                            "method": "log",
+                            "source": { "realm": "r1", "context": "c1" }
+                        }})
+                    }
+                    _ => json!({
+                        "type": "error",
+                        "id": id,
+                        "error": "unknown command",
+                        "message": "not supported",
+                    }),
+                };
+                ws.send(Message::Text(reply.to_string())).await.unwrap();
+            }
+        });
+        Url::parse(&format!("ws://{addr}/session")).unwrap()
+    }
+
+    #[tokio::test]
+    async fn subscribe_and_receive_events() {
+        let url = start_server().await;
+        let bidi = BiDiConnection::connect(&url).await.unwrap();
+        let mut events = bidi.events();
+
+        let subscription = bidi.subscribe(BiDiEvent::LOG_ENTRY_ADDED).await.unwrap();
+        assert_eq!(subscription.subscription.as_deref(), Some("sub-1"));
+
+        let event = events.next().await.unwrap();
+        assert_eq!(event.method(), BiDiEvent::LOG_ENTRY_ADDED);
+        assert_matches!(event, BiDiEvent::LogEntryAdded(entry) => {
+            assert_eq!(entry.text.as_deref(), Some("hello"));
+            assert_eq!(entry.source.context.as_deref(), Some("c1"));
+        });
+    }
+
+    #[tokio::test]
+    async fn malformed_subscription() {
+        let url = start_server().await;
+        let bidi = BiDiConnection::connect(url).await.unwrap();
+        let result = bidi.subscribe("malformed").await;
+        assert_matches!(result, Err(WebDriverError::ParseError(_)));
+    }
+
+    #[tokio::test]
+    async fn error_response() {
+        let url = start_server().await;
+        let bidi = BiDiConnection::connect(url).await.unwrap();
+        let result = bidi.cmd(BiDiCommand::SessionStatus).await;
+        assert_matches!(result, Err(WebDriverError::UnknownCommand(info)) => {
+            assert_eq!(info.value.message, "not supported");
+        });
+    }
+}
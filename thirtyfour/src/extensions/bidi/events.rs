@@ -0,0 +1,239 @@
+use serde::Deserialize;
+use serde_json::Value;
+
+/// The source of a log entry.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct LogSource {
+    /// The realm the entry originated from.
+    #[serde(default)]
+    pub realm: String,
+    /// The browsing context the entry originated from.
+    #[serde(default)]
+    pub context: Option<String>,
+}
+
+/// Parameters of the `log.entryAdded` event.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogEntry {
+    /// The entry type, e.g. `console` or `javascript`.
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// The log level, e.g. `debug`, `info`, `warn` or `error`.
+    pub level: String,
+    /// The log text.
+    #[serde(default)]
+    pub text: Option<String>,
+    /// The time the entry was logged, in milliseconds since the epoch.
+    pub timestamp: u64,
+    /// The console method, e.g. `log` or `error` (console entries only).
+    #[serde(default)]
+    pub method: Option<String>,
+    /// Where the entry originated.
+    #[serde(default)]
+    pub source: LogSource,
+    /// The stack trace, if any.
+    #[serde(default)]
+    pub stack_trace: Option<Value>,
+}
+
+/// Parameters of the `browsingContext` navigation events.
+#[derive(Debug, Clone, Deserialize)]
+pub struct NavigationInfo {
+    /// The browsing context being navigated.
+    pub context: String,
+    /// The navigation id.
+    #[serde(default)]
+    pub navigation: Option<String>,
+    /// The time of the event, in milliseconds since the epoch.
+    pub timestamp: u64,
+    /// The URL being navigated to.
+    pub url: String,
+}
+
+/// Parameters of the `browsingContext.contextCreated` and
+/// `browsingContext.contextDestroyed` events.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BrowsingContextInfo {
+    /// The browsing context id.
+    pub context: String,
+    /// The current URL of the context.
+    pub url: String,
+    /// The parent context, if this is a child context (e.g. an iframe).
+    #[serde(default)]
+    pub parent: Option<String>,
+    /// The user context the browsing context belongs to.
+    #[serde(default)]
+    pub user_context: Option<String>,
+}
+
+/// The request part of a network event.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkRequest {
+    /// The request id.
+    pub request: String,
+    /// The request URL.
+    pub url: String,
+    /// The request method.
+    pub method: String,
+}
+
+/// The response part of a network event.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkResponse {
+    /// The response URL.
+    pub url: String,
+    /// The HTTP status code.
+    pub status: u16,
+    /// The HTTP status text.
+    #[serde(default)]
+    pub status_text: String,
+    /// The MIME type of the response.
+    #[serde(default)]
+    pub mime_type: String,
+}
+
+/// Parameters of the `network` module events.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkEvent {
+    /// The browsing context that made the request.
+    #[serde(default)]
+    pub context: Option<String>,
+    /// The navigation id, if the request is part of a navigation.
+    #[serde(default)]
+    pub navigation: Option<String>,
+    /// The number of redirects so far.
+    #[serde(default)]
+    pub redirect_count: u32,
+    /// The request.
+    pub request: NetworkRequest,
+    /// The response, for `responseStarted` and `responseCompleted` events.
+    #[serde(default)]
+    pub response: Option<NetworkResponse>,
+    /// The error text, for `fetchError` events.
+    #[serde(default)]
+    pub error_text: Option<String>,
+    /// The time of the event, in milliseconds since the epoch.
+    pub timestamp: u64,
+}
+
+/// An event received over a WebDriver BiDi connection.
+///
+/// Well-known events are parsed into typed variants. Everything else, including
+/// well-known events whose parameters could not be parsed, is returned as
+/// [`BiDiEvent::Other`].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum BiDiEvent {
+    /// `log.entryAdded`
+    LogEntryAdded(LogEntry),
+    /// `browsingContext.contextCreated`
+    ContextCreated(BrowsingContextInfo),
+    /// `browsingContext.contextDestroyed`
+    ContextDestroyed(BrowsingContextInfo),
+    /// `browsingContext.navigationStarted`
+    NavigationStarted(NavigationInfo),
+    /// `browsingContext.domContentLoaded`
+    DomContentLoaded(NavigationInfo),
+    /// `browsingContext.load`
+    Load(NavigationInfo),
+    /// `network.beforeRequestSent`
+    BeforeRequestSent(NetworkEvent),
+    /// `network.responseStarted`
+    ResponseStarted(NetworkEvent),
+    /// `network.responseCompleted`
+    ResponseCompleted(NetworkEvent),
+    /// `network.fetchError`
+    FetchError(NetworkEvent),
+    /// Any other event.
+    Other {
+        /// The event method name.
+        method: String,
+        /// The raw event parameters.
+        params: Value,
+    },
+}
+
+impl BiDiEvent {
+    /// The `log.entryAdded` event name.
+    pub const LOG_ENTRY_ADDED: &'static str = "log.entryAdded";
+    /// The `browsingContext.contextCreated` event name.
+    pub const CONTEXT_CREATED: &'static str = "browsingContext.contextCreated";
+    /// The `browsingContext.contextDestroyed` event name.
+    pub const CONTEXT_DESTROYED: &'static str = "browsingContext.contextDestroyed";
+    /// The `browsingContext.navigationStarted` event name.
+    pub const NAVIGATION_STARTED: &'static str = "browsingContext.navigationStarted";
+    /// The `browsingContext.domContentLoaded` event name.
+    pub const DOM_CONTENT_LOADED: &'static str = "browsingContext.domContentLoaded";
+    /// The `browsingContext.load` event name.
+    pub const LOAD: &'static str = "browsingContext.load";
+    /// The `network.beforeRequestSent` event name.
+    pub const BEFORE_REQUEST_SENT: &'static str = "network.beforeRequestSent";
+    /// The `network.responseStarted` event name.
+    pub const RESPONSE_STARTED: &'static str = "network.responseStarted";
+    /// The `network.responseCompleted` event name.
+    pub const RESPONSE_COMPLETED: &'static str = "network.responseCompleted";
+    /// The `network.fetchError` event name.
+    pub const FETCH_ERROR: &'static str = "network.fetchError";
+
+    /// Parse an event from its method name and parameters.
+    pub fn from_raw(method: String, params: Value) -> Self {
+        fn parse<T: serde::de::DeserializeOwned>(
+            method: String,
+            params: Value,
+            f: impl FnOnce(T) -> BiDiEvent,
+        ) -> BiDiEvent {
+            match T::deserialize(&params) {
+                Ok(x) => f(x),
+                Err(e) => {
+                    tracing::debug!("unable to parse BiDi event {method}: {e}");
+                    BiDiEvent::Other {
+                        method,
+                        params,
+                    }
+                }
+            }
+        }
+
+        match method.as_str() {
+            Self::LOG_ENTRY_ADDED => parse(method, params, BiDiEvent::LogEntryAdded),
+            Self::CONTEXT_CREATED => parse(method, params, BiDiEvent::ContextCreated),
+            Self::CONTEXT_DESTROYED => parse(method, params, BiDiEvent::ContextDestroyed),
+            Self::NAVIGATION_STARTED => parse(method, params, BiDiEvent::NavigationStarted),
+            Self::DOM_CONTENT_LOADED => parse(method, params, BiDiEvent::DomContentLoaded),
+            Self::LOAD => parse(method, params, BiDiEvent::Load),
+            Self::BEFORE_REQUEST_SENT => parse(method, params, BiDiEvent::BeforeRequestSent),
+            Self::RESPONSE_STARTED => parse(method, params, BiDiEvent::ResponseStarted),
+            Self::RESPONSE_COMPLETED => parse(method, params, BiDiEvent::ResponseCompleted),
+            Self::FETCH_ERROR => parse(method, params, BiDiEvent::FetchError),
+            _ => BiDiEvent::Other {
+                method,
+                params,
+            },
+        }
+    }
+
+    /// The event method name, e.g. `log.entryAdded`.
+    pub fn method(&self) -> &str {
+        match self {
+            BiDiEvent::LogEntryAdded(_) => Self::LOG_ENTRY_ADDED,
+            BiDiEvent::ContextCreated(_) => Self::CONTEXT_CREATED,
+            BiDiEvent::ContextDestroyed(_) => Self::CONTEXT_DESTROYED,
+            BiDiEvent::NavigationStarted(_) => Self::NAVIGATION_STARTED,
+            BiDiEvent::DomContentLoaded(_) => Self::DOM_CONTENT_LOADED,
+            BiDiEvent::Load(_) => Self::LOAD,
+            BiDiEvent::BeforeRequestSent(_) => Self::BEFORE_REQUEST_SENT,
+            BiDiEvent::ResponseStarted(_) => Self::RESPONSE_STARTED,
+            BiDiEvent::ResponseCompleted(_) => Self::RESPONSE_COMPLETED,
+            BiDiEvent::FetchError(_) => Self::FETCH_ERROR,
+            BiDiEvent::Other {
+                method,
+                ..
+            } => method,
+        }
+    }
+}
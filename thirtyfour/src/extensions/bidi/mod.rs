@@ -0,0 +1,7 @@
+mod bidicommand;
+mod connection;
+mod events;
+
+pub use bidicommand::{BiDiCommand, FormatBiDiCommand, Subscription, SubscriptionRequest};
+pub use connection::{BiDiConnection, BiDiEventStream};
+pub use events::*;
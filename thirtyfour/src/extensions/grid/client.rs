@@ -0,0 +1,292 @@
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use http::HeaderValue;
+use url::Url;
+
+use super::{GridCommand, GridSession, GridStatus};
+use crate::common::command::{Command, FormatRequestData};
+use crate::common::config::WebDriverConfig;
+use crate::error::{WebDriverError, WebDriverResult};
+use crate::session::http::{run_webdriver_cmd, HttpClient};
+use crate::{IntoUrl, SessionId};
+
+/// The interval between `/status` requests while waiting for a free slot.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// A client for the administrative endpoints of a Selenium Grid 4 server.
+///
+/// # Example
+/// ```no_run
+/// # use std::time::Duration;
+/// # use thirtyfour::prelude::*;
+/// # use thirtyfour::support::block_on;
+/// use thirtyfour::extensions::grid::GridClient;
+/// #
+/// # fn main() -> WebDriverResult<()> {
+/// #     block_on(async {
+/// let grid = GridClient::new("http://localhost:4444")?;
+/// // Wait until the Grid can run another Chrome session.
+/// grid.wait_for_free_slot("chrome", Duration::from_secs(60)).await?;
+///
+/// let caps = DesiredCapabilities::chrome();
+/// let driver = WebDriver::new("http://localhost:4444", caps).await?;
+/// let session = grid.find_session(driver.session_id()).await?.unwrap();
+/// println!("Running on node {} with CDP at {:?}", session.uri, session.cdp_url());
+/// #         driver.quit().await?;
+/// #         Ok(())
+/// #     })
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct GridClient {
+    client: Arc<dyn HttpClient>,
+    server_url: Url,
+    config: WebDriverConfig,
+    registration_secret: HeaderValue,
+}
+
+impl Debug for GridClient {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GridClient")
+            .field("server_url", &self.server_url.as_str())
+            .field("config", &self.config)
+            .finish()
+    }
+}
+
+impl GridClient {
+    /// Create a new `GridClient` for the Grid at the specified URL.
+    #[cfg(feature = "reqwest")]
+    pub fn new(server_url: impl IntoUrl) -> WebDriverResult<Self> {
+        let client =
+            crate::session::http::create_reqwest_client(crate::web_driver::DEFAULT_REQUEST_TIMEOUT);
+        Self::new_with_client(server_url, client)
+    }
+
+    /// Create a new `GridClient` for the Grid at the specified URL, using the specified client.
+    pub fn new_with_client(
+        server_url: impl IntoUrl,
+        client: impl HttpClient,
+    ) -> WebDriverResult<Self> {
+        Ok(Self {
+            client: Arc::new(client),
+            server_url: server_url.into_url()?,
+            config: WebDriverConfig::default(),
+            registration_secret: HeaderValue::from_static(""),
+        })
+    }
+
+    /// Use the specified `WebDriverConfig` (e.g. for extra headers or command layers).
+    pub fn with_config(mut self, config: WebDriverConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Set the registration secret the Grid was started with.
+    ///
+    /// This is required for draining nodes and deleting sessions if the Grid was
+    /// started with `--registration-secret`.
+    pub fn registration_secret(mut self, secret: &str) -> WebDriverResult<Self> {
+        let mut secret = HeaderValue::from_str(secret)?;
+        secret.set_sensitive(true);
+        self.registration_secret = secret;
+        Ok(self)
+    }
+
+    /// The URL of the Grid.
+    pub fn server_url(&self) -> &Url {
+        &self.server_url
+    }
+
+    /// Get the status of the Grid, including its nodes and their slots.
+    pub async fn status(&self) -> WebDriverResult<GridStatus> {
+        let request = Command::Status.format_request(&SessionId::null());
+        let r = run_webdriver_cmd(&*self.client, &request, &self.server_url, &self.config).await?;
+        r.value()
+    }
+
+    /// Wait until a node that is accepting new sessions has a free slot for the
+    /// specified browser.
+    pub async fn wait_for_free_slot(
+        &self,
+        browser_name: &str,
+        timeout: Duration,
+    ) -> WebDriverResult<()> {
+        let start = Instant::now();
+        loop {
+            if self.status().await?.free_slots_for(browser_name) > 0 {
+                return Ok(());
+            }
+            if start.elapsed() >= timeout {
+                return Err(WebDriverError::Timeout(format!(
+                    "no free slot for {browser_name} after {timeout:?}"
+                )));
+            }
+            crate::support::sleep(POLL_INTERVAL).await;
+        }
+    }
+
+    /// Find the session with the specified id, including the node running it.
+    ///
+    /// Returns `None` if the Grid is not running the session.
+    pub async fn find_session(
+        &self,
+        session_id: &SessionId,
+    ) -> WebDriverResult<Option<GridSession>> {
+        Ok(self.status().await?.session(session_id).cloned())
+    }
+
+    /// Stop the node with the specified id from accepting new sessions.
+    ///
+    /// The node shuts down once its current sessions have finished.
+    pub async fn drain_node(&self, node_id: &str) -> WebDriverResult<()> {
+        let request = GridCommand::DrainNode(node_id.to_string())
+            .format_with_secret(&self.registration_secret);
+        run_webdriver_cmd(&*self.client, &request, &self.server_url, &self.config).await?;
+        Ok(())
+    }
+
+    /// Delete the specified session on the node running it.
+    ///
+    /// Unlike quitting the session through the Grid router, this works for sessions
+    /// that no longer respond, e.g. because their `WebDriver` was leaked.
+    pub async fn delete_session(&self, session_id: &SessionId) -> WebDriverResult<()> {
+        let session = self.find_session(session_id).await?.ok_or_else(|| {
+            WebDriverError::RequestFailed(format!(
+                "session {session_id} is not running on the Grid"
+            ))
+        })?;
+        let node_url = Url::parse(&session.uri)
+            .map_err(|e| WebDriverError::ParseError(format!("invalid node url: {e}")))?;
+        let request = GridCommand::DeleteNodeSession(session_id.clone())
+            .format_with_secret(&self.registration_secret);
+        run_webdriver_cmd(&*self.client, &request, &node_url, &self.config).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use bytes::Bytes;
+    use http::{Request, Response};
+    use serde_json::{json, Value};
+
+    use super::*;
+    use crate::extensions::grid::NodeAvailability;
+    use crate::session::http::Body;
+
+    /// Answers `/status` with a Grid of one node, and records all other requests.
+    #[derive(Default)]
+    struct FakeGrid {
+        requests: Mutex<Vec<(String, String, String)>>,
+    }
+
+    #[async_trait::async_trait]
+    impl HttpClient for FakeGrid {
+        async fn send(&self, request: Request<Body<'_>>) -> WebDriverResult<Response<Bytes>> {
+            let body = match request.uri().path() {
+                "/status" => json!({ "value": status() }),
+                _ => {
+                    let secret = request.headers().get("x-registration-secret").unwrap();
+                    self.requests.lock().unwrap().push((
+                        request.method().to_string(),
+                        request.uri().to_string(),
+                        secret.to_str().unwrap().to_string(),
+                    ));
+                    json!({ "value": null })
+                }
+            };
+            Ok(Response::builder().status(200).body(Bytes::from(body.to_string())).unwrap())
+        }
+    }
+
+    fn status() -> Value {
+        let slot = |id: &str, session: Value| {
+            json!({
+                "id": { "hostId": "node-1", "id": id },
+                "lastStarted": "1970-01-01T00:00:00Z",
+                "stereotype": { "browserName": "chrome", "platformName": "linux" },
+                "session": session,
+            })
+        };
+        json!({
+            "ready": true,
+            "message": "Selenium Grid ready.",
+            "nodes": [{
+                "id": "node-1",
+                "uri": "http://10.0.0.2:5555",
+                "maxSessions": 2,
+                "osInfo": { "arch": "amd64", "name": "Linux", "version": "6.1" },
+                "heartbeatPeriod": 60000,
+                "availability": "UP",
+                "version": "4.25.0",
+                "slots": [
+                    slot("slot-1", Value::Null),
+                    slot("slot-2", json!({
+                        "sessionId": "abc123",
+                        "start": "2024-01-01T00:00:00Z",
+                        "stereotype": { "browserName": "chrome" },
+                        "capabilities": {
+                            "browserName": "chrome",
+                            "se:cdp": "ws://localhost:4444/session/abc123/se/cdp",
+                        },
+                        "uri": "http://10.0.0.2:5555",
+                    })),
+                ],
+            }],
+        })
+    }
+
+    #[tokio::test]
+    async fn status_and_sessions() {
+        let grid =
+            GridClient::new_with_client("http://localhost:4444", FakeGrid::default()).unwrap();
+        let status = grid.status().await.unwrap();
+        assert!(status.ready);
+        assert_eq!(status.nodes[0].availability, NodeAvailability::Up);
+        assert_eq!(status.free_slots(), 1);
+        assert_eq!(status.free_slots_for("chrome"), 1);
+        assert_eq!(status.free_slots_for("firefox"), 0);
+        grid.wait_for_free_slot("chrome", Duration::ZERO).await.unwrap();
+        assert!(grid.wait_for_free_slot("firefox", Duration::ZERO).await.is_err());
+
+        let session = grid.find_session(&SessionId::from("abc123")).await.unwrap().unwrap();
+        assert_eq!(session.uri, "http://10.0.0.2:5555");
+        assert_eq!(session.cdp_url(), Some("ws://localhost:4444/session/abc123/se/cdp"));
+        assert_eq!(session.bidi_url(), None);
+        assert!(grid.find_session(&SessionId::from("missing")).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn drain_and_delete() {
+        let client = Arc::new(FakeGrid::default());
+        let grid = GridClient::new_with_client("http://localhost:4444", Arc::clone(&client))
+            .unwrap()
+            .registration_secret("s3cret")
+            .unwrap();
+        grid.drain_node("node-1").await.unwrap();
+        grid.delete_session(&SessionId::from("abc123")).await.unwrap();
+        assert!(grid.delete_session(&SessionId::from("missing")).await.is_err());
+
+        let requests = client.requests.lock().unwrap().clone();
+        assert_eq!(
+            requests,
+            [
+                (
+                    "POST".to_string(),
+                    "http://localhost:4444/se/grid/distributor/node/node-1/drain".to_string(),
+                    "s3cret".to_string()
+                ),
+                (
+                    "DELETE".to_string(),
+                    "http://10.0.0.2:5555/se/grid/node/session/abc123".to_string(),
+                    "s3cret".to_string()
+                ),
+            ]
+        );
+    }
+}
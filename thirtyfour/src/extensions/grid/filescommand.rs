@@ -0,0 +1,65 @@
+use std::io::{Cursor, Read};
+use std::sync::Arc;
+
+use http::Method;
+use serde_json::{json, Value};
+
+use crate::common::command::ExtensionCommand;
+use crate::error::{WebDriverError, WebDriverResult};
+
+/// Selenium commands for transferring files to and from the remote browser.
+///
+/// These are sent as a [`Command::ExtensionCommand`](crate::common::command::Command),
+/// e.g. by `WebDriver::downloadable_files()` or `WebElement::upload_file()`.
+#[derive(Debug)]
+pub enum FilesCommand {
+    /// List the names of the downloaded files.
+    GetDownloadableFiles,
+    /// Fetch the downloaded file with the specified name, as a zip archive.
+    DownloadFile(String),
+    /// Delete all downloaded files.
+    DeleteDownloadableFiles,
+    /// Upload a file, as a base64 encoded zip archive containing just that file.
+    UploadFile(String),
+}
+
+impl ExtensionCommand for FilesCommand {
+    fn parameters_json(&self) -> Option<Value> {
+        match self {
+            FilesCommand::DownloadFile(name) => Some(json!({ "name": name })),
+            FilesCommand::UploadFile(archive) => Some(json!({ "file": archive })),
+            _ => None,
+        }
+    }
+
+    fn method(&self) -> Method {
+        match self {
+            FilesCommand::GetDownloadableFiles => Method::GET,
+            FilesCommand::DownloadFile(_) => Method::POST,
+            FilesCommand::DeleteDownloadableFiles => Method::DELETE,
+            FilesCommand::UploadFile(_) => Method::POST,
+        }
+    }
+
+    fn endpoint(&self) -> Arc<str> {
+        match self {
+            FilesCommand::UploadFile(_) => Arc::from("/se/file"),
+            _ => Arc::from("/se/files"),
+        }
+    }
+}
+
+/// Extract the contents of the only file in a zip archive.
+pub(crate) fn unzip_file(archive: &[u8]) -> WebDriverResult<Vec<u8>> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(archive))?;
+    if archive.len() != 1 {
+        return Err(WebDriverError::ParseError(format!(
+            "expected a zip archive containing one file, found {} files",
+            archive.len()
+        )));
+    }
+    let mut file = archive.by_index(0)?;
+    let mut contents = Vec::with_capacity(file.size() as usize);
+    file.read_to_end(&mut contents)?;
+    Ok(contents)
+}
@@ -0,0 +1,43 @@
+use http::{HeaderName, HeaderValue, Method};
+
+use crate::common::command::FormatRequestData;
+use crate::{RequestData, SessionId};
+
+/// The header used by Selenium Grid to authenticate administrative requests.
+pub(crate) const REGISTRATION_SECRET_HEADER: HeaderName =
+    HeaderName::from_static("x-registration-secret");
+
+/// Administrative commands specific to Selenium Grid 4.
+///
+/// These are sent by [`GridClient`](super::GridClient) rather than by a session.
+#[derive(Debug)]
+pub enum GridCommand {
+    /// Stop the node with the specified id from accepting new sessions, and shut it
+    /// down once its current sessions have finished.
+    DrainNode(String),
+    /// Delete the specified session. This must be sent to the node running the session.
+    DeleteNodeSession(SessionId),
+}
+
+impl FormatRequestData for GridCommand {
+    fn format_request(&self, _session_id: &SessionId) -> RequestData {
+        match &self {
+            GridCommand::DrainNode(node_id) => RequestData::new(
+                Method::POST,
+                format!("/se/grid/distributor/node/{}/drain", node_id),
+            ),
+            GridCommand::DeleteNodeSession(session_id) => {
+                RequestData::new(Method::DELETE, format!("/se/grid/node/session/{}", session_id))
+            }
+        }
+    }
+}
+
+impl GridCommand {
+    /// Format the command, adding the registration secret required by the Grid.
+    pub(crate) fn format_with_secret(&self, secret: &HeaderValue) -> RequestData {
+        self.format_request(&SessionId::null())
+            .with_command_name(self.command_name())
+            .add_header(REGISTRATION_SECRET_HEADER, secret.clone())
+    }
+}
@@ -0,0 +1,10 @@
+mod client;
+mod filescommand;
+mod gridcommand;
+mod status;
+
+pub use client::GridClient;
+pub(crate) use filescommand::unzip_file;
+pub use filescommand::FilesCommand;
+pub use gridcommand::GridCommand;
+pub use status::{GridNode, GridSession, GridSlot, GridStatus, NodeAvailability, SlotId};
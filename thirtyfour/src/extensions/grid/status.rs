@@ -0,0 +1,157 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{Capabilities, SessionId};
+
+/// The status of a Selenium Grid, including the nodes registered with it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GridStatus {
+    /// Whether the Grid is ready to accept new sessions.
+    pub ready: bool,
+    /// The current status message.
+    pub message: String,
+    /// The nodes registered with the Grid.
+    #[serde(default)]
+    pub nodes: Vec<GridNode>,
+}
+
+impl GridStatus {
+    /// The number of free slots on nodes that are accepting new sessions.
+    pub fn free_slots(&self) -> usize {
+        self.nodes.iter().filter(|x| x.is_up()).map(|x| x.free_slots().count()).sum()
+    }
+
+    /// The number of free slots for the specified browser on nodes that are accepting
+    /// new sessions.
+    pub fn free_slots_for(&self, browser_name: &str) -> usize {
+        self.nodes
+            .iter()
+            .filter(|x| x.is_up())
+            .flat_map(|x| x.free_slots())
+            .filter(|x| x.browser_name() == Some(browser_name))
+            .count()
+    }
+
+    /// All sessions currently running on the Grid.
+    pub fn sessions(&self) -> impl Iterator<Item = &GridSession> {
+        self.nodes.iter().flat_map(|x| x.slots.iter()).filter_map(|x| x.session.as_ref())
+    }
+
+    /// Find the session with the specified id.
+    pub fn session(&self, session_id: &SessionId) -> Option<&GridSession> {
+        let session_id = session_id.to_string();
+        self.sessions().find(|x| x.session_id == session_id)
+    }
+}
+
+/// Whether a Grid node is accepting new sessions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum NodeAvailability {
+    /// The node is accepting new sessions.
+    Up,
+    /// The node is finishing its current sessions and will then shut down.
+    Draining,
+    /// The node is not reachable.
+    Down,
+    /// Any other state reported by the Grid.
+    #[serde(other)]
+    Unknown,
+}
+
+/// A node registered with a Selenium Grid.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GridNode {
+    /// The node id.
+    pub id: String,
+    /// The URI of the node.
+    pub uri: String,
+    /// The maximum number of concurrent sessions on the node.
+    #[serde(default)]
+    pub max_sessions: u32,
+    /// Whether the node is accepting new sessions.
+    pub availability: NodeAvailability,
+    /// The Selenium version running on the node.
+    #[serde(default)]
+    pub version: String,
+    /// The slots on the node, one for each session it can run.
+    #[serde(default)]
+    pub slots: Vec<GridSlot>,
+}
+
+impl GridNode {
+    /// Whether the node is accepting new sessions.
+    pub fn is_up(&self) -> bool {
+        self.availability == NodeAvailability::Up
+    }
+
+    /// The slots that are not running a session.
+    pub fn free_slots(&self) -> impl Iterator<Item = &GridSlot> {
+        self.slots.iter().filter(|x| x.is_free())
+    }
+}
+
+/// The id of a slot on a Grid node.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SlotId {
+    /// The id of the node the slot is on.
+    pub host_id: String,
+    /// The id of the slot.
+    pub id: String,
+}
+
+/// A slot on a Grid node, which can run a single session.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GridSlot {
+    /// The slot id.
+    pub id: SlotId,
+    /// The capabilities of the sessions this slot can run.
+    pub stereotype: Capabilities,
+    /// The session currently running in this slot, if any.
+    #[serde(default)]
+    pub session: Option<GridSession>,
+}
+
+impl GridSlot {
+    /// Whether the slot is not running a session.
+    pub fn is_free(&self) -> bool {
+        self.session.is_none()
+    }
+
+    /// The browser this slot runs, from the `browserName` capability of its stereotype.
+    pub fn browser_name(&self) -> Option<&str> {
+        self.stereotype.get("browserName").and_then(|x| x.as_str())
+    }
+}
+
+/// A session running on a Grid node.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GridSession {
+    /// The session id.
+    pub session_id: String,
+    /// The URI of the node running the session.
+    pub uri: String,
+    /// The time the session was started.
+    #[serde(default)]
+    pub start: String,
+    /// The stereotype of the slot running the session.
+    #[serde(default)]
+    pub stereotype: Capabilities,
+    /// The capabilities negotiated for the session.
+    #[serde(default)]
+    pub capabilities: Capabilities,
+}
+
+impl GridSession {
+    /// The Chrome DevTools Protocol endpoint for the session, proxied by the Grid.
+    pub fn cdp_url(&self) -> Option<&str> {
+        self.capabilities.get("se:cdp").and_then(|x| x.as_str())
+    }
+
+    /// The WebDriver BiDi endpoint for the session, proxied by the Grid.
+    pub fn bidi_url(&self) -> Option<&str> {
+        self.capabilities.get("se:bidi").and_then(|x| x.as_str())
+    }
+}
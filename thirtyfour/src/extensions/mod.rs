@@ -1,6 +1,11 @@
 /// Extensions for working with Firefox Addons.
 pub mod addons;
+/// WebDriver BiDi connections over WebSocket.
+#[cfg(feature = "bidi")]
+pub mod bidi;
 /// Extensions for Chrome Devtools Protocol
 pub mod cdp;
+/// Administration and introspection of Selenium Grid 4.
+pub mod grid;
 // ElementQuery and ElementWaiter interfaces.
 pub mod query;
@@ -149,6 +149,66 @@ where
     }
 }
 
+/// Predicate that returns true for elements that have the specified computed role.
+/// See the `Needle` documentation for more details on text matching rules.
+pub fn element_has_role<N>(role: N, ignore_errors: bool) -> impl ElementPredicate
+where
+    N: Needle + Clone + Send + Sync + 'static,
+{
+    move |elem: &WebElement| {
+        let elem = elem.clone();
+        let role = role.clone();
+        async move {
+            handle_errors(elem.computed_role().await.map(|x| role.is_match(&x)), ignore_errors)
+        }
+    }
+}
+
+/// Predicate that returns true for elements that do not have the specified computed role.
+/// See the `Needle` documentation for more details on text matching rules.
+pub fn element_lacks_role<N>(role: N, ignore_errors: bool) -> impl ElementPredicate
+where
+    N: Needle + Clone + Send + Sync + 'static,
+{
+    move |elem: &WebElement| {
+        let elem = elem.clone();
+        let role = role.clone();
+        async move {
+            handle_errors(elem.computed_role().await.map(|x| !role.is_match(&x)), ignore_errors)
+        }
+    }
+}
+
+/// Predicate that returns true for elements that have the specified accessible name.
+/// See the `Needle` documentation for more details on text matching rules.
+pub fn element_has_accessible_name<N>(name: N, ignore_errors: bool) -> impl ElementPredicate
+where
+    N: Needle + Clone + Send + Sync + 'static,
+{
+    move |elem: &WebElement| {
+        let elem = elem.clone();
+        let name = name.clone();
+        async move {
+            handle_errors(elem.computed_label().await.map(|x| name.is_match(&x)), ignore_errors)
+        }
+    }
+}
+
+/// Predicate that returns true for elements that do not have the specified accessible name.
+/// See the `Needle` documentation for more details on text matching rules.
+pub fn element_lacks_accessible_name<N>(name: N, ignore_errors: bool) -> impl ElementPredicate
+where
+    N: Needle + Clone + Send + Sync + 'static,
+{
+    move |elem: &WebElement| {
+        let elem = elem.clone();
+        let name = name.clone();
+        async move {
+            handle_errors(elem.computed_label().await.map(|x| !name.is_match(&x)), ignore_errors)
+        }
+    }
+}
+
 /// Predicate that returns true for elements that have the specified value.
 /// See the `Needle` documentation for more details on text matching rules.
 pub fn element_has_value<N>(value: N, ignore_errors: bool) -> impl ElementPredicate
@@ -3,8 +3,9 @@ use super::{conditions, ElementPollerNoWait, ElementPollerWithTimeout, IntoEleme
 use crate::error::WebDriverError;
 use crate::prelude::WebDriverResult;
 use crate::session::handle::SessionHandle;
+use crate::session::listener::{notify_exception, WebDriverEvent};
 use crate::IntoArcStr;
-use crate::{By, DynElementPredicate, ElementPredicate, WebElement};
+use crate::{By, DynElementPredicate, ElementPredicate, ShadowRoot, WebElement};
 use indexmap::IndexMap;
 use std::borrow::Cow;
 use std::fmt::{Debug, Display, Formatter, Write};
@@ -117,6 +118,8 @@ pub enum ElementQuerySource {
     Driver(Arc<SessionHandle>),
     /// Execute a query using the specified `WebElement` as the base.
     Element(WebElement),
+    /// Execute a query within the specified `ShadowRoot`.
+    ShadowRoot(ShadowRoot),
 }
 
 /// Options for wait characteristics for an element query.
@@ -222,17 +225,6 @@ pub struct ElementQuery {
     options: ElementQueryOptions,
 }
 
-macro_rules! disallow_empty {
-    ($elements: expr, $self: expr) => {
-        if $elements.is_empty() {
-            let desc: &str = $self.options.description.as_deref().unwrap_or("");
-            Err(no_such_element(&$self.selectors, desc))
-        } else {
-            Ok($elements)
-        }
-    };
-}
-
 impl ElementQuery {
     /// Create a new `ElementQuery`.
     ///
@@ -331,13 +323,13 @@ impl ElementQuery {
 
     /// Return true if an element matches any selector (including filters), otherwise false.
     pub async fn exists(&self) -> WebDriverResult<bool> {
-        let elements = self.run_poller(true, false).await?;
+        let elements = self.resolve(true, false, |_| true).await?;
         Ok(!elements.is_empty())
     }
 
     /// Return true if no element matches any selector (including filters), otherwise false.
     pub async fn not_exists(&self) -> WebDriverResult<bool> {
-        let elements = self.run_poller(false, true).await?;
+        let elements = self.resolve(false, true, |_| true).await?;
         Ok(elements.is_empty())
     }
 
@@ -345,7 +337,7 @@ impl ElementQuery {
     ///
     /// Returns None if no elements match.
     pub async fn first_opt(&self) -> WebDriverResult<Option<WebElement>> {
-        let elements = self.run_poller(true, false).await?;
+        let elements = self.resolve(true, false, |_| true).await?;
         Ok(elements.into_iter().next())
     }
 
@@ -353,15 +345,8 @@ impl ElementQuery {
     ///
     /// Returns Err(WebDriverError::NoSuchElement) if no elements match.
     pub async fn first(&self) -> WebDriverResult<WebElement> {
-        let mut elements = self.run_poller(true, false).await?;
-
-        if elements.is_empty() {
-            let desc: &str = self.options.description.as_deref().unwrap_or("");
-            let err = no_such_element(&self.selectors, desc);
-            Err(err)
-        } else {
-            Ok(elements.remove(0))
-        }
+        let mut elements = self.resolve(true, false, |x| !x.is_empty()).await?;
+        Ok(elements.remove(0))
     }
 
     /// Return only a single WebElement that matches any selector (including filters).
@@ -377,15 +362,8 @@ impl ElementQuery {
     /// By requiring that only one element is matched, you can be more sure that it is the
     /// one you intended.
     pub async fn single(&self) -> WebDriverResult<WebElement> {
-        let mut elements = self.run_poller(false, false).await?;
-
-        if elements.len() == 1 {
-            Ok(elements.remove(0))
-        } else {
-            let desc: &str = self.options.description.as_deref().unwrap_or("");
-            let err = no_such_element(&self.selectors, desc);
-            Err(err)
-        }
+        let mut elements = self.resolve(false, false, |x| x.len() == 1).await?;
+        Ok(elements.remove(0))
     }
 
     /// Return all WebElements that match any selector (including filters).
@@ -394,7 +372,7 @@ impl ElementQuery {
     ///
     /// Returns an empty Vec if no elements match.
     pub async fn any(&self) -> WebDriverResult<Vec<WebElement>> {
-        self.run_poller(false, false).await
+        self.resolve(false, false, |_| true).await
     }
 
     /// Return all WebElements that match any selector (including filters).
@@ -403,8 +381,7 @@ impl ElementQuery {
     ///
     /// Returns Err(WebDriverError::NoSuchElement) if no elements match.
     pub async fn any_required(&self) -> WebDriverResult<Vec<WebElement>> {
-        let elements = self.run_poller(false, false).await?;
-        disallow_empty!(elements, self)
+        self.resolve(false, false, |x| !x.is_empty()).await
     }
 
     /// Return all WebElements that match any single selector (including filters).
@@ -420,7 +397,7 @@ impl ElementQuery {
     ///
     /// Returns an empty Vec if no elements match.
     pub async fn all_from_selector(&self) -> WebDriverResult<Vec<WebElement>> {
-        self.run_poller(true, false).await
+        self.resolve(true, false, |_| true).await
     }
 
     /// Return all WebElements that match any single selector (including filters).
@@ -436,8 +413,61 @@ impl ElementQuery {
     ///
     /// Returns Err(WebDriverError::NoSuchElement) if no elements match.
     pub async fn all_from_selector_required(&self) -> WebDriverResult<Vec<WebElement>> {
-        let elements = self.run_poller(true, false).await?;
-        disallow_empty!(elements, self)
+        self.resolve(true, false, |x| !x.is_empty()).await
+    }
+
+    /// The description of this query, for listeners and for the elements it finds.
+    ///
+    /// This is the description set with `desc()`, or else a summary of the selectors.
+    fn summary(&self) -> Arc<str> {
+        match &self.options.description {
+            Some(description) if !description.is_empty() => description.clone(),
+            _ => get_selector_summary(&self.selectors).into(),
+        }
+    }
+
+    /// Run the poller, notifying any `WebDriverListener` before and after.
+    ///
+    /// Returns `Err(WebDriverError::NoSuchElement)` if `is_match` returns false for the
+    /// elements found. See `run_poller()` for the other parameters.
+    async fn resolve(
+        &self,
+        short_circuit: bool,
+        stop_on_miss: bool,
+        is_match: impl FnOnce(&[WebElement]) -> bool,
+    ) -> WebDriverResult<Vec<WebElement>> {
+        let summary = self.summary();
+        let listeners = &self.source_handle().config().listeners;
+        for listener in listeners {
+            listener.before_query(&summary).await;
+        }
+
+        let result = self.run_poller(short_circuit, stop_on_miss).await;
+        let result: WebDriverResult<Vec<_>> = result.and_then(|elements| {
+            if is_match(&elements) {
+                Ok(elements.into_iter().map(|x| x.with_description(summary.clone())).collect())
+            } else {
+                let desc: &str = self.options.description.as_deref().unwrap_or("");
+                Err(no_such_element(&self.selectors, desc))
+            }
+        });
+        match &result {
+            Ok(elements) => {
+                for listener in listeners {
+                    listener.after_query(&summary, elements).await;
+                }
+            }
+            Err(e) => notify_exception(listeners, WebDriverEvent::Query(&summary), e).await,
+        }
+        result
+    }
+
+    fn source_handle(&self) -> &SessionHandle {
+        match &self.source {
+            ElementQuerySource::Driver(handle) => handle,
+            ElementQuerySource::Element(element) => &element.handle,
+            ElementQuerySource::ShadowRoot(root) => &root.handle,
+        }
     }
 
     /// Run the poller for this ElementQuery and return the Vec of WebElements matched.
@@ -509,6 +539,7 @@ impl ElementQuery {
         match &self.source {
             ElementQuerySource::Driver(driver) => driver.find_all(by).await,
             ElementQuerySource::Element(element) => element.find_all(by).await,
+            ElementQuerySource::ShadowRoot(root) => root.find_all(by).await,
         }
     }
 
@@ -692,6 +723,46 @@ impl ElementQuery {
         })
     }
 
+    /// Only match elements that have the specified computed WAI-ARIA role.
+    /// See the `Needle` documentation for more details on text matching rules.
+    pub fn with_role<N>(self, role: N) -> Self
+    where
+        N: Needle + Clone + Send + Sync + 'static,
+    {
+        let ignore_errors = self.options.ignore_errors.unwrap_or_default();
+        self.with_filter(conditions::element_has_role(role, ignore_errors))
+    }
+
+    /// Only match elements that do not have the specified computed WAI-ARIA role.
+    /// See the `Needle` documentation for more details on text matching rules.
+    pub fn without_role<N>(self, role: N) -> Self
+    where
+        N: Needle + Clone + Send + Sync + 'static,
+    {
+        let ignore_errors = self.options.ignore_errors.unwrap_or_default();
+        self.with_filter(conditions::element_lacks_role(role, ignore_errors))
+    }
+
+    /// Only match elements that have the specified accessible name.
+    /// See the `Needle` documentation for more details on text matching rules.
+    pub fn with_accessible_name<N>(self, name: N) -> Self
+    where
+        N: Needle + Clone + Send + Sync + 'static,
+    {
+        let ignore_errors = self.options.ignore_errors.unwrap_or_default();
+        self.with_filter(conditions::element_has_accessible_name(name, ignore_errors))
+    }
+
+    /// Only match elements that do not have the specified accessible name.
+    /// See the `Needle` documentation for more details on text matching rules.
+    pub fn without_accessible_name<N>(self, name: N) -> Self
+    where
+        N: Needle + Clone + Send + Sync + 'static,
+    {
+        let ignore_errors = self.options.ignore_errors.unwrap_or_default();
+        self.with_filter(conditions::element_lacks_accessible_name(name, ignore_errors))
+    }
+
     /// Only match elements that have the specified value.
     /// See the `Needle` documentation for more details on text matching rules.
     pub fn with_value<N>(self, value: N) -> Self
@@ -923,6 +994,19 @@ impl ElementQueryable for WebElement {
     }
 }
 
+impl ElementQueryable for ShadowRoot {
+    /// Return an ElementQuery instance for querying elements within this shadow root.
+    ///
+    /// See [`ElementQuery`] for more documentation.
+    fn query(&self, by: By) -> ElementQuery {
+        ElementQuery::new(
+            ElementQuerySource::ShadowRoot(self.clone()),
+            by,
+            self.handle.config().poller.clone(),
+        )
+    }
+}
+
 impl ElementQueryable for Arc<SessionHandle> {
     /// Return an ElementQuery instance for more executing powerful element queries.
     ///
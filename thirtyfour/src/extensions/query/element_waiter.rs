@@ -220,6 +220,42 @@ impl ElementWaiter {
         self.condition(conditions::element_lacks_text(text, ignore_errors)).await
     }
 
+    /// Wait until the element has the specified computed WAI-ARIA role.
+    pub async fn has_role<N>(self, role: N) -> WebDriverResult<()>
+    where
+        N: Needle + Clone + Send + Sync + 'static,
+    {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_has_role(role, ignore_errors)).await
+    }
+
+    /// Wait until the element lacks the specified computed WAI-ARIA role.
+    pub async fn lacks_role<N>(self, role: N) -> WebDriverResult<()>
+    where
+        N: Needle + Clone + Send + Sync + 'static,
+    {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_lacks_role(role, ignore_errors)).await
+    }
+
+    /// Wait until the element has the specified accessible name.
+    pub async fn has_accessible_name<N>(self, name: N) -> WebDriverResult<()>
+    where
+        N: Needle + Clone + Send + Sync + 'static,
+    {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_has_accessible_name(name, ignore_errors)).await
+    }
+
+    /// Wait until the element lacks the specified accessible name.
+    pub async fn lacks_accessible_name<N>(self, name: N) -> WebDriverResult<()>
+    where
+        N: Needle + Clone + Send + Sync + 'static,
+    {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_lacks_accessible_name(name, ignore_errors)).await
+    }
+
     /// Wait until the element has the specified value.
     pub async fn has_value<N>(self, value: N) -> WebDriverResult<()>
     where
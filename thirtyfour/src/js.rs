@@ -47,3 +47,57 @@ function simulateDragDrop(sourceNode, destinationNode) {
 }
 
 simulateDragDrop(arguments[0], arguments[1]);"#;
+
+/// A javascript function that returns the rendered text of each element in `arguments`,
+/// or null for an element that is no longer in the document.
+pub const ELEMENT_TEXTS: &str = r#"
+return Array.prototype.map.call(arguments, function(el) {
+    return el.isConnected ? el.innerText : null;
+});"#;
+
+/// A javascript function that returns the text of each element in `arguments`, excluding
+/// the text of its descendants, or null for an element that is no longer in the document.
+pub const OWN_TEXTS: &str = r#"
+return Array.prototype.map.call(arguments, function(el) {
+    if (!el.isConnected) {
+        return null;
+    }
+    return Array.prototype.filter.call(el.childNodes, function(node) {
+        return node.nodeType === Node.TEXT_NODE;
+    }).map(function(node) {
+        return node.textContent;
+    }).join('');
+});"#;
+
+/// A javascript function that returns the accessible name of each form control in
+/// `arguments`, or null for an element that is no longer in the document.
+///
+/// This follows the name computation for the candidates of `By::Label`: `aria-labelledby`,
+/// then `aria-label`, then the associated `<label>` elements, then `title`.
+pub const ACCESSIBLE_NAMES: &str = r#"
+function textOf(el) {
+    return el ? el.textContent : '';
+}
+
+function accessibleName(el) {
+    var ids = (el.getAttribute('aria-labelledby') || '').trim();
+    if (ids) {
+        var root = el.getRootNode();
+        return ids.split(/\s+/).map(function(id) {
+            return textOf(root.getElementById ? root.getElementById(id) : document.getElementById(id));
+        }).join(' ');
+    }
+    var label = el.getAttribute('aria-label');
+    if (label && label.trim()) {
+        return label;
+    }
+    var labels = Array.prototype.map.call(el.labels || [], textOf).join(' ');
+    if (labels.trim()) {
+        return labels;
+    }
+    return el.getAttribute('title') || '';
+}
+
+return Array.prototype.map.call(arguments, function(el) {
+    return el.isConnected ? accessibleName(el) : null;
+});"#;
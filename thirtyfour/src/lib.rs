@@ -11,10 +11,15 @@
 //! ## Features
 //!
 //! - All W3C WebDriver and WebElement methods supported
-//! - Async / await support (tokio only)
+//! - Async / await support (tokio, async-std or smol)
+//! - Synchronous API for code that does not use async (with the `blocking` feature)
 //! - Create new browser session directly via WebDriver (e.g. chromedriver)
+//! - Launch and manage local driver processes (chromedriver, geckodriver, msedgedriver)
 //! - Create new browser session via Selenium Standalone or Grid
+//! - Session pool for running tests in parallel against warm browser sessions
+//! - Selenium Grid 4 status, node draining, session cleanup and managed downloads
 //! - Find elements (via all common selectors e.g. Id, Class, CSS, Tag, XPath)
+//! - Find elements the way users see them (by role, label, text, placeholder or test id)
 //! - Send keys to elements, including key-combinations
 //! - Execute Javascript
 //! - Action Chains
@@ -24,14 +29,25 @@
 //! - Alert support
 //! - Capture / Save screenshot of browser or individual element as PNG
 //! - Some Chrome DevTools Protocol (CDP) support
+//! - WebDriver BiDi event subscriptions (with the `bidi` feature)
 //! - Advanced query interface including explicit waits and various predicates
 //! - Component Wrappers (similar to `Page Object Model`)
+//! - In-memory mock WebDriver for testing components and queries without a browser (with the `testing` feature)
+//! - Per-command tracing spans and latency statistics
+//! - Event listeners for clicks, typing, navigation and element queries
+//! - Opt-in session recovery after a browser crash
 //!
 //! ## Feature Flags
 //!
 //! * `rustls-tls`: (Default) Use rustls to provide TLS support (via reqwest).
 //! * `native-tls`: Use native TLS (via reqwest).
 //! * `component`: (Default) Enable the `Component` derive macro (via thirtyfour-macros).
+//! * `bidi`: Enable WebDriver BiDi connections over WebSocket (via tokio-tungstenite).
+//! * `blocking`: Enable the synchronous API in the `blocking` module.
+//! * `testing`: Enable the in-memory mock WebDriver in the `testing` module.
+//! * `tokio-runtime`: (Default) Use tokio for timers and background tasks, and enable `DriverService`.
+//! * `async-std-runtime`: Use async-std for timers and background tasks outside of a tokio runtime.
+//! * `smol-runtime`: Use smol for timers and background tasks outside of a tokio runtime.
 //!
 //! ## Example
 //!
@@ -83,6 +99,12 @@
 //! while quiting. you can use the feature `debug_sync_quit` to get a backtrace printed if your webdriver ever
 //! quits synchronously
 //!
+//! [`WebDriver::scope`] runs a closure with a new session and always quits it asynchronously
+//! afterwards, even if the closure returns an error or panics. To change what happens when a
+//! session is dropped without quitting, set the [`DropPolicy`] in the `WebDriverConfig`.
+//!
+//! [`DropPolicy`]: common::config::DropPolicy
+//!
 //! ### Advanced element queries and explicit waits
 //!
 //! You can use [`WebDriver::query`] to perform more advanced queries
@@ -161,15 +183,20 @@ pub use common::{
         ie::InternetExplorerCapabilities,
         opera::OperaCapabilities,
         safari::SafariCapabilities,
+        session::SessionCapabilities,
     },
-    command::By,
+    command::{By, Relation, TextMatch},
     cookie::*,
     keys::*,
     requestdata::*,
     types::*,
 };
+pub use pool::{PooledWebDriver, WebDriverPool};
+#[cfg(feature = "tokio-runtime")]
+pub use service::DriverService;
+pub use shadow_root::ShadowRoot;
 pub use switch_to::SwitchTo;
-pub use web_driver::WebDriver;
+pub use web_driver::{WebDriver, WebDriverBuilder};
 pub use web_element::WebElement;
 
 /// Allow importing the common types via `use thirtyfour::prelude::*`.
@@ -179,11 +206,12 @@ pub mod prelude {
     pub use crate::extensions::query::{ElementPoller, ElementQueryable, ElementWaitable};
     pub use crate::session::scriptret::ScriptRet;
     pub use crate::switch_to::SwitchTo;
+    pub use crate::ShadowRoot;
     pub use crate::WebDriver;
     pub use crate::WebElement;
     pub use crate::{
         BrowserCapabilitiesHelper, By, Capabilities, CapabilitiesHelper, ChromiumLikeCapabilities,
-        DesiredCapabilities,
+        DesiredCapabilities, TextMatch,
     };
     pub use crate::{Cookie, Key, SameSite, TimeoutConfiguration, TypingData, WindowHandle};
 }
@@ -192,6 +220,8 @@ pub mod prelude {
 pub mod action_chain;
 /// Alert handling.
 pub mod alert;
+#[cfg(feature = "blocking")]
+pub mod blocking;
 /// Common wrappers used by both async and sync implementations.
 pub mod common;
 /// Components and component wrappers.
@@ -200,12 +230,24 @@ pub mod components;
 pub mod error;
 /// Extensions for specific browsers.
 pub mod extensions;
+/// A pool of reusable WebDriver sessions for running tests in parallel.
+pub mod pool;
+/// Executor-agnostic sleep, spawn and timeout helpers.
+pub mod runtime;
+/// Spawn and manage local WebDriver server processes.
+#[cfg(feature = "tokio-runtime")]
+pub mod service;
 /// Everything related to driving the underlying WebDriver session.
 pub mod session;
 /// Miscellaneous support functions for `thirtyfour` tests.
 pub mod support;
+/// An in-memory mock WebDriver server for testing without a browser.
+#[cfg(feature = "testing")]
+pub mod testing;
 
 mod js;
+mod locator;
+mod shadow_root;
 mod switch_to;
 mod web_driver;
 mod web_element;
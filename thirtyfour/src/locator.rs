@@ -0,0 +1,383 @@
+//! Selectors that are resolved by thirtyfour rather than by the WebDriver.
+//!
+//! The WebDriver is sent a selector that matches a superset of the elements, and the
+//! candidates it returns are then refined using further WebDriver commands, or a single
+//! injected script where one can check all the candidates at once.
+
+use std::future::Future;
+use std::sync::Arc;
+
+use futures_util::future::try_join_all;
+use futures_util::stream::{self, StreamExt, TryStreamExt};
+use indexmap::IndexMap;
+use serde_json::Value;
+
+use crate::common::command::{Command, Relation, TextMatch};
+use crate::common::selector::css_string;
+use crate::common::types::ElementRect;
+use crate::components::escape_string;
+use crate::error::{no_such_element, WebDriverError, WebDriverResult};
+use crate::js::{ACCESSIBLE_NAMES, ELEMENT_TEXTS, OWN_TEXTS};
+use crate::session::handle::SessionHandle;
+use crate::session::scriptret::ScriptRet;
+use crate::{By, WebElement};
+
+/// The candidate elements for a form control label.
+pub(crate) const LABEL_CANDIDATES: &str =
+    "input, select, textarea, [aria-label], [aria-labelledby]";
+
+/// Tags with an implicit WAI-ARIA role, and the roles they may have.
+const IMPLICIT_ROLES: &[(&str, &[&str])] = &[
+    ("a[href], area[href]", &["link"]),
+    ("article", &["article"]),
+    ("aside", &["complementary"]),
+    ("button", &["button"]),
+    ("datalist", &["listbox"]),
+    ("details", &["group"]),
+    ("dialog", &["dialog"]),
+    ("fieldset", &["group"]),
+    ("footer", &["contentinfo"]),
+    ("form", &["form"]),
+    ("h1, h2, h3, h4, h5, h6", &["heading"]),
+    ("header", &["banner"]),
+    ("hr", &["separator"]),
+    ("img", &["img", "presentation", "none"]),
+    (
+        "input",
+        &[
+            "button",
+            "checkbox",
+            "radio",
+            "searchbox",
+            "slider",
+            "spinbutton",
+            "textbox",
+            "combobox",
+        ],
+    ),
+    ("li", &["listitem"]),
+    ("main", &["main"]),
+    ("menu, ol, ul", &["list"]),
+    ("nav", &["navigation"]),
+    ("optgroup", &["group"]),
+    ("option", &["option"]),
+    ("p", &["paragraph"]),
+    ("progress", &["progressbar"]),
+    ("section", &["region"]),
+    ("select", &["combobox", "listbox"]),
+    ("summary", &["button"]),
+    ("table", &["table"]),
+    ("td", &["cell", "gridcell"]),
+    ("textarea", &["textbox"]),
+    ("th", &["columnheader", "rowheader"]),
+    ("tr", &["row"]),
+];
+
+/// The CSS selector for the candidate elements for the specified role.
+pub(crate) fn role_candidates(role: &str) -> String {
+    let mut css = format!("[role~={}]", css_string(role));
+    for (tags, roles) in IMPLICIT_ROLES {
+        if roles.contains(&role) {
+            css.push_str(", ");
+            css.push_str(tags);
+        }
+    }
+    css
+}
+
+/// The XPath for the candidate elements for the specified text.
+///
+/// These are the elements that contain a text node of their own.
+pub(crate) fn text_candidates(text: &TextMatch) -> String {
+    let mut xpath = ".//*[text()[normalize-space()]]".to_string();
+    if let Some(exact) = text.exact() {
+        xpath.push_str(&format!("[contains(normalize-space(.), {})]", escape_string(exact)));
+    }
+    xpath
+}
+
+/// Split a deep CSS selector at each `>>>` that is outside brackets and quotes.
+/// See [`By::DeepCss()`].
+pub(crate) fn deep_css_parts(css: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0usize;
+    let mut quote = None;
+    let mut start = 0;
+    let mut chars = css.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match (quote, c) {
+            (_, '\\') => {
+                chars.next();
+            }
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '(' | '[') => depth += 1,
+            (None, ')' | ']') => depth = depth.saturating_sub(1),
+            (None, '>') if depth == 0 && css[i..].starts_with(">>>") => {
+                parts.push(css[start..i].trim());
+                chars.nth(1);
+                start = i + 3;
+            }
+            _ => {}
+        }
+    }
+    parts.push(css[start..].trim());
+    parts
+}
+
+/// The step that thirtyfour applies to the candidate elements returned by the WebDriver.
+#[derive(Debug)]
+pub(crate) enum Refine {
+    /// The elements are returned as is.
+    None,
+    /// Match each CSS selector within the shadow roots of the elements matched so far.
+    Deep(Vec<Arc<str>>),
+    /// Match the computed role and accessible name.
+    Role(Arc<str>, TextMatch),
+    /// Match the accessible name.
+    Label(TextMatch),
+    /// Match the rendered text.
+    Text(TextMatch),
+    /// Apply the inner step, then match the position relative to other elements.
+    Relative(Box<Refine>, Vec<Relation>),
+}
+
+/// The maximum number of candidate elements that are checked at once with their own requests.
+const MAX_CONCURRENT_CHECKS: usize = 8;
+
+impl Refine {
+    /// Refine the candidate elements returned by the WebDriver.
+    pub(crate) async fn apply(
+        &self,
+        elements: Vec<WebElement>,
+    ) -> WebDriverResult<Vec<WebElement>> {
+        match self {
+            Refine::None => Ok(elements),
+            Refine::Deep(parts) => find_all_deep(elements, parts).await,
+            Refine::Relative(refine, relations) => {
+                let elements = Box::pin(refine.apply(elements)).await?;
+                find_all_relative(elements, relations).await
+            }
+            Refine::Label(text) => filter_by_script(elements, ACCESSIBLE_NAMES, text).await,
+            // An exact match is for the whole rendered text, while other matches only look
+            // at the element's own text, so that they don't also match its ancestors.
+            Refine::Text(text) => {
+                let script = if text.exact().is_some() {
+                    ELEMENT_TEXTS
+                } else {
+                    OWN_TEXTS
+                };
+                filter_by_script(elements, script, text).await
+            }
+            Refine::Role(role, name) => filter(elements, |x| role_matches(x, role, name)).await,
+        }
+    }
+}
+
+/// Keep the elements for which `check` returns true, checking a few elements at a time.
+async fn filter<F, Fut>(elements: Vec<WebElement>, check: F) -> WebDriverResult<Vec<WebElement>>
+where
+    F: Fn(WebElement) -> Fut,
+    Fut: Future<Output = WebDriverResult<bool>>,
+{
+    let matches: Vec<bool> = stream::iter(elements.iter().cloned())
+        .map(|x| {
+            let check = check(x);
+            async {
+                match check.await {
+                    // The element was removed after the WebDriver returned it.
+                    Err(WebDriverError::StaleElementReference(_)) => Ok(false),
+                    result => result,
+                }
+            }
+        })
+        .buffered(MAX_CONCURRENT_CHECKS)
+        .try_collect()
+        .await?;
+    Ok(elements.into_iter().zip(matches).filter_map(|(x, m)| m.then_some(x)).collect())
+}
+
+/// Keep the elements whose text, as returned by `script`, matches. The text of all the
+/// elements is fetched with a single request.
+async fn filter_by_script(
+    elements: Vec<WebElement>,
+    script: &str,
+    text: &TextMatch,
+) -> WebDriverResult<Vec<WebElement>> {
+    let texts = match script_texts(script, &elements).await {
+        Ok(x) => x,
+        // An element was removed after the WebDriver returned it, so check each on its own.
+        Err(WebDriverError::StaleElementReference(_)) => {
+            return filter(elements, |x| async move {
+                let texts = script_texts(script, &[x]).await?;
+                Ok(texts.into_iter().flatten().any(|x| text.is_match(&x)))
+            })
+            .await;
+        }
+        Err(e) => return Err(e),
+    };
+    let matches = texts.into_iter().map(|x| x.is_some_and(|x| text.is_match(&x)));
+    Ok(elements.into_iter().zip(matches).filter_map(|(x, m)| m.then_some(x)).collect())
+}
+
+/// Run a script that returns the text of each of the specified elements.
+async fn script_texts(
+    script: &str,
+    elements: &[WebElement],
+) -> WebDriverResult<Vec<Option<String>>> {
+    let Some(handle) = elements.first().map(|x| &x.handle) else {
+        return Ok(Vec::new());
+    };
+    let args = elements.iter().map(WebElement::to_json).collect::<WebDriverResult<_>>()?;
+    Ok(serde_json::from_value(execute(handle, script, args).await?)?)
+}
+
+/// Return true if the element has the specified computed role and accessible name.
+async fn role_matches(element: WebElement, role: &str, name: &TextMatch) -> WebDriverResult<bool> {
+    if element.computed_role().await? != role {
+        return Ok(false);
+    }
+    if name.is_any() {
+        return Ok(true);
+    }
+    element.computed_label().await.map(|x| name.is_match(&x))
+}
+
+/// Match the remaining parts of a deep CSS selector, starting from the shadow roots of the
+/// specified elements. See [`By::DeepCss()`].
+async fn find_all_deep(
+    mut elements: Vec<WebElement>,
+    parts: &[Arc<str>],
+) -> WebDriverResult<Vec<WebElement>> {
+    for part in parts {
+        let mut found = IndexMap::new();
+        for host in elements {
+            let root = match host.get_shadow_root().await {
+                Ok(root) => root,
+                Err(WebDriverError::NoSuchShadowRoot(_)) => continue,
+                Err(e) => return Err(e),
+            };
+            for element in root.find_all_shallow(By::Css(part.clone())).await? {
+                found.insert(element.element_id(), element);
+            }
+        }
+        elements = found.into_values().collect();
+    }
+    Ok(elements)
+}
+
+/// Match the positions of the specified elements relative to the elements in `relations`,
+/// and sort them by their distance from the first of those elements.
+async fn find_all_relative(
+    elements: Vec<WebElement>,
+    relations: &[Relation],
+) -> WebDriverResult<Vec<WebElement>> {
+    let anchors = try_join_all(relations.iter().map(|x| x.element().rect())).await?;
+    let rects = try_join_all(elements.iter().map(|x| async move {
+        match x.rect().await {
+            Ok(rect) => Ok(Some(rect)),
+            // The element was removed after the WebDriver returned it.
+            Err(WebDriverError::StaleElementReference(_)) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }))
+    .await?;
+
+    let mut found = Vec::new();
+    for (element, rect) in elements.into_iter().zip(rects) {
+        let Some(rect) = rect else {
+            continue;
+        };
+        let is_match = relations.iter().zip(&anchors).all(|(relation, anchor)| {
+            *relation.element() != element && is_related(relation, &rect, anchor)
+        });
+        if is_match {
+            let distance = anchors.first().map(|x| center_distance(&rect, x)).unwrap_or_default();
+            found.push((distance, element));
+        }
+    }
+    found.sort_by(|a, b| a.0.total_cmp(&b.0));
+    Ok(found.into_iter().map(|(_, x)| x).collect())
+}
+
+/// Return true if `rect` is positioned as described by `relation`, relative to `anchor`.
+fn is_related(relation: &Relation, rect: &ElementRect, anchor: &ElementRect) -> bool {
+    match relation {
+        Relation::Above(_) => rect.y + rect.height <= anchor.y,
+        Relation::Below(_) => rect.y >= anchor.y + anchor.height,
+        Relation::LeftOf(_) => rect.x + rect.width <= anchor.x,
+        Relation::RightOf(_) => rect.x >= anchor.x + anchor.width,
+        Relation::Near(_, px) => edge_distance(rect, anchor) <= f64::from(*px),
+    }
+}
+
+/// The distance between the nearest edges of two rects, or 0 if they overlap.
+fn edge_distance(a: &ElementRect, b: &ElementRect) -> f64 {
+    let dx = (b.x - (a.x + a.width)).max(a.x - (b.x + b.width)).max(0.0);
+    let dy = (b.y - (a.y + a.height)).max(a.y - (b.y + b.height)).max(0.0);
+    dx.hypot(dy)
+}
+
+/// The distance between the centers of two rects.
+fn center_distance(a: &ElementRect, b: &ElementRect) -> f64 {
+    let (ax, ay) = a.center();
+    let (bx, by) = b.center();
+    (ax - bx).hypot(ay - by)
+}
+
+/// Run a script that is part of resolving a selector.
+///
+/// The script is sent as a plain command rather than via [`SessionHandle::execute()`], so
+/// listeners are not notified each time a query polls.
+async fn execute(handle: &SessionHandle, script: &str, args: Vec<Value>) -> WebDriverResult<Value> {
+    handle.cmd(Command::ExecuteScript(Arc::from(script), args.into())).await?.value_json()
+}
+
+/// Run the script for a [`By::Js()`] selector with the specified element or shadow root as
+/// `this`, or the document if `context` is null.
+pub(crate) async fn find_all_js(
+    handle: &Arc<SessionHandle>,
+    script: &str,
+    args: &[Value],
+    context: Value,
+) -> WebDriverResult<Vec<WebElement>> {
+    let script = format!(
+        "var context = arguments[arguments.length - 1] || document;\n\
+         var args = Array.prototype.slice.call(arguments, 0, -1);\n\
+         return (function() {{\n{script}\n}}).apply(context, args);"
+    );
+    let args: Vec<Value> = args.iter().cloned().chain([context]).collect();
+    let ret = ScriptRet::new(handle.clone(), execute(handle, &script, args).await?);
+    match ret.json() {
+        Value::Null => Ok(Vec::new()),
+        Value::Array(_) => ret.elements(),
+        _ => Ok(vec![ret.element()?]),
+    }
+}
+
+/// The first element matched by a selector that is refined by thirtyfour.
+pub(crate) fn first(elements: Vec<WebElement>, by: &By) -> WebDriverResult<WebElement> {
+    elements
+        .into_iter()
+        .next()
+        .ok_or_else(|| no_such_element(format!("Unable to locate element: {by}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deep_css() {
+        assert_eq!(
+            deep_css_parts("app-shell >>> nav-menu>>>button"),
+            ["app-shell", "nav-menu", "button"]
+        );
+        assert_eq!(deep_css_parts("button.save"), ["button.save"]);
+        assert_eq!(
+            deep_css_parts(r#"a[title='x >>> y'] >>> b[title="\" >>> "] >>> :is(c >>> d)"#),
+            ["a[title='x >>> y']", r#"b[title="\" >>> "]"#, ":is(c >>> d)"]
+        );
+    }
+}
@@ -0,0 +1,314 @@
+use std::fmt::{Debug, Formatter};
+use std::future::Future;
+use std::ops::Deref;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use futures_util::future::{try_join_all, BoxFuture};
+use futures_util::FutureExt;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use crate::error::{WebDriverError, WebDriverResult};
+use crate::{Capabilities, WebDriver, WindowHandle};
+
+/// Clears local and session storage for the current origin.
+///
+/// Pages such as `about:blank` throw when storage is accessed, so errors are ignored.
+const CLEAR_STORAGE_SCRIPT: &str = r#"
+try { window.localStorage.clear(); } catch (e) {}
+try { window.sessionStorage.clear(); } catch (e) {}
+"#;
+
+type SessionFactory = dyn Fn() -> BoxFuture<'static, WebDriverResult<WebDriver>> + Send + Sync;
+
+/// A pool of warm `WebDriver` sessions that can be leased by parallel tests.
+///
+/// The pool holds at most `size` sessions. [`WebDriverPool::get()`] waits until a session
+/// is available, and the returned [`PooledWebDriver`] goes back to the pool when it is
+/// dropped. Before a session is leased again its state is reset: cookies and storage are
+/// cleared, extra windows are closed and the remaining window navigates to `about:blank`.
+///
+/// Sessions that fail the health check when leased, or that cannot be reset, are
+/// discarded and replaced with new sessions.
+///
+/// # Example:
+/// ```no_run
+/// # use thirtyfour::prelude::*;
+/// # use thirtyfour::support::block_on;
+/// use thirtyfour::WebDriverPool;
+///
+/// # fn main() -> WebDriverResult<()> {
+/// #     block_on(async {
+/// let caps = DesiredCapabilities::chrome();
+/// let pool = WebDriverPool::new("http://localhost:4444", caps, 4).await?;
+///
+/// let driver = pool.get().await?;
+/// driver.goto("https://www.rust-lang.org/").await?;
+/// // Return the session to the pool.
+/// drop(driver);
+///
+/// // Quit all sessions once the tests are done.
+/// pool.close().await?;
+/// #         Ok(())
+/// #     })
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct WebDriverPool {
+    inner: Arc<PoolInner>,
+}
+
+struct PoolInner {
+    size: usize,
+    factory: Box<SessionFactory>,
+    idle: Mutex<Vec<PooledSession>>,
+    permits: Arc<Semaphore>,
+    closed: AtomicBool,
+}
+
+/// A session owned by the pool, along with the window it was created with.
+struct PooledSession {
+    driver: WebDriver,
+    window: WindowHandle,
+}
+
+impl Debug for WebDriverPool {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WebDriverPool")
+            .field("size", &self.inner.size)
+            .field("idle", &self.idle())
+            .finish()
+    }
+}
+
+impl WebDriverPool {
+    /// Create a pool of `size` sessions with the specified capabilities.
+    ///
+    /// All sessions are started before this returns.
+    pub async fn new<S, C>(server_url: S, capabilities: C, size: usize) -> WebDriverResult<Self>
+    where
+        S: Into<String>,
+        C: Into<Capabilities>,
+    {
+        let server_url: String = server_url.into();
+        let capabilities: Capabilities = capabilities.into();
+        Self::with_factory(size, move || WebDriver::new(server_url.clone(), capabilities.clone()))
+            .await
+    }
+
+    /// Create a pool of `size` sessions, using the specified function to start each session.
+    ///
+    /// Use this to start sessions with a custom `WebDriverConfig` or HTTP client.
+    /// All sessions are started before this returns.
+    pub async fn with_factory<F, Fut>(size: usize, factory: F) -> WebDriverResult<Self>
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = WebDriverResult<WebDriver>> + Send + 'static,
+    {
+        let inner = Arc::new(PoolInner {
+            size,
+            factory: Box::new(move || factory().boxed()),
+            idle: Mutex::new(Vec::with_capacity(size)),
+            permits: Arc::new(Semaphore::new(size)),
+            closed: AtomicBool::new(false),
+        });
+        let sessions = try_join_all((0..size).map(|_| inner.create())).await?;
+        inner.idle.lock().unwrap().extend(sessions);
+        Ok(Self {
+            inner,
+        })
+    }
+
+    /// The maximum number of sessions in the pool.
+    pub fn size(&self) -> usize {
+        self.inner.size
+    }
+
+    /// The number of sessions that are currently waiting to be leased.
+    pub fn idle(&self) -> usize {
+        self.inner.idle.lock().unwrap().len()
+    }
+
+    /// Lease a session from the pool, waiting until one is available.
+    ///
+    /// The session is returned to the pool when the `PooledWebDriver` is dropped.
+    pub async fn get(&self) -> WebDriverResult<PooledWebDriver> {
+        let permit = Arc::clone(&self.inner.permits).acquire_owned().await.map_err(|_| {
+            WebDriverError::SessionCreateError("the WebDriver pool has been closed".to_string())
+        })?;
+
+        loop {
+            let session = self.inner.idle.lock().unwrap().pop();
+            let Some(session) = session else {
+                break;
+            };
+            match session.driver.window().await {
+                Ok(_) => return Ok(PooledWebDriver::new(session, permit, &self.inner)),
+                Err(e) => discard(session, e).await,
+            }
+        }
+
+        let session = self.inner.create().await?;
+        Ok(PooledWebDriver::new(session, permit, &self.inner))
+    }
+
+    /// Quit all idle sessions and stop leasing new ones.
+    ///
+    /// Sessions that are currently leased are quit when they are returned.
+    /// Every idle session is quit even if some fail, and the first error is returned.
+    pub async fn close(&self) -> WebDriverResult<()> {
+        self.inner.closed.store(true, Ordering::SeqCst);
+        self.inner.permits.close();
+        let sessions = std::mem::take(&mut *self.inner.idle.lock().unwrap());
+        let mut result = Ok(());
+        for session in sessions {
+            result = result.and(session.driver.quit().await);
+        }
+        result
+    }
+}
+
+impl PoolInner {
+    async fn create(&self) -> WebDriverResult<PooledSession> {
+        let driver = (self.factory)().await?;
+        let window = driver.window().await?;
+        Ok(PooledSession {
+            driver,
+            window,
+        })
+    }
+
+    /// Reset a returned session and put it back in the pool.
+    async fn release(&self, mut session: PooledSession) {
+        if self.closed.load(Ordering::SeqCst) {
+            let _ = session.driver.quit().await;
+            return;
+        }
+
+        match reset(&session.driver, &session.window).await {
+            Ok(window) => {
+                session.window = window;
+                // The pool may have been closed while the session was being reset.
+                let mut idle = self.idle.lock().unwrap();
+                if !self.closed.load(Ordering::SeqCst) {
+                    idle.push(session);
+                    return;
+                }
+                drop(idle);
+                let _ = session.driver.quit().await;
+            }
+            Err(e) => discard(session, e).await,
+        }
+    }
+}
+
+/// Close all windows except one, clearing cookies and storage in each of them,
+/// and navigate the remaining window to `about:blank`.
+///
+/// Returns the window that was kept open.
+async fn reset(driver: &WebDriver, window: &WindowHandle) -> WebDriverResult<WindowHandle> {
+    let handles = driver.windows().await?;
+    let keep = match handles.iter().find(|x| *x == window).or(handles.first()) {
+        Some(handle) => handle.clone(),
+        None => driver.new_window().await?,
+    };
+
+    for handle in handles {
+        driver.switch_to_window(handle.clone()).await?;
+        driver.execute(CLEAR_STORAGE_SCRIPT, Vec::new()).await?;
+        driver.delete_all_cookies().await?;
+        if handle != keep {
+            driver.close_window().await?;
+        }
+    }
+
+    driver.switch_to_window(keep.clone()).await?;
+    driver.goto("about:blank").await?;
+    Ok(keep)
+}
+
+/// Remove a session that is no longer usable.
+async fn discard(session: PooledSession, error: WebDriverError) {
+    tracing::debug!(
+        "discarding pooled WebDriver session {}: {error}",
+        session.driver.current_session_id()
+    );
+    match error {
+        // The session is already gone, so there is nothing to quit.
+        WebDriverError::InvalidSessionId(_) => {
+            let _ = session.driver.leak();
+        }
+        _ => {
+            let _ = session.driver.quit().await;
+        }
+    }
+}
+
+/// A `WebDriver` session leased from a [`WebDriverPool`].
+///
+/// This dereferences to [`WebDriver`]. When dropped, the session is reset in the
+/// background and returned to the pool, rather than being quit.
+///
+/// Do not clone the inner `WebDriver` (e.g. with `(*lease).clone()`) to keep it beyond
+/// the lease. Once the lease is dropped the session is handed to the next caller of
+/// [`WebDriverPool::get()`], and a clone would keep driving the same browser.
+pub struct PooledWebDriver {
+    lease: Option<Lease>,
+    pool: Arc<PoolInner>,
+}
+
+struct Lease {
+    session: PooledSession,
+    permit: OwnedSemaphorePermit,
+}
+
+impl PooledWebDriver {
+    fn new(session: PooledSession, permit: OwnedSemaphorePermit, pool: &Arc<PoolInner>) -> Self {
+        Self {
+            lease: Some(Lease {
+                session,
+                permit,
+            }),
+            pool: Arc::clone(pool),
+        }
+    }
+
+    /// Remove the session from the pool and quit it.
+    ///
+    /// Use this if the session is in a state that should not be reused.
+    /// The pool will start a new session in its place when required.
+    pub async fn quit(mut self) -> WebDriverResult<()> {
+        match self.lease.take() {
+            Some(lease) => lease.session.driver.quit().await,
+            None => Ok(()),
+        }
+    }
+}
+
+impl Debug for PooledWebDriver {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PooledWebDriver").field("driver", &**self).finish()
+    }
+}
+
+impl Deref for PooledWebDriver {
+    type Target = WebDriver;
+
+    fn deref(&self) -> &Self::Target {
+        &self.lease.as_ref().expect("lease is only taken when consumed").session.driver
+    }
+}
+
+impl Drop for PooledWebDriver {
+    fn drop(&mut self) {
+        let Some(lease) = self.lease.take() else {
+            return;
+        };
+        let pool = Arc::clone(&self.pool);
+        crate::runtime::spawn(async move {
+            pool.release(lease.session).await;
+            // The next lease must wait until the reset has finished.
+            drop(lease.permit);
+        });
+    }
+}
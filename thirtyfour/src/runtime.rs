@@ -0,0 +1,384 @@
+//! The async runtime used for timers and background tasks.
+//!
+//! `thirtyfour` does not require a particular executor. The runtime used for sleeping,
+//! spawning and blocking work is selected with cargo features:
+//!
+//! * `tokio-runtime` (default): tokio is used whenever the calling code runs inside a tokio
+//!   runtime.
+//! * `async-std-runtime`: async-std is used outside of a tokio runtime.
+//! * `smol-runtime`: smol is used outside of a tokio runtime.
+//!
+//! When none of these apply, timers are driven by a single background thread and tasks
+//! are run on their own threads, so that element pollers and waits work under any
+//! executor. The `HttpClient` must be compatible with the executor in use, e.g. the
+//! default `reqwest` client requires tokio.
+
+use std::future::Future;
+use std::io;
+use std::pin::pin;
+use std::time::Duration;
+
+use futures_util::future::{select, Either};
+
+use crate::error::{WebDriverError, WebDriverResult};
+
+/// Sleep for the specified duration.
+pub async fn sleep(duration: Duration) {
+    #[cfg(feature = "tokio-runtime")]
+    if tokio::runtime::Handle::try_current().is_ok() {
+        return tokio::time::sleep(duration).await;
+    }
+
+    cfg_if::cfg_if! {
+        if #[cfg(feature = "async-std-runtime")] {
+            async_std::task::sleep(duration).await
+        } else if #[cfg(feature = "smol-runtime")] {
+            smol::Timer::after(duration).await;
+        } else {
+            timer::Sleep::new(duration).await
+        }
+    }
+}
+
+/// Spawn the specified future in the background, without waiting for it to complete.
+pub fn spawn<F>(future: F)
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    cfg_if::cfg_if! {
+        if #[cfg(feature = "tokio-runtime")] {
+            match tokio::runtime::Handle::try_current() {
+                Ok(handle) => handle.spawn(future),
+                // Outside of a tokio runtime, use the runtime that drives `block_on()`.
+                Err(_) => global::handle().spawn(future),
+            };
+        } else if #[cfg(feature = "async-std-runtime")] {
+            async_std::task::spawn(future);
+        } else if #[cfg(feature = "smol-runtime")] {
+            smol::spawn(future).detach();
+        } else {
+            std::thread::spawn(move || executor::block_on(future));
+        }
+    }
+}
+
+/// Wait for the specified future to complete, up to the specified duration.
+///
+/// Returns [`WebDriverError::Timeout`] if the future did not complete in time.
+pub async fn timeout<F>(duration: Duration, future: F) -> WebDriverResult<F::Output>
+where
+    F: Future,
+{
+    match select(pin!(future), pin!(sleep(duration))).await {
+        Either::Left((output, _)) => Ok(output),
+        Either::Right(_) => {
+            Err(WebDriverError::Timeout(format!("operation timed out after {duration:?}")))
+        }
+    }
+}
+
+/// Run the specified blocking function without blocking the async executor.
+pub(crate) async fn spawn_blocking<F, T>(f: F) -> io::Result<T>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    #[cfg(feature = "tokio-runtime")]
+    if let Ok(handle) = tokio::runtime::Handle::try_current() {
+        return Ok(handle.spawn_blocking(f).await?);
+    }
+
+    cfg_if::cfg_if! {
+        if #[cfg(feature = "async-std-runtime")] {
+            Ok(async_std::task::spawn_blocking(f).await)
+        } else if #[cfg(feature = "smol-runtime")] {
+            Ok(smol::unblock(f).await)
+        } else {
+            let (tx, rx) = tokio::sync::oneshot::channel();
+            std::thread::spawn(move || {
+                let _ = tx.send(f());
+            });
+            rx.await.map_err(|_| io::Error::other("blocking task panicked"))
+        }
+    }
+}
+
+/// Run the specified future and block the current thread waiting for the result.
+pub(crate) fn block_on<F>(future: F) -> F::Output
+where
+    F: Future + Send,
+    F::Output: Send,
+{
+    cfg_if::cfg_if! {
+        if #[cfg(feature = "tokio-runtime")] {
+            global::block_on(future)
+        } else if #[cfg(feature = "async-std-runtime")] {
+            async_std::task::block_on(future)
+        } else if #[cfg(feature = "smol-runtime")] {
+            smol::block_on(future)
+        } else {
+            executor::block_on(future)
+        }
+    }
+}
+
+#[cfg(feature = "tokio-runtime")]
+mod global {
+    use std::convert::Infallible;
+    use std::future::Future;
+    use std::panic::AssertUnwindSafe;
+    use std::sync::LazyLock;
+    use std::thread;
+
+    fn no_unwind<T>(f: impl FnOnce() -> T) -> T {
+        let res = std::panic::catch_unwind(AssertUnwindSafe(f));
+
+        res.unwrap_or_else(|_| {
+            struct Abort;
+            impl Drop for Abort {
+                fn drop(&mut self) {
+                    eprintln!("unrecoverable error reached aborting...");
+                    std::process::abort()
+                }
+            }
+
+            let _abort_on_unwind = Abort;
+            unreachable!("thirtyfour global runtime panicked")
+        })
+    }
+
+    static GLOBAL_RT: LazyLock<tokio::runtime::Handle> = LazyLock::new(|| {
+        no_unwind(|| {
+            let rt = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+            let handle = rt.handle().clone();
+
+            // drive the runtime
+            // we do this so that all calls to GLOBAL_RT.block_on() work
+            thread::spawn(move || -> ! {
+                async fn forever() -> ! {
+                    match std::future::pending::<Infallible>().await {}
+                }
+
+                no_unwind(move || rt.block_on(forever()))
+            });
+            handle
+        })
+    });
+
+    /// The handle of the global runtime, which is driven by its own thread.
+    pub(super) fn handle() -> &'static tokio::runtime::Handle {
+        &GLOBAL_RT
+    }
+
+    /// Block on the specified future. This works even while in a tokio runtime.
+    pub(super) fn block_on<F>(future: F) -> F::Output
+    where
+        F: Future + Send,
+        F::Output: Send,
+    {
+        macro_rules! block_global {
+            ($future:expr) => {
+                thread::scope(|scope| match scope.spawn(|| GLOBAL_RT.block_on($future)).join() {
+                    Ok(res) => res,
+                    Err(panic) => std::panic::resume_unwind(panic),
+                })
+            };
+        }
+
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "tokio-multi-threaded")] {
+                use tokio::runtime::RuntimeFlavor;
+
+                match tokio::runtime::Handle::try_current() {
+                    Ok(handle) if handle.runtime_flavor() == RuntimeFlavor::MultiThread => {
+                        tokio::task::block_in_place(|| handle.block_on(future))
+                    }
+                    _ => block_global!(future),
+                }
+            } else {
+                block_global!(future)
+            }
+        }
+    }
+}
+
+#[cfg(not(any(
+    feature = "tokio-runtime",
+    feature = "async-std-runtime",
+    feature = "smol-runtime"
+)))]
+mod executor {
+    use std::future::Future;
+    use std::pin::pin;
+    use std::sync::Arc;
+    use std::task::{Context, Poll, Wake, Waker};
+    use std::thread::{self, Thread};
+
+    struct ThreadWaker(Thread);
+
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    /// Poll the specified future on the current thread, parking it while the future is pending.
+    pub(super) fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = pin!(future);
+        let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+        let mut cx = Context::from_waker(&waker);
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+                return output;
+            }
+            thread::park();
+        }
+    }
+}
+
+#[cfg(not(any(feature = "async-std-runtime", feature = "smol-runtime")))]
+mod timer {
+    use std::cmp::Reverse;
+    use std::collections::BinaryHeap;
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::sync::{Arc, Condvar, LazyLock, Mutex};
+    use std::task::{Context, Poll, Waker};
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    /// The waker for a registered timer, which is updated each time the timer is polled.
+    type SharedWaker = Arc<Mutex<Waker>>;
+
+    struct Entry {
+        deadline: Instant,
+        waker: SharedWaker,
+    }
+
+    impl PartialEq for Entry {
+        fn eq(&self, other: &Self) -> bool {
+            self.deadline == other.deadline
+        }
+    }
+
+    impl Eq for Entry {}
+
+    impl PartialOrd for Entry {
+        fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl Ord for Entry {
+        fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+            self.deadline.cmp(&other.deadline)
+        }
+    }
+
+    /// The pending timers, which are woken by a single background thread.
+    #[derive(Default)]
+    struct Timers {
+        queue: Mutex<BinaryHeap<Reverse<Entry>>>,
+        changed: Condvar,
+    }
+
+    static TIMERS: LazyLock<&'static Timers> = LazyLock::new(|| {
+        let timers: &'static Timers = Box::leak(Box::default());
+        thread::Builder::new()
+            .name("thirtyfour-timer".to_string())
+            .spawn(move || timers.run())
+            .expect("failed to spawn timer thread");
+        timers
+    });
+
+    impl Timers {
+        fn register(&self, deadline: Instant, waker: SharedWaker) {
+            let mut queue = self.queue.lock().unwrap_or_else(|e| e.into_inner());
+            queue.push(Reverse(Entry {
+                deadline,
+                waker,
+            }));
+            self.changed.notify_one();
+        }
+
+        fn run(&self) -> ! {
+            let mut queue = self.queue.lock().unwrap_or_else(|e| e.into_inner());
+            loop {
+                let now = Instant::now();
+                queue = match queue.peek() {
+                    Some(Reverse(entry)) if entry.deadline <= now => {
+                        if let Some(Reverse(entry)) = queue.pop() {
+                            entry.waker.lock().unwrap_or_else(|e| e.into_inner()).wake_by_ref();
+                        }
+                        queue
+                    }
+                    Some(Reverse(entry)) => {
+                        let wait = entry.deadline - now;
+                        self.changed.wait_timeout(queue, wait).unwrap_or_else(|e| e.into_inner()).0
+                    }
+                    None => self.changed.wait(queue).unwrap_or_else(|e| e.into_inner()),
+                };
+            }
+        }
+    }
+
+    /// A future that completes once the specified duration has elapsed.
+    pub(super) struct Sleep {
+        deadline: Instant,
+        /// The waker shared with the timer thread, once this has been registered.
+        waker: Option<SharedWaker>,
+    }
+
+    impl Sleep {
+        pub(super) fn new(duration: Duration) -> Self {
+            Self {
+                deadline: Instant::now() + duration,
+                waker: None,
+            }
+        }
+    }
+
+    impl Future for Sleep {
+        type Output = ();
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+            if Instant::now() >= self.deadline {
+                return Poll::Ready(());
+            }
+            match &self.waker {
+                // Already registered, so only update the waker if it changed.
+                Some(waker) => {
+                    let mut waker = waker.lock().unwrap_or_else(|e| e.into_inner());
+                    if !waker.will_wake(cx.waker()) {
+                        *waker = cx.waker().clone();
+                    }
+                }
+                None => {
+                    let waker = Arc::new(Mutex::new(cx.waker().clone()));
+                    TIMERS.register(self.deadline, waker.clone());
+                    self.waker = Some(waker);
+                }
+            }
+            Poll::Pending
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use futures_util::task::noop_waker;
+
+        #[test]
+        fn sleep_registers_once() {
+            let mut sleep = Sleep::new(Duration::from_millis(50));
+            let waker = noop_waker();
+            let mut cx = Context::from_waker(&waker);
+            for _ in 0..3 {
+                assert!(Pin::new(&mut sleep).poll(&mut cx).is_pending());
+            }
+            // One reference is held by the sleep, and one by its single timer entry.
+            assert_eq!(sleep.waker.as_ref().map(Arc::strong_count), Some(2));
+            super::super::block_on(sleep);
+        }
+    }
+}
@@ -0,0 +1,371 @@
+use std::ffi::OsString;
+use std::fmt::{Debug, Formatter};
+use std::net::{Ipv4Addr, TcpListener};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
+use tokio::process::{Child, Command as ProcessCommand};
+use tokio::sync::Mutex;
+use url::Url;
+
+use crate::common::command::{Command, FormatRequestData};
+use crate::common::config::WebDriverConfig;
+use crate::error::{WebDriverError, WebDriverResult};
+use crate::session::http::{run_webdriver_cmd, HttpClient};
+use crate::{Capabilities, SessionId, WebDriverStatus};
+
+/// The default time to wait for the driver to report that it is ready.
+const DEFAULT_START_TIMEOUT: Duration = Duration::from_secs(20);
+
+/// The interval between `/status` requests while waiting for the driver to start.
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// A locally running WebDriver server process, such as chromedriver or geckodriver.
+///
+/// The process is started on a free port, its stdout and stderr are forwarded to
+/// `tracing` at debug level, and it is killed when the `DriverService` is stopped or dropped.
+///
+/// Use [`WebDriver::launch()`] to start the driver for a browser found on the `PATH`,
+/// or [`WebDriver::launch_with_service()`] to use a `DriverService` you configured yourself.
+///
+/// # Example:
+/// ```no_run
+/// # use thirtyfour::prelude::*;
+/// # use thirtyfour::support::block_on;
+/// use thirtyfour::DriverService;
+///
+/// # fn main() -> WebDriverResult<()> {
+/// #     block_on(async {
+/// let service = DriverService::builder("/usr/local/bin/chromedriver")
+///     .arg("--verbose")
+///     .start()
+///     .await?;
+/// let caps = DesiredCapabilities::chrome();
+/// let driver = WebDriver::launch_with_service(service, caps).await?;
+/// driver.goto("https://www.rust-lang.org/").await?;
+/// // Quitting the session also stops chromedriver.
+/// driver.quit().await?;
+/// #         Ok(())
+/// #     })
+/// # }
+/// ```
+///
+/// [`WebDriver::launch()`]: crate::WebDriver::launch
+/// [`WebDriver::launch_with_service()`]: crate::WebDriver::launch_with_service
+pub struct DriverService {
+    name: String,
+    url: Url,
+    child: Mutex<Child>,
+}
+
+impl Debug for DriverService {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DriverService").field("name", &self.name).field("url", &self.url).finish()
+    }
+}
+
+impl DriverService {
+    /// Create a new `DriverServiceBuilder` for the driver binary at the specified path.
+    pub fn builder(path: impl Into<PathBuf>) -> DriverServiceBuilder {
+        DriverServiceBuilder::new(path)
+    }
+
+    /// Start the driver binary at the specified path, using the default settings.
+    #[cfg(feature = "reqwest")]
+    pub async fn start(path: impl Into<PathBuf>) -> WebDriverResult<Self> {
+        Self::builder(path).start().await
+    }
+
+    /// Find the driver binary for the browser requested in the capabilities.
+    ///
+    /// The `browserName` capability selects the binary (`chromedriver`, `geckodriver` or
+    /// `msedgedriver`), which is then looked up on the `PATH`.
+    pub fn find_for(capabilities: &Capabilities) -> WebDriverResult<PathBuf> {
+        let browser = capabilities.get("browserName").and_then(|x| x.as_str()).unwrap_or_default();
+        let name = match browser {
+            "chrome" => "chromedriver",
+            "firefox" => "geckodriver",
+            "MicrosoftEdge" | "msedge" => "msedgedriver",
+            _ => {
+                return Err(WebDriverError::DriverServiceError(format!(
+                    "no known driver for browser '{browser}'"
+                )))
+            }
+        };
+        find_on_path(name).ok_or_else(|| {
+            WebDriverError::DriverServiceError(format!("{name} was not found on the PATH"))
+        })
+    }
+
+    /// The name of the driver binary, e.g. `chromedriver`.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The URL the driver is listening on.
+    pub fn url(&self) -> &Url {
+        &self.url
+    }
+
+    /// The port the driver is listening on.
+    pub fn port(&self) -> u16 {
+        self.url.port().unwrap_or_default()
+    }
+
+    /// Kill the driver process and wait for it to exit.
+    ///
+    /// Does nothing if the process has already exited.
+    pub async fn stop(&self) -> WebDriverResult<()> {
+        let mut child = self.child.lock().await;
+        if child.try_wait()?.is_none() {
+            tracing::debug!("stopping {} on {}", self.name, self.url);
+            child.kill().await?;
+        }
+        Ok(())
+    }
+}
+
+/// Builder for starting a [`DriverService`].
+#[derive(Debug)]
+pub struct DriverServiceBuilder {
+    path: PathBuf,
+    port: Option<u16>,
+    args: Vec<OsString>,
+    envs: Vec<(OsString, OsString)>,
+    start_timeout: Duration,
+}
+
+impl DriverServiceBuilder {
+    /// Create a new `DriverServiceBuilder` for the driver binary at the specified path.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            port: None,
+            args: Vec::new(),
+            envs: Vec::new(),
+            start_timeout: DEFAULT_START_TIMEOUT,
+        }
+    }
+
+    /// Listen on the specified port, rather than picking a free port.
+    pub fn port(mut self, port: u16) -> Self {
+        self.port = Some(port);
+        self
+    }
+
+    /// Pass an extra argument to the driver.
+    ///
+    /// The port is always passed as `--port=<port>`, which chromedriver, geckodriver
+    /// and msedgedriver all accept.
+    pub fn arg(mut self, arg: impl Into<OsString>) -> Self {
+        self.args.push(arg.into());
+        self
+    }
+
+    /// Pass extra arguments to the driver.
+    pub fn args(mut self, args: impl IntoIterator<Item = impl Into<OsString>>) -> Self {
+        self.args.extend(args.into_iter().map(Into::into));
+        self
+    }
+
+    /// Set an environment variable for the driver process.
+    pub fn env(mut self, key: impl Into<OsString>, value: impl Into<OsString>) -> Self {
+        self.envs.push((key.into(), value.into()));
+        self
+    }
+
+    /// How long to wait for the driver to report that it is ready.
+    pub fn start_timeout(mut self, timeout: Duration) -> Self {
+        self.start_timeout = timeout;
+        self
+    }
+
+    /// Start the driver and wait until it is ready to accept new sessions.
+    #[cfg(feature = "reqwest")]
+    pub async fn start(self) -> WebDriverResult<DriverService> {
+        let client = crate::session::http::create_reqwest_client(POLL_INTERVAL * 20);
+        self.start_with_client(&client).await
+    }
+
+    /// Start the driver, using the specified client to poll its `/status` endpoint.
+    pub async fn start_with_client(
+        self,
+        client: &dyn HttpClient,
+    ) -> WebDriverResult<DriverService> {
+        let port = match self.port {
+            Some(port) => port,
+            None => TcpListener::bind((Ipv4Addr::LOCALHOST, 0))?.local_addr()?.port(),
+        };
+        let url = Url::parse(&format!("http://localhost:{port}"))
+            .map_err(|e| WebDriverError::ParseError(format!("invalid url: {e}")))?;
+        let name = self
+            .path
+            .file_stem()
+            .map(|x| x.to_string_lossy().into_owned())
+            .unwrap_or_else(|| self.path.display().to_string());
+
+        tracing::debug!("starting {} on port {port}", self.path.display());
+        let mut child = ProcessCommand::new(&self.path)
+            .arg(format!("--port={port}"))
+            .args(&self.args)
+            .envs(self.envs.iter().map(|(k, v)| (k, v)))
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| {
+                WebDriverError::DriverServiceError(format!(
+                    "failed to start {}: {e}",
+                    self.path.display()
+                ))
+            })?;
+
+        let name: Arc<str> = Arc::from(name);
+        if let Some(stdout) = child.stdout.take() {
+            tokio::spawn(forward_output(name.clone(), stdout));
+        }
+        if let Some(stderr) = child.stderr.take() {
+            tokio::spawn(forward_output(name.clone(), stderr));
+        }
+
+        let service = DriverService {
+            name: name.to_string(),
+            url,
+            child: Mutex::new(child),
+        };
+        service.wait_until_ready(client, self.start_timeout).await?;
+        Ok(service)
+    }
+}
+
+impl DriverService {
+    async fn wait_until_ready(
+        &self,
+        client: &dyn HttpClient,
+        timeout: Duration,
+    ) -> WebDriverResult<()> {
+        let config = WebDriverConfig::default();
+        let request = Command::Status.format_request(&SessionId::null());
+        let deadline = Instant::now() + timeout;
+        loop {
+            if let Some(status) = self.child.lock().await.try_wait()? {
+                return Err(WebDriverError::DriverServiceError(format!(
+                    "{} exited during startup ({status})",
+                    self.name
+                )));
+            }
+
+            let resp = run_webdriver_cmd(client, &request, &self.url, &config).await;
+            match resp.and_then(|x| x.value::<WebDriverStatus>()) {
+                Ok(status) if status.ready => {
+                    tracing::debug!("{} is ready on {}", self.name, self.url);
+                    return Ok(());
+                }
+                Ok(status) => tracing::trace!("{} is not ready: {}", self.name, status.message),
+                Err(e) => tracing::trace!("{} is not ready: {e}", self.name),
+            }
+
+            if Instant::now() >= deadline {
+                self.stop().await?;
+                return Err(WebDriverError::DriverServiceError(format!(
+                    "{} was not ready after {timeout:?}",
+                    self.name
+                )));
+            }
+            crate::support::sleep(POLL_INTERVAL).await;
+        }
+    }
+}
+
+async fn forward_output(name: Arc<str>, output: impl AsyncRead + Unpin) {
+    let mut lines = BufReader::new(output).lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        tracing::debug!(driver = &*name, "{line}");
+    }
+}
+
+fn find_on_path(name: &str) -> Option<PathBuf> {
+    let file_name = format!("{name}{}", std::env::consts::EXE_SUFFIX);
+    let paths = std::env::var_os("PATH")?;
+    std::env::split_paths(&paths).map(|dir| dir.join(&file_name)).find(|x| is_file(x))
+}
+
+fn is_file(path: &Path) -> bool {
+    path.metadata().map(|x| x.is_file()).unwrap_or_default()
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::os::unix::fs::PermissionsExt;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use assert_matches::assert_matches;
+    use bytes::Bytes;
+    use http::{Request, Response};
+    use serde_json::json;
+
+    use super::*;
+    use crate::session::http::Body;
+
+    /// Reports ready once it has received `ready_after` `/status` requests.
+    struct FakeStatus {
+        requests: AtomicUsize,
+        ready_after: usize,
+    }
+
+    impl FakeStatus {
+        fn new(ready_after: usize) -> Self {
+            Self {
+                requests: AtomicUsize::new(0),
+                ready_after,
+            }
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl HttpClient for FakeStatus {
+        async fn send(&self, request: Request<Body<'_>>) -> WebDriverResult<Response<Bytes>> {
+            assert_eq!(request.uri().path(), "/status");
+            let ready = self.requests.fetch_add(1, Ordering::SeqCst) >= self.ready_after;
+            let body = json!({ "value": { "ready": ready, "message": "" } });
+            Ok(Response::builder().status(200).body(Bytes::from(body.to_string())).unwrap())
+        }
+    }
+
+    fn fake_driver(name: &str, script: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("{name}-{}", std::process::id()));
+        std::fs::write(&path, format!("#!/bin/sh\n{script}\n")).unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        path
+    }
+
+    #[tokio::test]
+    async fn start_and_stop() {
+        let path = fake_driver("fakedriver", "echo \"listening $1\"; exec sleep 30");
+        let client = FakeStatus::new(2);
+        let service = DriverService::builder(&path).start_with_client(&client).await.unwrap();
+        assert_eq!(service.name(), format!("fakedriver-{}", std::process::id()));
+        assert_eq!(client.requests.load(Ordering::SeqCst), 3);
+        assert_ne!(service.port(), 0);
+
+        service.stop().await.unwrap();
+        assert!(service.child.lock().await.try_wait().unwrap().is_some());
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn exit_during_startup() {
+        let path = fake_driver("crashingdriver", "exit 3");
+        let client = FakeStatus::new(usize::MAX);
+        let result = DriverService::builder(&path)
+            .start_timeout(Duration::from_secs(5))
+            .start_with_client(&client)
+            .await;
+        assert_matches!(result, Err(WebDriverError::DriverServiceError(msg)) if msg.contains("exited"));
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -6,9 +6,9 @@ use crate::{
         command::{Command, FormatRequestData},
         config::WebDriverConfig,
     },
-    prelude::{WebDriverError, WebDriverResult},
+    prelude::WebDriverResult,
     session::http::run_webdriver_cmd,
-    Capabilities, SessionId, TimeoutConfiguration,
+    support, Capabilities, SessionId, TimeoutConfiguration,
 };
 
 use super::http::HttpClient;
@@ -20,34 +20,32 @@ pub async fn start_session(
     server_url: &Url,
     config: &WebDriverConfig,
     capabilities: Capabilities,
-) -> WebDriverResult<SessionId> {
+) -> WebDriverResult<(SessionId, Capabilities)> {
     let request_data = Command::NewSession(serde_json::Value::Object(capabilities))
         .format_request(&SessionId::null());
 
-    let v = match run_webdriver_cmd(http_client, &request_data, server_url, config).await {
-        Ok(x) => Ok(x),
-        Err(e) => {
-            // Selenium sometimes gives a bogus 500 error "Chrome failed to start".
-            // Retry if we get a 500. If it happens twice in a row, then the second error
-            // will be returned.
-            if let WebDriverError::UnknownError(x) = &e {
-                if x.status == 500 {
-                    run_webdriver_cmd(http_client, &request_data, server_url, config).await
-                } else {
-                    Err(e)
-                }
-            } else {
-                Err(e)
+    // Selenium sometimes gives a bogus 500 error "Chrome failed to start", so by default
+    // this is retried once. If all attempts fail, the last error is returned.
+    let retry_policy = &config.session_retry_policy;
+    let mut retries = 0;
+    let v = loop {
+        match run_webdriver_cmd(http_client, &request_data, server_url, config).await {
+            Ok(x) => break x,
+            Err(e) if retries < retry_policy.max_retries() && retry_policy.should_retry(&e) => {
+                retries += 1;
+                tracing::debug!("retrying new session ({retries}) after error: {e}");
+                support::sleep(retry_policy.retry_delay()).await;
             }
+            Err(e) => return Err(e),
         }
-    }?;
+    };
 
     #[derive(Debug, Deserialize)]
     struct ConnectionData {
         #[serde(default, rename(deserialize = "sessionId"))]
         session_id: String,
-        // #[serde(default)]
-        // capabilities: serde_json::Value,
+        #[serde(default)]
+        capabilities: Capabilities,
     }
 
     #[derive(Debug, Deserialize)]
@@ -70,5 +68,104 @@ pub async fn start_session(
         Command::SetTimeouts(TimeoutConfiguration::default()).format_request(&session_id);
     run_webdriver_cmd(http_client, &request_data, server_url, config).await?;
 
-    Ok(session_id)
+    Ok((session_id, data.capabilities))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use bytes::Bytes;
+    use http::{Request, Response};
+    use serde_json::json;
+
+    use super::*;
+    use crate::common::config::SessionRetryPolicy;
+    use crate::error::WebDriverError;
+    use crate::session::http::Body;
+
+    /// Fails the first `failures` NewSession requests with an HTTP 500, and records the
+    /// authorization header of every request.
+    struct FlakyServer {
+        failures: Mutex<u32>,
+        auth: Mutex<Vec<String>>,
+    }
+
+    impl FlakyServer {
+        fn new(failures: u32) -> Self {
+            Self {
+                failures: Mutex::new(failures),
+                auth: Mutex::new(Vec::new()),
+            }
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl HttpClient for FlakyServer {
+        async fn send(&self, request: Request<Body<'_>>) -> WebDriverResult<Response<Bytes>> {
+            let auth = request.headers().get(http::header::AUTHORIZATION);
+            self.auth
+                .lock()
+                .unwrap()
+                .push(auth.map(|x| x.to_str().unwrap().to_string()).unwrap_or_default());
+
+            let mut failures = self.failures.lock().unwrap();
+            let (status, body) = if request.uri().path() != "/session" {
+                (200, json!({ "value": null }))
+            } else if *failures > 0 {
+                *failures -= 1;
+                (
+                    500,
+                    json!({ "value": { "error": "unknown error", "message": "failed to start" } }),
+                )
+            } else {
+                (
+                    200,
+                    json!({ "value": { "sessionId": "abc", "capabilities": { "browserName": "chrome" } } }),
+                )
+            };
+            Ok(Response::builder().status(status).body(Bytes::from(body.to_string())).unwrap())
+        }
+    }
+
+    async fn start(server: &FlakyServer, config: WebDriverConfig) -> WebDriverResult<SessionId> {
+        let url = Url::parse("http://localhost:4444").unwrap();
+        let (session_id, caps) = start_session(server, &url, &config, Capabilities::new()).await?;
+        assert_eq!(caps["browserName"], "chrome");
+        Ok(session_id)
+    }
+
+    #[tokio::test]
+    async fn retries_once_by_default() {
+        let server = FlakyServer::new(1);
+        let session_id = start(&server, WebDriverConfig::default()).await.unwrap();
+        assert_eq!(session_id.to_string(), "abc");
+
+        let server = FlakyServer::new(2);
+        let result = start(&server, WebDriverConfig::default()).await;
+        assert!(matches!(result, Err(WebDriverError::UnknownError(x)) if x.status == 500));
+    }
+
+    #[tokio::test]
+    async fn custom_retry_policy_and_headers() {
+        let config = WebDriverConfig::builder()
+            .session_retry_policy(SessionRetryPolicy::new(3))
+            .bearer_auth("token")
+            .build()
+            .unwrap();
+        let server = FlakyServer::new(3);
+        start(&server, config).await.unwrap();
+
+        // 4 NewSession attempts, then SetTimeouts.
+        let auth = server.auth.lock().unwrap().clone();
+        assert_eq!(auth.len(), 5);
+        assert!(auth.iter().all(|x| x == "Bearer token"));
+
+        let config = WebDriverConfig::builder()
+            .session_retry_policy(SessionRetryPolicy::none())
+            .build()
+            .unwrap();
+        let server = FlakyServer::new(1);
+        assert!(start(&server, config).await.is_err());
+    }
 }
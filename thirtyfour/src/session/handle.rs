@@ -1,24 +1,39 @@
 use std::fmt::{Debug, Display, Formatter};
 use std::future::Future;
 use std::path::Path;
-use std::sync::Arc;
-use std::time::Duration;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
 
+use arc_swap::ArcSwap;
+use serde::Deserialize;
 use serde_json::Value;
 use tokio::sync::OnceCell;
 use url::{ParseError, Url};
 
 use crate::action_chain::ActionChain;
 use crate::common::command::{Command, FormatRequestData};
-use crate::common::config::WebDriverConfig;
+use crate::common::config::{DropPolicy, RecoveryPolicy, WebDriverConfig};
 use crate::common::cookie::Cookie;
 use crate::common::print::PrintParameters;
 use crate::error::WebDriverResult;
+#[cfg(feature = "bidi")]
+use crate::extensions::bidi::{BiDiConnection, BiDiEventStream, Subscription, SubscriptionRequest};
+use crate::extensions::grid::{unzip_file, FilesCommand};
+use crate::locator;
 use crate::prelude::WebDriverError;
+#[cfg(feature = "tokio-runtime")]
+use crate::service::DriverService;
+use crate::session::create::start_session;
+use crate::session::listener::{notify, WebDriverEvent};
 use crate::session::scriptret::ScriptRet;
+use crate::session::stats::CommandStats;
+use crate::session::upload::zip_file;
 use crate::support::base64_decode;
 use crate::web_driver::AlreadyQuit;
-use crate::{support, By, OptionRect, Rect, SessionId, SwitchTo, WebDriverStatus, WebElement};
+use crate::{
+    support, By, Capabilities, OptionRect, Rect, SessionCapabilities, SessionId, SwitchTo,
+    WebDriverStatus, WebElement,
+};
 use crate::{IntoArcStr, IntoUrl};
 use crate::{TimeoutConfiguration, WindowHandle};
 
@@ -31,18 +46,65 @@ pub struct SessionHandle {
     pub client: Arc<dyn HttpClient>,
     /// The webdriver server URL.
     server_url: Arc<Url>,
-    /// The session id for this webdriver session.
+    /// The id of the session this handle was created with.
     session_id: SessionId,
+    /// The current session, which is replaced if the session is recovered after a crash.
+    session: Arc<ArcSwap<LiveSession>>,
+    /// What is needed to recover the session after a crash.
+    recovery: Arc<RecoveryState>,
     /// The config used by this instance.
     config: WebDriverConfig,
     /// quit session flag
     quit: Arc<OnceCell<()>>,
+    /// The driver process started for this session, if any. Stopped when the session quits.
+    #[cfg(feature = "tokio-runtime")]
+    service: Option<Arc<DriverService>>,
+    /// Counts and latencies of the commands sent by this session.
+    stats: Arc<CommandStats>,
+}
+
+/// The WebDriver session that a `SessionHandle` currently controls.
+#[derive(Debug)]
+struct LiveSession {
+    id: SessionId,
+    /// The number of times the session has been recovered.
+    generation: u64,
+    /// The capabilities returned by the server when the session was created.
+    capabilities: Arc<SessionCapabilities>,
+    /// The BiDi connection, opened on first use.
+    #[cfg(feature = "bidi")]
+    bidi: OnceCell<BiDiConnection>,
+}
+
+impl LiveSession {
+    fn new(id: SessionId, generation: u64, capabilities: SessionCapabilities) -> Self {
+        Self {
+            id,
+            generation,
+            capabilities: Arc::new(capabilities),
+            #[cfg(feature = "bidi")]
+            bidi: OnceCell::new(),
+        }
+    }
+}
+
+/// What is needed to recover the session after a crash.
+#[derive(Debug, Default)]
+struct RecoveryState {
+    /// The capabilities that created the session, if it was started by `WebDriver`.
+    capabilities: Option<Capabilities>,
+    /// Held while the session is being replaced.
+    lock: tokio::sync::Mutex<()>,
+    /// The last URL loaded with `goto()`.
+    url: Mutex<Option<Arc<str>>>,
+    /// The cookies to add to the new session.
+    cookies: Mutex<Vec<Cookie>>,
 }
 
 impl Debug for SessionHandle {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("SessionHandle")
-            .field("session_id", &self.session_id)
+            .field("session_id", &self.session.load().id)
             .field("config", &self.config)
             .finish()
     }
@@ -68,12 +130,48 @@ impl SessionHandle {
         Ok(Self {
             client,
             server_url: Arc::new(server_url.into_url()?),
+            session: Arc::new(ArcSwap::from_pointee(LiveSession::new(
+                session_id.clone(),
+                0,
+                SessionCapabilities::default(),
+            ))),
             session_id,
+            recovery: Arc::new(RecoveryState::default()),
             config,
             quit: Arc::new(OnceCell::new()),
+            #[cfg(feature = "tokio-runtime")]
+            service: None,
+            stats: Arc::new(CommandStats::default()),
         })
     }
 
+    /// Set the capabilities returned by the server when the session was created.
+    pub(crate) fn with_capabilities(mut self, capabilities: Capabilities) -> Self {
+        let id = self.session_id.clone();
+        self.session = Arc::new(ArcSwap::from_pointee(LiveSession::new(
+            id,
+            0,
+            SessionCapabilities::new(capabilities),
+        )));
+        self
+    }
+
+    /// Attach the driver process, so that it is stopped when the session quits.
+    #[cfg(feature = "tokio-runtime")]
+    pub(crate) fn with_service(mut self, service: DriverService) -> Self {
+        self.service = Some(Arc::new(service));
+        self
+    }
+
+    /// Remember the capabilities that created the session, so that it can be recovered.
+    pub(crate) fn with_recovery_capabilities(mut self, capabilities: Capabilities) -> Self {
+        self.recovery = Arc::new(RecoveryState {
+            capabilities: Some(capabilities),
+            ..Default::default()
+        });
+        self
+    }
+
     /// Clone this session handle but attach the specified `WebDriverConfig`.
     ///
     /// See `WebDriver::clone_with_config()`.
@@ -82,16 +180,72 @@ impl SessionHandle {
             client: self.client.clone(),
             server_url: self.server_url.clone(),
             session_id: self.session_id.clone(),
+            session: Arc::clone(&self.session),
+            recovery: Arc::clone(&self.recovery),
             quit: Arc::clone(&self.quit),
+            #[cfg(feature = "tokio-runtime")]
+            service: self.service.clone(),
+            stats: Arc::clone(&self.stats),
             config,
         }
     }
 
+    /// The URL of the WebDriver server.
+    pub fn server_url(&self) -> &Url {
+        &self.server_url
+    }
+
     /// The session id for this webdriver session.
+    ///
+    /// If the session has been recovered after a crash, this is still the id of the
+    /// original session. Use [`SessionHandle::current_session_id()`] to get the id of the
+    /// session that commands are sent to.
     pub fn session_id(&self) -> &SessionId {
         &self.session_id
     }
 
+    /// The id of the session that commands are currently sent to.
+    ///
+    /// This changes if the session is recovered after a crash. See [`RecoveryPolicy`].
+    pub fn current_session_id(&self) -> SessionId {
+        self.session.load().id.clone()
+    }
+
+    /// The number of times the session has been recovered after a crash.
+    pub(crate) fn generation(&self) -> u64 {
+        self.session.load().generation
+    }
+
+    /// The capabilities negotiated with the WebDriver server when the session was created.
+    ///
+    /// These describe what the driver actually granted, which may differ from what
+    /// was requested. For sessions created via [`SessionHandle::new()`] or attached via
+    /// [`WebDriver::attach()`] this is empty. If the session is recovered after a crash,
+    /// these are the capabilities of the new session.
+    ///
+    /// [`WebDriver::attach()`]: crate::WebDriver::attach
+    ///
+    /// # Example:
+    /// ```no_run
+    /// # use thirtyfour::prelude::*;
+    /// # use thirtyfour::support::block_on;
+    /// #
+    /// # fn main() -> WebDriverResult<()> {
+    /// #     block_on(async {
+    /// let caps = DesiredCapabilities::firefox();
+    /// let driver = WebDriver::new("http://localhost:4444", caps).await?;
+    /// if driver.capabilities().browser_name() == Some("firefox") {
+    ///     println!("debugger: {:?}", driver.capabilities().firefox_debugger_address());
+    /// }
+    /// #         driver.quit().await?;
+    /// #         Ok(())
+    /// #     })
+    /// # }
+    /// ```
+    pub fn capabilities(&self) -> Arc<SessionCapabilities> {
+        self.session.load().capabilities.clone()
+    }
+
     /// The configuration used by this instance.
     ///
     /// NOTE: It's sometimes useful to have separate instances pointing at the same
@@ -104,9 +258,156 @@ impl SessionHandle {
     }
 
     /// Send the specified command to the webdriver server.
+    ///
+    /// If the browser has crashed and a [`RecoveryPolicy`] is configured, the session is
+    /// replaced and [`WebDriverError::SessionRecovered`] is returned.
     pub async fn cmd(&self, command: impl FormatRequestData) -> WebDriverResult<CmdResponse> {
-        let request_data = command.format_request(&self.session_id);
-        run_webdriver_cmd(&*self.client, &request_data, &self.server_url, &self.config).await
+        let session = self.session.load_full();
+        match self.send(&command, &session.id).await {
+            Err(e) if self.is_crash(&e, &session.id).await => Err(self.recover(&session, e).await),
+            result => result,
+        }
+    }
+
+    async fn send(
+        &self,
+        command: &impl FormatRequestData,
+        session_id: &SessionId,
+    ) -> WebDriverResult<CmdResponse> {
+        let mut request_data = command.format_request(session_id);
+        let name = request_data.command_name.get_or_insert_with(|| command.command_name()).clone();
+        let start = Instant::now();
+        let result =
+            run_webdriver_cmd(&*self.client, &request_data, &self.server_url, &self.config).await;
+        self.stats.record(&name, start.elapsed(), result.is_ok());
+        result
+    }
+
+    /// Return true if the error means the browser crashed, and the session can be recovered.
+    async fn is_crash(&self, error: &WebDriverError, session_id: &SessionId) -> bool {
+        let Some(policy) = &self.config.recovery_policy else {
+            return false;
+        };
+        if self.recovery.capabilities.is_none() || !policy.should_recover(error) {
+            return false;
+        }
+        match error {
+            // `unknown error` is also returned for many other failures, so check that the
+            // session no longer responds.
+            WebDriverError::UnknownError(_) => matches!(
+                self.send(&Command::GetWindowHandle, session_id).await,
+                Err(WebDriverError::InvalidSessionId(_) | WebDriverError::UnknownError(_))
+            ),
+            _ => true,
+        }
+    }
+
+    /// Replace the crashed session with a new one, and return the error for the failed command.
+    async fn recover(&self, crashed: &LiveSession, error: WebDriverError) -> WebDriverError {
+        let guard = self.recovery.lock.lock().await;
+        let current = self.session.load_full();
+        if current.generation != crashed.generation {
+            // Another command has already replaced the session.
+            return WebDriverError::SessionRecovered(format!(
+                "session {} was replaced by session {}",
+                crashed.id, current.id
+            ));
+        }
+        let (Some(policy), Some(capabilities)) =
+            (&self.config.recovery_policy, &self.recovery.capabilities)
+        else {
+            return error;
+        };
+        if self.quit.initialized() || current.generation >= u64::from(policy.max_recoveries()) {
+            return error;
+        }
+
+        tracing::warn!("session {} crashed, starting a new session: {error}", crashed.id);
+        // The crashed session is usually gone already, but delete it in case it is not.
+        let _ = self.send(&Command::DeleteSession, &crashed.id).await;
+        let (session_id, capabilities) = match start_session(
+            self.client.as_ref(),
+            &self.server_url,
+            &self.config,
+            capabilities.clone(),
+        )
+        .await
+        {
+            Ok(x) => x,
+            Err(e) => return e,
+        };
+        // The new session has its own capabilities and BiDi connection.
+        self.session.store(Arc::new(LiveSession::new(
+            session_id.clone(),
+            crashed.generation + 1,
+            SessionCapabilities::new(capabilities),
+        )));
+        if let Err(e) = self.restore(policy, &session_id).await {
+            tracing::warn!("failed to restore the state of session {session_id}: {e}");
+        }
+        drop(guard);
+
+        for listener in &self.config.listeners {
+            listener.on_session_recovered(self, &crashed.id).await;
+        }
+        WebDriverError::SessionRecovered(format!(
+            "session {} was replaced by session {session_id} after: {error}",
+            crashed.id
+        ))
+    }
+
+    /// Restore the URL and cookies of the crashed session in the new session.
+    async fn restore(
+        &self,
+        policy: &RecoveryPolicy,
+        session_id: &SessionId,
+    ) -> WebDriverResult<()> {
+        let url = self.recovery.url.lock().unwrap_or_else(PoisonError::into_inner).clone();
+        let Some(url) = url.filter(|_| policy.restores_url()) else {
+            return Ok(());
+        };
+        self.send(&Command::NavigateTo(url), session_id).await?;
+        if policy.restores_cookies() {
+            let cookies =
+                self.recovery.cookies.lock().unwrap_or_else(PoisonError::into_inner).clone();
+            if !cookies.is_empty() {
+                for cookie in cookies {
+                    self.send(&Command::AddCookie(cookie), session_id).await?;
+                }
+                self.send(&Command::Refresh, session_id).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Remember the URL and cookies to restore if the session is recovered after a crash.
+    async fn save_recovery_state(&self, url: Arc<str>) {
+        let Some(policy) = &self.config.recovery_policy else {
+            return;
+        };
+        if policy.restores_url() {
+            *self.recovery.url.lock().unwrap_or_else(PoisonError::into_inner) = Some(url);
+        }
+        if policy.restores_cookies() {
+            match self.get_all_cookies().await {
+                Ok(cookies) => self.update_saved_cookies(|x| *x = cookies),
+                Err(e) => tracing::debug!("failed to save cookies for recovery: {e}"),
+            }
+        }
+    }
+
+    /// Update the cookies to restore if the session is recovered after a crash.
+    fn update_saved_cookies(&self, f: impl FnOnce(&mut Vec<Cookie>)) {
+        if self.config.recovery_policy.as_ref().is_some_and(|x| x.restores_cookies()) {
+            f(&mut self.recovery.cookies.lock().unwrap_or_else(PoisonError::into_inner));
+        }
+    }
+
+    /// The counts and latencies of the commands sent by this session, by command name.
+    ///
+    /// See [`CommandStats`] for an example.
+    pub fn command_stats(&self) -> &CommandStats {
+        &self.stats
     }
 
     /// Get the WebDriver status.
@@ -198,7 +499,12 @@ impl SessionHandle {
                 e => Err(e),
             })
             .map_err(WebDriverError::InvalidUrl)?;
-        self.cmd(Command::NavigateTo(url)).await?;
+        let event = WebDriverEvent::Navigate {
+            handle: self,
+            url: &url,
+        };
+        notify(&self.config.listeners, event, self.cmd(Command::NavigateTo(url.clone()))).await?;
+        self.save_recovery_state(url).await;
         Ok(())
     }
 
@@ -254,7 +560,10 @@ impl SessionHandle {
     /// # }
     /// ```
     pub async fn find(self: &Arc<Self>, by: By) -> WebDriverResult<WebElement> {
-        let r = self.cmd(Command::FindElement(by.into())).await?;
+        if by.is_refined() {
+            return locator::first(self.find_all(by.clone()).await?, &by);
+        }
+        let r = self.cmd(Command::FindElement(by.candidates())).await?;
         r.element(self.clone())
     }
 
@@ -290,8 +599,14 @@ impl SessionHandle {
     /// # }
     /// ```
     pub async fn find_all(self: &Arc<Self>, by: By) -> WebDriverResult<Vec<WebElement>> {
-        let r = self.cmd(Command::FindElements(by.into())).await?;
-        r.elements(self.clone())
+        let refine = by.refine();
+        let elements = match by.script() {
+            Some((script, args)) => locator::find_all_js(self, script, args, Value::Null).await?,
+            None => {
+                self.cmd(Command::FindElements(by.candidates())).await?.elements(self.clone())?
+            }
+        };
+        refine.apply(elements).await
     }
 
     /// Search for all elements on the current page that match the specified selector.
@@ -353,8 +668,15 @@ impl SessionHandle {
         script: impl IntoArcStr,
         args: impl Into<Arc<[Value]>>,
     ) -> WebDriverResult<ScriptRet> {
-        let r = self.cmd(Command::ExecuteScript(script.into(), args.into())).await?;
-        Ok(ScriptRet::new(self.clone(), r.value()?))
+        let script = script.into();
+        let event = WebDriverEvent::Execute {
+            handle: self,
+            script: &script,
+        };
+        let command = Command::ExecuteScript(script.clone(), args.into());
+        let r = notify(&self.config.listeners, event, async { self.cmd(command).await?.value() })
+            .await?;
+        Ok(ScriptRet::new(self.clone(), r))
     }
 
     /// Execute the specified Javascript synchronously and return the result.
@@ -671,7 +993,7 @@ impl SessionHandle {
     /// # }
     /// ```
     pub async fn back(&self) -> WebDriverResult<()> {
-        self.cmd(Command::Back).await?;
+        notify(&self.config.listeners, WebDriverEvent::Back(self), self.cmd(Command::Back)).await?;
         Ok(())
     }
 
@@ -984,7 +1306,9 @@ impl SessionHandle {
     /// # }
     /// ```
     pub async fn delete_cookie(&self, name: impl IntoArcStr) -> WebDriverResult<()> {
-        self.cmd(Command::DeleteCookie(name.into())).await?;
+        let name = name.into();
+        self.cmd(Command::DeleteCookie(name.clone())).await?;
+        self.update_saved_cookies(|x| x.retain(|c| c.name != *name));
         Ok(())
     }
 
@@ -1007,6 +1331,7 @@ impl SessionHandle {
     /// ```
     pub async fn delete_all_cookies(&self) -> WebDriverResult<()> {
         self.cmd(Command::DeleteAllCookies).await?;
+        self.update_saved_cookies(Vec::clear);
         Ok(())
     }
 
@@ -1033,7 +1358,11 @@ impl SessionHandle {
     /// # }
     /// ```
     pub async fn add_cookie(&self, cookie: Cookie) -> WebDriverResult<()> {
-        self.cmd(Command::AddCookie(cookie)).await?;
+        self.cmd(Command::AddCookie(cookie.clone())).await?;
+        self.update_saved_cookies(|x| {
+            x.retain(|c| c.name != cookie.name);
+            x.push(cookie);
+        });
         Ok(())
     }
 
@@ -1064,6 +1393,92 @@ impl SessionHandle {
         Ok(())
     }
 
+    /// Get the names of the files downloaded by the browser.
+    ///
+    /// This requires a Selenium Grid 4 session with managed downloads enabled.
+    /// See [`CapabilitiesHelper::set_downloads_enabled()`].
+    ///
+    /// # Example:
+    /// ```no_run
+    /// # use thirtyfour::prelude::*;
+    /// # use thirtyfour::support::block_on;
+    /// #
+    /// # fn main() -> WebDriverResult<()> {
+    /// #     block_on(async {
+    /// let mut caps = DesiredCapabilities::chrome();
+    /// caps.set_downloads_enabled(true)?;
+    /// let driver = WebDriver::new("http://localhost:4444", caps).await?;
+    /// driver.goto("https://example.com/export").await?;
+    /// driver.find(By::Id("export-csv")).await?.click().await?;
+    ///
+    /// let files = driver.downloadable_files().await?;
+    /// let csv = driver.download_file_as_bytes(&files[0]).await?;
+    /// driver.delete_downloadable_files().await?;
+    /// #         driver.quit().await?;
+    /// #         Ok(())
+    /// #     })
+    /// # }
+    /// ```
+    ///
+    /// [`CapabilitiesHelper::set_downloads_enabled()`]: crate::CapabilitiesHelper::set_downloads_enabled
+    pub async fn downloadable_files(&self) -> WebDriverResult<Vec<String>> {
+        #[derive(Deserialize)]
+        struct DownloadableFiles {
+            names: Vec<String>,
+        }
+
+        let command = Command::ExtensionCommand(Box::new(FilesCommand::GetDownloadableFiles));
+        let files: DownloadableFiles = self.cmd(command).await?.value()?;
+        Ok(files.names)
+    }
+
+    /// Fetch the file with the specified name downloaded by the browser, and return its contents.
+    ///
+    /// See [`SessionHandle::downloadable_files()`].
+    pub async fn download_file_as_bytes(&self, name: &str) -> WebDriverResult<Vec<u8>> {
+        #[derive(Deserialize)]
+        struct DownloadedFile {
+            contents: String,
+        }
+
+        let command =
+            Command::ExtensionCommand(Box::new(FilesCommand::DownloadFile(name.to_string())));
+        let file: DownloadedFile = self.cmd(command).await?.value()?;
+        unzip_file(&base64_decode(&file.contents)?)
+    }
+
+    /// Fetch the file with the specified name downloaded by the browser, and write it to the
+    /// specified path.
+    ///
+    /// See [`SessionHandle::downloadable_files()`].
+    pub async fn download_file(&self, name: &str, path: &Path) -> WebDriverResult<()> {
+        let contents = self.download_file_as_bytes(name).await?;
+        support::write_file(path, contents).await?;
+        Ok(())
+    }
+
+    /// Upload the local file at the specified path to the WebDriver server, and return
+    /// its path on the remote machine.
+    ///
+    /// This is only supported by Selenium. Use [`WebElement::upload_file()`] to upload
+    /// a file and select it in a file input.
+    ///
+    /// [`WebElement::upload_file()`]: crate::WebElement::upload_file
+    pub async fn upload_file(&self, path: &Path) -> WebDriverResult<String> {
+        let archive = support::base64_encode(&zip_file(path).await?);
+        let command = Command::ExtensionCommand(Box::new(FilesCommand::UploadFile(archive)));
+        self.cmd(command).await?.value()
+    }
+
+    /// Delete all files downloaded by the browser.
+    ///
+    /// See [`SessionHandle::downloadable_files()`].
+    pub async fn delete_downloadable_files(&self) -> WebDriverResult<()> {
+        let command = Command::ExtensionCommand(Box::new(FilesCommand::DeleteDownloadableFiles));
+        self.cmd(command).await?;
+        Ok(())
+    }
+
     /// Return a SwitchTo struct for switching to another window or frame.
     #[deprecated(
         since = "0.30.0",
@@ -1163,9 +1578,107 @@ impl SessionHandle {
         result
     }
 
+    /// Get the WebDriver BiDi connection for this session, connecting on first use.
+    ///
+    /// The session must have been created with the `webSocketUrl` capability enabled.
+    /// See [`CapabilitiesHelper::set_web_socket_url()`].
+    ///
+    /// The connection is shared by all clones of this session handle. If the session is
+    /// recovered after a crash, a new connection is opened to the new session.
+    ///
+    /// # Example:
+    /// ```no_run
+    /// # use thirtyfour::prelude::*;
+    /// # use thirtyfour::support::block_on;
+    /// #
+    /// # fn main() -> WebDriverResult<()> {
+    /// #     block_on(async {
+    /// let mut caps = DesiredCapabilities::chrome();
+    /// caps.set_web_socket_url(true)?;
+    /// let driver = WebDriver::new("http://localhost:4444", caps).await?;
+    /// let bidi = driver.bidi().await?;
+    /// #         driver.quit().await?;
+    /// #         Ok(())
+    /// #     })
+    /// # }
+    /// ```
+    ///
+    /// [`CapabilitiesHelper::set_web_socket_url()`]: crate::CapabilitiesHelper::set_web_socket_url
+    #[cfg(feature = "bidi")]
+    pub async fn bidi(&self) -> WebDriverResult<BiDiConnection> {
+        let session = self.session.load_full();
+        session
+            .bidi
+            .get_or_try_init(|| async {
+                let url = session.capabilities.web_socket_url().ok_or_else(|| {
+                    WebDriverError::NotFound(
+                        "webSocketUrl".to_string(),
+                        "the session was not created with the webSocketUrl capability".to_string(),
+                    )
+                })?;
+                BiDiConnection::connect(url).await
+            })
+            .await
+            .cloned()
+    }
+
+    /// Subscribe to the specified BiDi events for this session.
+    ///
+    /// Use [`SessionHandle::bidi_events()`] to receive the events.
+    #[cfg(feature = "bidi")]
+    pub async fn bidi_subscribe(
+        &self,
+        request: impl Into<SubscriptionRequest>,
+    ) -> WebDriverResult<Subscription> {
+        self.bidi().await?.subscribe(request).await
+    }
+
+    /// Return a stream of all BiDi events received for this session from now on.
+    ///
+    /// # Example:
+    /// ```no_run
+    /// # use futures_util::StreamExt;
+    /// # use thirtyfour::prelude::*;
+    /// # use thirtyfour::extensions::bidi::BiDiEvent;
+    /// # use thirtyfour::support::block_on;
+    /// #
+    /// # fn main() -> WebDriverResult<()> {
+    /// #     block_on(async {
+    /// #         let mut caps = DesiredCapabilities::chrome();
+    /// #         caps.set_web_socket_url(true)?;
+    /// #         let driver = WebDriver::new("http://localhost:4444", caps).await?;
+    /// let mut events = driver.bidi_events().await?;
+    /// driver.bidi_subscribe(BiDiEvent::LOAD).await?;
+    /// driver.goto("https://www.rust-lang.org").await?;
+    /// while let Some(event) = events.next().await {
+    ///     if let BiDiEvent::Load(info) = event {
+    ///         println!("loaded {}", info.url);
+    ///         break;
+    ///     }
+    /// }
+    /// #         driver.quit().await?;
+    /// #         Ok(())
+    /// #     })
+    /// # }
+    /// ```
+    #[cfg(feature = "bidi")]
+    pub async fn bidi_events(&self) -> WebDriverResult<BiDiEventStream> {
+        Ok(self.bidi().await?.events())
+    }
+
     pub(crate) async fn quit(&self) -> WebDriverResult<()> {
         self.quit
-            .get_or_try_init(|| async { self.cmd(Command::DeleteSession).await.map(drop) })
+            .get_or_try_init(|| async {
+                let result =
+                    self.send(&Command::DeleteSession, &self.current_session_id()).await.map(drop);
+                // Always stop the driver, but report the error from DeleteSession first.
+                #[cfg(feature = "tokio-runtime")]
+                let result = match &self.service {
+                    Some(service) => result.and(service.stop().await),
+                    None => result,
+                };
+                result
+            })
             .await?;
         Ok(())
     }
@@ -1183,12 +1696,21 @@ impl Drop for SessionHandle {
             return;
         }
 
-        #[cfg(feature = "debug_sync_quit")]
-        eprintln!(
-            "WebDriver didn't wasn't quit properly at\n{}",
-            std::backtrace::Backtrace::force_capture()
-        );
-
-        let _ = support::block_on(self.quit());
+        match self.config.drop_policy {
+            DropPolicy::Quit => {
+                #[cfg(feature = "debug_sync_quit")]
+                eprintln!(
+                    "WebDriver didn't wasn't quit properly at\n{}",
+                    std::backtrace::Backtrace::force_capture()
+                );
+
+                let _ = support::block_on(self.quit());
+            }
+            DropPolicy::Leak => {}
+            DropPolicy::Warn => tracing::warn!(
+                "WebDriver session {} was dropped without calling quit(), and has been leaked",
+                self.current_session_id()
+            ),
+        }
     }
 }
@@ -1,4 +1,5 @@
 use std::sync::Arc;
+use std::time::Instant;
 
 use base64::Engine;
 use bytes::Bytes;
@@ -7,6 +8,7 @@ use http::{
     HeaderValue, Request, Response,
 };
 use serde_json::Value;
+use tracing::Instrument;
 use url::Url;
 
 use crate::{
@@ -16,6 +18,7 @@ use crate::{
 };
 
 use super::handle::SessionHandle;
+use super::layer::Next;
 
 /// Enum representing the body of an HTTP request.
 #[derive(Debug, Clone)]
@@ -35,6 +38,13 @@ impl<'a, T: Into<Option<&'a Value>>> From<T> for Body<'a> {
     }
 }
 
+/// Marks a request whose body is sensitive, such as keys sent to a password field.
+///
+/// This is added to the extensions of the request passed to [`HttpClient::send()`] when
+/// [`RequestData::sensitive`] is set, so that clients can avoid logging or storing the body.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SensitiveBody;
+
 /// Trait used to implement a HTTP client.
 #[async_trait::async_trait]
 pub trait HttpClient: Send + Sync + 'static {
@@ -42,6 +52,13 @@ pub trait HttpClient: Send + Sync + 'static {
     async fn send(&self, request: Request<Body<'_>>) -> WebDriverResult<Response<Bytes>>;
 }
 
+#[async_trait::async_trait]
+impl<T: HttpClient + ?Sized> HttpClient for Arc<T> {
+    async fn send(&self, request: Request<Body<'_>>) -> WebDriverResult<Response<Bytes>> {
+        (**self).send(request).await
+    }
+}
+
 #[cfg(feature = "reqwest")]
 #[async_trait::async_trait]
 impl HttpClient for reqwest::Client {
@@ -105,12 +122,46 @@ pub(crate) mod null_client {
     }
 }
 
-#[tracing::instrument(skip_all)]
+/// Run the command through the `CommandLayer` stack, inside a `webdriver_command` span.
+///
+/// The span records the command name, session id, HTTP status, duration and error variant.
 pub(crate) async fn run_webdriver_cmd(
     client: &dyn HttpClient,
     request_data: &RequestData,
     server_url: &Url,
     config: &WebDriverConfig,
+) -> WebDriverResult<CmdResponse> {
+    let span = tracing::debug_span!(
+        "webdriver_command",
+        command = request_data.command_name.as_deref().unwrap_or("Unknown"),
+        session_id = session_id_from_uri(&request_data.uri),
+        status = tracing::field::Empty,
+        duration_ms = tracing::field::Empty,
+        error = tracing::field::Empty,
+    );
+    let start = Instant::now();
+    let result = Next::new(client, server_url, config)
+        .run(request_data.clone())
+        .instrument(span.clone())
+        .await;
+    span.record("duration_ms", start.elapsed().as_millis() as u64);
+    if let Err(e) = &result {
+        span.record("error", e.name());
+    }
+    result
+}
+
+/// Get the session id from a request URI such as `/session/{id}/url`.
+fn session_id_from_uri(uri: &str) -> Option<&str> {
+    uri.strip_prefix("/session/").and_then(|x| x.split('/').next())
+}
+
+/// Send the request to the WebDriver server, after all command layers have run.
+pub(crate) async fn send_request(
+    client: &dyn HttpClient,
+    request_data: &RequestData,
+    server_url: &Url,
+    config: &WebDriverConfig,
 ) -> WebDriverResult<CmdResponse> {
     tracing::debug!("webdriver request: {request_data}");
     let uri = server_url
@@ -145,11 +196,17 @@ pub(crate) async fn run_webdriver_cmd(
         None => Body::Empty,
     };
 
-    let request = builder
+    let mut request = builder
         .body(body)
         .map_err(|e| WebDriverError::RequestFailed(format!("invalid request body: {e}")))?;
+    request.headers_mut().extend(config.headers.clone());
+    request.headers_mut().extend(request_data.headers.clone());
+    if request_data.sensitive {
+        request.extensions_mut().insert(SensitiveBody);
+    }
     let response = client.send(request).await?;
     let status = response.status().as_u16();
+    tracing::Span::current().record("status", status);
     let lossy_response = String::from_utf8_lossy(response.body());
     tracing::debug!("webdriver response: {status} {lossy_response}");
     match status {
@@ -0,0 +1,194 @@
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use url::Url;
+
+use crate::common::config::WebDriverConfig;
+use crate::error::WebDriverResult;
+use crate::RequestData;
+
+use super::http::{send_request, CmdResponse, HttpClient};
+
+/// Middleware that wraps every command sent to the WebDriver server.
+///
+/// Layers are added to the [`WebDriverConfig`] via [`WebDriverConfigBuilder::layer()`],
+/// and are run in the order they were added. Each layer receives the request and a
+/// [`Next`] handle for running the rest of the stack, and may modify the request,
+/// inspect or replace the response, or call [`Next::run()`] more than once (e.g. to
+/// retry transient errors).
+///
+/// Layers also apply to the commands sent while starting the session.
+///
+/// # Example
+/// ```
+/// # use std::time::Instant;
+/// # use thirtyfour::prelude::*;
+/// # use thirtyfour::RequestData;
+/// # use thirtyfour::common::config::WebDriverConfig;
+/// # use thirtyfour::session::http::CmdResponse;
+/// # use thirtyfour::session::layer::{CommandLayer, Next};
+/// /// Logs the duration of every command, without logging the keys sent to elements.
+/// #[derive(Debug)]
+/// struct TimingLayer;
+///
+/// #[async_trait::async_trait]
+/// impl CommandLayer for TimingLayer {
+///     async fn call(
+///         &self,
+///         mut request: RequestData,
+///         next: Next<'_>,
+///     ) -> WebDriverResult<CmdResponse> {
+///         if request.uri.ends_with("/value") {
+///             request.set_sensitive(true);
+///         }
+///         let description = request.to_string();
+///         let start = Instant::now();
+///         let result = next.run(request).await;
+///         println!("{description} took {:?}", start.elapsed());
+///         result
+///     }
+/// }
+///
+/// # fn main() -> WebDriverResult<()> {
+/// let config = WebDriverConfig::builder().layer(TimingLayer).build()?;
+/// #     Ok(())
+/// # }
+/// ```
+///
+/// [`WebDriverConfigBuilder::layer()`]: crate::common::config::WebDriverConfigBuilder::layer
+#[async_trait::async_trait]
+pub trait CommandLayer: Debug + Send + Sync + 'static {
+    /// Handle the request, calling `next.run(request)` to pass it on to the next layer
+    /// (or to the HTTP client if this is the last layer).
+    async fn call(&self, request: RequestData, next: Next<'_>) -> WebDriverResult<CmdResponse>;
+}
+
+/// The remainder of the [`CommandLayer`] stack.
+#[derive(Clone, Copy)]
+pub struct Next<'a> {
+    layers: &'a [Arc<dyn CommandLayer>],
+    client: &'a dyn HttpClient,
+    server_url: &'a Url,
+    config: &'a WebDriverConfig,
+}
+
+impl Debug for Next<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Next").field("layers", &self.layers).finish()
+    }
+}
+
+impl<'a> Next<'a> {
+    pub(crate) fn new(
+        client: &'a dyn HttpClient,
+        server_url: &'a Url,
+        config: &'a WebDriverConfig,
+    ) -> Self {
+        Self {
+            layers: &config.layers,
+            client,
+            server_url,
+            config,
+        }
+    }
+
+    /// Run the rest of the stack for the specified request.
+    pub async fn run(self, request: RequestData) -> WebDriverResult<CmdResponse> {
+        match self.layers.split_first() {
+            Some((layer, layers)) => {
+                let next = Self {
+                    layers,
+                    ..self
+                };
+                layer.call(request, next).await
+            }
+            None => send_request(self.client, &request, self.server_url, self.config).await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
+
+    use bytes::Bytes;
+    use http::{HeaderValue, Method, Request, Response};
+    use serde_json::json;
+
+    use super::*;
+    use crate::error::WebDriverError;
+    use crate::session::http::{run_webdriver_cmd, Body};
+
+    /// Fails the first request with a stale element error, then echoes the `x-layer` headers.
+    #[derive(Default)]
+    struct EchoServer {
+        requests: AtomicUsize,
+    }
+
+    #[async_trait::async_trait]
+    impl HttpClient for EchoServer {
+        async fn send(&self, request: Request<Body<'_>>) -> WebDriverResult<Response<Bytes>> {
+            let body = match self.requests.fetch_add(1, Ordering::SeqCst) {
+                0 => json!({ "value": { "error": "stale element reference", "message": "" } }),
+                _ => {
+                    let values = request.headers().get_all("x-layer").iter();
+                    let values: Vec<_> = values.map(|x| x.to_str().unwrap()).collect();
+                    json!({ "value": values })
+                }
+            };
+            let status = if body["value"].is_array() {
+                200
+            } else {
+                404
+            };
+            Ok(Response::builder().status(status).body(Bytes::from(body.to_string())).unwrap())
+        }
+    }
+
+    #[derive(Debug)]
+    struct HeaderLayer(&'static str, Arc<Mutex<Vec<&'static str>>>);
+
+    #[async_trait::async_trait]
+    impl CommandLayer for HeaderLayer {
+        async fn call(&self, request: RequestData, next: Next<'_>) -> WebDriverResult<CmdResponse> {
+            self.1.lock().unwrap().push(self.0);
+            let request = request.add_header(
+                http::HeaderName::from_static("x-layer"),
+                HeaderValue::from_static(self.0),
+            );
+            next.run(request).await
+        }
+    }
+
+    #[derive(Debug)]
+    struct RetryLayer;
+
+    #[async_trait::async_trait]
+    impl CommandLayer for RetryLayer {
+        async fn call(&self, request: RequestData, next: Next<'_>) -> WebDriverResult<CmdResponse> {
+            match next.run(request.clone()).await {
+                Err(WebDriverError::StaleElementReference(_)) => next.run(request).await,
+                result => result,
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn layers_run_in_order() {
+        let order = Arc::new(Mutex::new(Vec::new()));
+        let config = WebDriverConfig::builder()
+            .layer(RetryLayer)
+            .layer(HeaderLayer("outer", order.clone()))
+            .layer(HeaderLayer("inner", order.clone()))
+            .build()
+            .unwrap();
+        let url = Url::parse("http://localhost:4444").unwrap();
+        let request = RequestData::new(Method::GET, "/status");
+
+        let resp = run_webdriver_cmd(&EchoServer::default(), &request, &url, &config).await;
+        let value: Vec<String> = resp.unwrap().value().unwrap();
+        assert_eq!(value, ["outer", "inner"]);
+        assert_eq!(*order.lock().unwrap(), ["outer", "inner", "outer", "inner"]);
+    }
+}
@@ -0,0 +1,232 @@
+use std::fmt::Debug;
+use std::future::Future;
+use std::sync::Arc;
+
+use crate::error::{WebDriverError, WebDriverResult};
+use crate::session::handle::SessionHandle;
+use crate::{SessionId, TypingData, WebElement};
+
+/// Hooks that are called before and after high-level browser actions.
+///
+/// Listeners are added to the [`WebDriverConfig`] via
+/// [`WebDriverConfigBuilder::listener()`], and are called in the order they were added.
+/// Unlike a [`CommandLayer`], which sees every raw WebDriver command, a listener is only
+/// notified about the actions a test performs: navigating, going back, executing scripts,
+/// clicking, typing and clearing elements, and resolving element queries.
+///
+/// All methods do nothing by default, so implementations only need to override the hooks
+/// they are interested in. If an action fails, [`WebDriverListener::on_exception()`] is
+/// called instead of the `after_*` hook.
+///
+/// Elements returned from `ElementQuery` carry the description of the query that found
+/// them (see [`WebElement::description()`]), which is useful for step logging.
+///
+/// # Example
+/// ```
+/// # use std::time::Duration;
+/// # use thirtyfour::prelude::*;
+/// # use thirtyfour::common::config::WebDriverConfig;
+/// # use thirtyfour::session::handle::SessionHandle;
+/// # use thirtyfour::session::listener::{WebDriverEvent, WebDriverListener};
+/// /// Logs each step, and slows down clicks so a demo is easier to follow.
+/// #[derive(Debug)]
+/// struct StepLogger;
+///
+/// #[async_trait::async_trait]
+/// impl WebDriverListener for StepLogger {
+///     async fn after_navigate(&self, _handle: &SessionHandle, url: &str) {
+///         println!("navigated to {url}");
+///     }
+///
+///     async fn before_click(&self, element: &WebElement) {
+///         println!("clicking {}", element.description().unwrap_or("element"));
+///         tokio::time::sleep(Duration::from_millis(500)).await;
+///     }
+///
+///     async fn on_exception(&self, event: WebDriverEvent<'_>, error: &WebDriverError) {
+///         println!("{event:?} failed: {error}");
+///     }
+/// }
+///
+/// # fn main() -> WebDriverResult<()> {
+/// let config = WebDriverConfig::builder().listener(StepLogger).build()?;
+/// #     Ok(())
+/// # }
+/// ```
+///
+/// [`WebDriverConfig`]: crate::common::config::WebDriverConfig
+/// [`WebDriverConfigBuilder::listener()`]: crate::common::config::WebDriverConfigBuilder::listener
+/// [`CommandLayer`]: crate::session::layer::CommandLayer
+#[async_trait::async_trait]
+#[allow(unused_variables)]
+pub trait WebDriverListener: Debug + Send + Sync + 'static {
+    /// Called before navigating to the specified URL.
+    async fn before_navigate(&self, handle: &SessionHandle, url: &str) {}
+
+    /// Called after navigating to the specified URL.
+    async fn after_navigate(&self, handle: &SessionHandle, url: &str) {}
+
+    /// Called before going back in the browser history.
+    async fn before_back(&self, handle: &SessionHandle) {}
+
+    /// Called after going back in the browser history.
+    async fn after_back(&self, handle: &SessionHandle) {}
+
+    /// Called before executing the specified script.
+    async fn before_execute(&self, handle: &SessionHandle, script: &str) {}
+
+    /// Called after executing the specified script.
+    async fn after_execute(&self, handle: &SessionHandle, script: &str) {}
+
+    /// Called before clicking the element.
+    async fn before_click(&self, element: &WebElement) {}
+
+    /// Called after clicking the element.
+    async fn after_click(&self, element: &WebElement) {}
+
+    /// Called before typing into the element.
+    ///
+    /// The keys may contain sensitive data such as passwords.
+    async fn before_send_keys(&self, element: &WebElement, keys: &TypingData) {}
+
+    /// Called after typing into the element.
+    async fn after_send_keys(&self, element: &WebElement, keys: &TypingData) {}
+
+    /// Called before clearing the element.
+    async fn before_clear(&self, element: &WebElement) {}
+
+    /// Called after clearing the element.
+    async fn after_clear(&self, element: &WebElement) {}
+
+    /// Called before resolving an `ElementQuery`.
+    ///
+    /// The description is the one set with `ElementQuery::desc()`, or else a summary of
+    /// the query's selectors.
+    async fn before_query(&self, description: &str) {}
+
+    /// Called after resolving an `ElementQuery`, with the elements that were found.
+    async fn after_query(&self, description: &str, elements: &[WebElement]) {}
+
+    /// Called when an action fails, instead of the `after_*` hook.
+    async fn on_exception(&self, event: WebDriverEvent<'_>, error: &WebDriverError) {}
+
+    /// Called after the session was replaced by a new one, following a browser crash.
+    ///
+    /// See [`RecoveryPolicy`](crate::common::config::RecoveryPolicy).
+    async fn on_session_recovered(&self, handle: &SessionHandle, previous_session_id: &SessionId) {}
+}
+
+/// An action that a [`WebDriverListener`] is notified about.
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub enum WebDriverEvent<'a> {
+    /// Navigating to a URL.
+    Navigate {
+        /// The session handle.
+        handle: &'a SessionHandle,
+        /// The URL being navigated to.
+        url: &'a str,
+    },
+    /// Going back in the browser history.
+    Back(&'a SessionHandle),
+    /// Executing a script.
+    Execute {
+        /// The session handle.
+        handle: &'a SessionHandle,
+        /// The script being executed.
+        script: &'a str,
+    },
+    /// Clicking an element.
+    Click(&'a WebElement),
+    /// Typing into an element.
+    SendKeys {
+        /// The element being typed into.
+        element: &'a WebElement,
+        /// The keys being sent.
+        keys: &'a TypingData,
+    },
+    /// Clearing an element.
+    Clear(&'a WebElement),
+    /// Resolving an `ElementQuery` with the specified description.
+    Query(&'a str),
+}
+
+impl WebDriverEvent<'_> {
+    async fn before(self, listener: &dyn WebDriverListener) {
+        match self {
+            Self::Navigate {
+                handle,
+                url,
+            } => listener.before_navigate(handle, url).await,
+            Self::Back(handle) => listener.before_back(handle).await,
+            Self::Execute {
+                handle,
+                script,
+            } => listener.before_execute(handle, script).await,
+            Self::Click(element) => listener.before_click(element).await,
+            Self::SendKeys {
+                element,
+                keys,
+            } => listener.before_send_keys(element, keys).await,
+            Self::Clear(element) => listener.before_clear(element).await,
+            Self::Query(description) => listener.before_query(description).await,
+        }
+    }
+
+    async fn after(self, listener: &dyn WebDriverListener) {
+        match self {
+            Self::Navigate {
+                handle,
+                url,
+            } => listener.after_navigate(handle, url).await,
+            Self::Back(handle) => listener.after_back(handle).await,
+            Self::Execute {
+                handle,
+                script,
+            } => listener.after_execute(handle, script).await,
+            Self::Click(element) => listener.after_click(element).await,
+            Self::SendKeys {
+                element,
+                keys,
+            } => listener.after_send_keys(element, keys).await,
+            Self::Clear(element) => listener.after_clear(element).await,
+            // `ElementQuery` calls `after_query()` itself, with the elements it found.
+            Self::Query(_) => {}
+        }
+    }
+}
+
+/// Run the action, notifying the listeners before and after it.
+pub(crate) async fn notify<T, F>(
+    listeners: &[Arc<dyn WebDriverListener>],
+    event: WebDriverEvent<'_>,
+    action: F,
+) -> WebDriverResult<T>
+where
+    F: Future<Output = WebDriverResult<T>>,
+{
+    for listener in listeners {
+        event.before(&**listener).await;
+    }
+    let result = action.await;
+    match &result {
+        Ok(_) => {
+            for listener in listeners {
+                event.after(&**listener).await;
+            }
+        }
+        Err(e) => notify_exception(listeners, event, e).await,
+    }
+    result
+}
+
+/// Notify the listeners that the action failed.
+pub(crate) async fn notify_exception(
+    listeners: &[Arc<dyn WebDriverListener>],
+    event: WebDriverEvent<'_>,
+    error: &WebDriverError,
+) {
+    for listener in listeners {
+        listener.on_exception(event, error).await;
+    }
+}
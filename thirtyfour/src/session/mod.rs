@@ -4,5 +4,15 @@ pub mod create;
 pub mod handle;
 /// HTTP helpers for WebDriver commands.
 pub mod http;
+/// Middleware for WebDriver commands.
+pub mod layer;
+/// Hooks for high-level browser actions.
+pub mod listener;
+/// Record and replay HTTP clients for running tests without a WebDriver server.
+pub mod recording;
 /// Helper for values returned from scripts.
 pub mod scriptret;
+/// Per-command statistics for a session.
+pub mod stats;
+/// Uploading local files for use by a remote browser.
+pub mod upload;
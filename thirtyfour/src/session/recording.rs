@@ -0,0 +1,519 @@
+use std::collections::{HashMap, VecDeque};
+use std::fmt::{Debug, Display, Formatter};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::sync::Mutex;
+
+use bytes::Bytes;
+use http::{Request, Response};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::{WebDriverError, WebDriverResult};
+use crate::session::http::{Body, HttpClient, SensitiveBody};
+
+const ELEMENT_KEYS: [&str; 2] =
+    ["element-6066-11e4-a52e-4f735466cecf", "shadow-6066-11e4-a52e-4f735466cecf"];
+
+/// A single request/response pair in a cassette.
+///
+/// Session ids, element ids and window handles are replaced with stable placeholders
+/// such as `session-1`, `element-3` or `window-2`, so that recordings are deterministic.
+/// The bodies of sensitive requests (see [`RequestData::sensitive`]) are not recorded.
+///
+/// [`RequestData::sensitive`]: crate::RequestData::sensitive
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Interaction {
+    /// The HTTP method.
+    pub method: String,
+    /// The request path (and query), relative to the server.
+    pub path: String,
+    /// The JSON request body, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<Value>,
+    /// If true, the request body was redacted and is ignored when replaying.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub redacted: bool,
+    /// The HTTP status of the response.
+    pub status: u16,
+    /// The response body, if it was JSON.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response: Option<Value>,
+    /// The response body, if it was not JSON.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response_text: Option<String>,
+}
+
+impl Interaction {
+    fn matches(&self, method: &str, path: &str, body: &Option<Value>) -> bool {
+        self.method == method && self.path == path && (self.redacted || &self.body == body)
+    }
+}
+
+/// Replaces nondeterministic ids with stable placeholders.
+#[derive(Debug, Default)]
+struct Normalizer {
+    ids: HashMap<String, String>,
+    counts: HashMap<&'static str, usize>,
+}
+
+impl Normalizer {
+    fn learn(&mut self, kind: &'static str, id: &str) {
+        if !self.ids.contains_key(id) && !id.is_empty() {
+            let count = self.counts.entry(kind).or_default();
+            *count += 1;
+            self.ids.insert(id.to_string(), format!("{kind}-{count}"));
+        }
+    }
+
+    /// Learn the ids contained in the response to the specified request.
+    fn learn_response(&mut self, method: &str, path: &str, response: &Value) {
+        let value = &response["value"];
+        if method == "POST" && path.ends_with("/session") {
+            let session_id = response["sessionId"].as_str().or(value["sessionId"].as_str());
+            if let Some(session_id) = session_id {
+                self.learn("session", session_id);
+            }
+        }
+
+        if path.ends_with("/window") || path.ends_with("/window/handles") {
+            for handle in value.as_array().map(|x| x.iter()).into_iter().flatten().chain([value]) {
+                if let Some(handle) = handle.as_str() {
+                    self.learn("window", handle);
+                }
+            }
+        } else if path.ends_with("/window/new") {
+            if let Some(handle) = value["handle"].as_str() {
+                self.learn("window", handle);
+            }
+        }
+
+        self.learn_elements(value);
+    }
+
+    fn learn_elements(&mut self, value: &Value) {
+        match value {
+            Value::Array(values) => values.iter().for_each(|x| self.learn_elements(x)),
+            Value::Object(map) => {
+                for (key, value) in map {
+                    match value.as_str() {
+                        Some(id) if ELEMENT_KEYS.contains(&key.as_str()) => {
+                            self.learn("element", id)
+                        }
+                        _ => self.learn_elements(value),
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn path(&self, path: &str) -> String {
+        path.split('/')
+            .map(|x| self.ids.get(x).map_or(x, String::as_str))
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    fn value(&self, value: &mut Value) {
+        match value {
+            Value::String(s) => {
+                if let Some(id) = self.ids.get(s.as_str()) {
+                    *s = id.clone();
+                }
+            }
+            Value::Array(values) => values.iter_mut().for_each(|x| self.value(x)),
+            Value::Object(map) => map.values_mut().for_each(|x| self.value(x)),
+            _ => {}
+        }
+    }
+}
+
+/// The method, path and body of the request, and whether the body is sensitive.
+fn request_parts(request: &Request<Body<'_>>) -> (String, String, Option<Value>, bool) {
+    let path = request.uri().path_and_query().map(|x| x.as_str()).unwrap_or("/").to_string();
+    let body = match request.body() {
+        Body::Empty => None,
+        Body::Json(value) => Some((*value).clone()),
+    };
+    let sensitive = request.extensions().get::<SensitiveBody>().is_some();
+    (request.method().to_string(), path, body, sensitive)
+}
+
+/// The request body, or `None` if it is sensitive. Also returns whether it was redacted.
+fn redact(body: Option<Value>, sensitive: bool) -> (Option<Value>, bool) {
+    match body {
+        Some(_) if sensitive => (None, true),
+        body => (body, false),
+    }
+}
+
+/// Format a request body for display.
+fn display_body(body: &Option<Value>, redacted: bool) -> String {
+    match body {
+        _ if redacted => "<redacted>".to_string(),
+        Some(body) => body.to_string(),
+        None => String::new(),
+    }
+}
+
+/// An [`HttpClient`] that records every request and response to a JSONL cassette.
+///
+/// Each line of the cassette is a serialized [`Interaction`]. Use [`ReplayHttpClient`]
+/// to play the cassette back without a WebDriver server.
+///
+/// # Example
+/// ```no_run
+/// # use thirtyfour::prelude::*;
+/// # use thirtyfour::common::config::WebDriverConfig;
+/// # use thirtyfour::session::recording::RecordingHttpClient;
+/// # use thirtyfour::support::block_on;
+/// #
+/// # fn main() -> WebDriverResult<()> {
+/// #     block_on(async {
+/// let client = RecordingHttpClient::new(reqwest::Client::new(), "login.jsonl")?;
+/// let caps = DesiredCapabilities::chrome();
+/// let config = WebDriverConfig::default();
+/// let driver =
+///     WebDriver::new_with_config_and_client("http://localhost:4444", caps, config, client)
+///         .await?;
+/// driver.goto("https://www.rust-lang.org").await?;
+/// driver.quit().await?;
+/// #         Ok(())
+/// #     })
+/// # }
+/// ```
+pub struct RecordingHttpClient<C> {
+    inner: C,
+    state: Mutex<(Normalizer, Box<dyn Write + Send>)>,
+}
+
+impl<C> Debug for RecordingHttpClient<C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RecordingHttpClient").finish_non_exhaustive()
+    }
+}
+
+impl<C: HttpClient> RecordingHttpClient<C> {
+    /// Record all requests sent via `inner` to a new cassette file at the specified path.
+    pub fn new(inner: C, path: impl AsRef<Path>) -> WebDriverResult<Self> {
+        let file = File::create(path)?;
+        Ok(Self::from_writer(inner, BufWriter::new(file)))
+    }
+
+    /// Record all requests sent via `inner` to the specified writer.
+    pub fn from_writer(inner: C, writer: impl Write + Send + 'static) -> Self {
+        Self {
+            inner,
+            state: Mutex::new((Normalizer::default(), Box::new(writer))),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<C: HttpClient> HttpClient for RecordingHttpClient<C> {
+    async fn send(&self, request: Request<Body<'_>>) -> WebDriverResult<Response<Bytes>> {
+        let (method, path, body, sensitive) = request_parts(&request);
+        let response = self.inner.send(request).await?;
+        let (mut body, redacted) = redact(body, sensitive);
+
+        let (mut response_json, response_text) = match serde_json::from_slice(response.body()) {
+            Ok(value) => (Some(value), None),
+            Err(_) => (None, Some(String::from_utf8_lossy(response.body()).into_owned())),
+        };
+
+        let mut state = self.state.lock().unwrap();
+        let (normalizer, writer) = &mut *state;
+        if let Some(value) = &response_json {
+            normalizer.learn_response(&method, &path, value);
+        }
+        body.iter_mut().chain(response_json.iter_mut()).for_each(|x| normalizer.value(x));
+        let interaction = Interaction {
+            path: normalizer.path(&path),
+            method,
+            body,
+            redacted,
+            status: response.status().as_u16(),
+            response: response_json,
+            response_text,
+        };
+
+        let line = serde_json::to_string(&interaction)?;
+        writeln!(writer, "{line}")?;
+        writer.flush()?;
+        Ok(response)
+    }
+}
+
+/// A request that did not match the next interaction in the cassette.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplayMismatch {
+    /// The index of the expected interaction in the cassette, if any remained.
+    pub index: usize,
+    /// The expected interaction, if any remained.
+    pub expected: Option<Interaction>,
+    /// The HTTP method of the actual request.
+    pub method: String,
+    /// The path of the actual request.
+    pub path: String,
+    /// The body of the actual request.
+    pub body: Option<Value>,
+    /// If true, the body of the actual request was sensitive and has been redacted.
+    pub redacted: bool,
+}
+
+impl Display for ReplayMismatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let body = display_body(&self.body, self.redacted);
+        write!(f, "request {} {} {body} ", self.method, self.path)?;
+        match &self.expected {
+            Some(x) => {
+                let expected = display_body(&x.body, x.redacted);
+                write!(
+                    f,
+                    "does not match interaction {}: {} {} {expected}",
+                    self.index, x.method, x.path
+                )
+            }
+            None => write!(f, "was made after the cassette ended"),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct ReplayState {
+    interactions: VecDeque<Interaction>,
+    served: usize,
+    mismatches: Vec<ReplayMismatch>,
+}
+
+/// An [`HttpClient`] that serves the responses from a cassette recorded by
+/// [`RecordingHttpClient`], in order.
+///
+/// Each request must match the next interaction in the cassette (method, path and body).
+/// A request that does not match fails with [`WebDriverError::RequestFailed`], and is
+/// also recorded so that it can be checked via [`ReplayHttpClient::mismatches()`].
+///
+/// # Example
+/// ```no_run
+/// # use thirtyfour::prelude::*;
+/// # use thirtyfour::common::config::WebDriverConfig;
+/// # use thirtyfour::session::recording::ReplayHttpClient;
+/// # use thirtyfour::support::block_on;
+/// #
+/// # fn main() -> WebDriverResult<()> {
+/// #     block_on(async {
+/// let client = ReplayHttpClient::from_file("login.jsonl")?;
+/// let caps = DesiredCapabilities::chrome();
+/// let config = WebDriverConfig::default();
+/// let driver =
+///     WebDriver::new_with_config_and_client("http://localhost:4444", caps, config, client)
+///         .await?;
+/// driver.goto("https://www.rust-lang.org").await?;
+/// driver.quit().await?;
+/// #         Ok(())
+/// #     })
+/// # }
+/// ```
+#[derive(Debug, Default)]
+pub struct ReplayHttpClient {
+    state: Mutex<ReplayState>,
+}
+
+impl ReplayHttpClient {
+    /// Create a new `ReplayHttpClient` that serves the specified interactions.
+    pub fn new(interactions: impl IntoIterator<Item = Interaction>) -> Self {
+        Self {
+            state: Mutex::new(ReplayState {
+                interactions: interactions.into_iter().collect(),
+                ..Default::default()
+            }),
+        }
+    }
+
+    /// Load the cassette from the specified JSONL file.
+    pub fn from_file(path: impl AsRef<Path>) -> WebDriverResult<Self> {
+        Self::from_jsonl(&std::fs::read_to_string(path)?)
+    }
+
+    /// Load the cassette from a string containing one JSON interaction per line.
+    pub fn from_jsonl(jsonl: &str) -> WebDriverResult<Self> {
+        let interactions = jsonl
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(serde_json::from_str)
+            .collect::<Result<Vec<Interaction>, _>>()?;
+        Ok(Self::new(interactions))
+    }
+
+    /// The number of interactions that have not been served yet.
+    pub fn remaining(&self) -> usize {
+        self.state.lock().unwrap().interactions.len()
+    }
+
+    /// All requests that did not match the cassette so far.
+    pub fn mismatches(&self) -> Vec<ReplayMismatch> {
+        self.state.lock().unwrap().mismatches.clone()
+    }
+}
+
+#[async_trait::async_trait]
+impl HttpClient for ReplayHttpClient {
+    async fn send(&self, request: Request<Body<'_>>) -> WebDriverResult<Response<Bytes>> {
+        let (method, path, body, sensitive) = request_parts(&request);
+        let (body, redacted) = redact(body, sensitive);
+        let mut state = self.state.lock().unwrap();
+        let interaction = match state.interactions.front() {
+            Some(x) if x.matches(&method, &path, &body) => state.interactions.pop_front().unwrap(),
+            expected => {
+                let mismatch = ReplayMismatch {
+                    index: state.served,
+                    expected: expected.cloned(),
+                    method,
+                    path,
+                    body,
+                    redacted,
+                };
+                let message = format!("replay mismatch: {mismatch}");
+                tracing::error!("{message}");
+                state.mismatches.push(mismatch);
+                return Err(WebDriverError::RequestFailed(message));
+            }
+        };
+        state.served += 1;
+
+        let body = match (interaction.response, interaction.response_text) {
+            (Some(value), _) => Bytes::from(value.to_string()),
+            (None, text) => Bytes::from(text.unwrap_or_default()),
+        };
+        Response::builder().status(interaction.status).body(body).map_err(|e| {
+            WebDriverError::UnknownResponse(interaction.status, format!("invalid response: {e}"))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use assert_matches::assert_matches;
+    use serde_json::json;
+
+    use super::*;
+    use crate::common::config::WebDriverConfig;
+    use crate::prelude::*;
+
+    /// A fake driver that hands out random-looking ids.
+    struct FakeDriver;
+
+    #[async_trait::async_trait]
+    impl HttpClient for FakeDriver {
+        async fn send(&self, request: Request<Body<'_>>) -> WebDriverResult<Response<Bytes>> {
+            let path = request.uri().path();
+            let value = if path == "/session" {
+                json!({ "sessionId": "9f8e7d", "capabilities": {} })
+            } else if path.ends_with("/element") {
+                json!({ ELEMENT_KEYS[0]: "f.1A2B.d.3C4D.e.5" })
+            } else if path.ends_with("/text") {
+                assert_eq!(path, "/session/9f8e7d/element/f.1A2B.d.3C4D.e.5/text");
+                json!("Hello")
+            } else {
+                Value::Null
+            };
+            let body = Bytes::from(json!({ "value": value }).to_string());
+            Ok(Response::builder().status(200).body(body).unwrap())
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    async fn find_text(client: impl HttpClient) -> WebDriverResult<String> {
+        let caps = DesiredCapabilities::chrome();
+        let config = WebDriverConfig::default();
+        let driver =
+            WebDriver::new_with_config_and_client("http://localhost:4444", caps, config, client)
+                .await?;
+        let text = driver.find(By::Id("greeting")).await?.text().await?;
+        driver.quit().await?;
+        Ok(text)
+    }
+
+    #[tokio::test]
+    async fn record_and_replay() {
+        let buffer = SharedBuffer::default();
+        let client = RecordingHttpClient::from_writer(FakeDriver, buffer.clone());
+        assert_eq!(find_text(client).await.unwrap(), "Hello");
+
+        let cassette = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        assert!(!cassette.contains("9f8e7d"));
+        assert!(!cassette.contains("1A2B"));
+        assert!(cassette.contains("/session/session-1/element/element-1/text"));
+
+        let replay = Arc::new(ReplayHttpClient::from_jsonl(&cassette).unwrap());
+        assert_eq!(find_text(replay.clone()).await.unwrap(), "Hello");
+        assert_eq!(replay.remaining(), 0);
+        assert!(replay.mismatches().is_empty());
+    }
+
+    #[tokio::test]
+    async fn replay_mismatch() {
+        let buffer = SharedBuffer::default();
+        let client = RecordingHttpClient::from_writer(FakeDriver, buffer.clone());
+        find_text(client).await.unwrap();
+        let cassette = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+
+        let replay = Arc::new(ReplayHttpClient::from_jsonl(&cassette).unwrap());
+        let caps = DesiredCapabilities::chrome();
+        let config = WebDriverConfig::default();
+        let driver = WebDriver::new_with_config_and_client(
+            "http://localhost:4444",
+            caps,
+            config,
+            replay.clone(),
+        )
+        .await
+        .unwrap();
+        let result = driver.find(By::Id("other")).await;
+        assert_matches!(result, Err(WebDriverError::RequestFailed(_)));
+        let mismatches = replay.mismatches();
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(mismatches[0].index, 2);
+        driver.leak().unwrap();
+    }
+
+    #[tokio::test]
+    async fn sensitive_bodies_are_redacted() {
+        fn send_keys(text: &Value) -> Request<Body<'_>> {
+            let mut request =
+                Request::post("/session/9f8e7d/element/1/value").body(Body::Json(text)).unwrap();
+            request.extensions_mut().insert(SensitiveBody);
+            request
+        }
+
+        let secret = json!({ "text": "hunter2" });
+        let buffer = SharedBuffer::default();
+        let client = RecordingHttpClient::from_writer(FakeDriver, buffer.clone());
+        client.send(send_keys(&secret)).await.unwrap();
+        let cassette = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        assert!(!cassette.contains("hunter2"));
+
+        // Redacted requests are matched by method and path only.
+        let replay = ReplayHttpClient::from_jsonl(&cassette).unwrap();
+        replay.send(send_keys(&json!({ "text": "other" }))).await.unwrap();
+        let error = replay.send(send_keys(&secret)).await.unwrap_err();
+        assert!(!error.to_string().contains("hunter2"));
+        assert_eq!(replay.mismatches()[0].body, None);
+    }
+}
@@ -1,6 +1,6 @@
 use crate::error::WebDriverResult;
 use crate::session::handle::SessionHandle;
-use crate::WebElement;
+use crate::{ShadowRoot, WebElement};
 use serde::de::DeserializeOwned;
 use serde_json::Value;
 use std::sync::Arc;
@@ -59,6 +59,14 @@ impl ScriptRet {
         WebElement::from_json(self.value, self.handle)
     }
 
+    /// Get a single ShadowRoot return value.
+    ///
+    /// Your script must return only a single shadow root for this to work,
+    /// e.g. `return arguments[0].shadowRoot;`.
+    pub fn shadow_root(self) -> WebDriverResult<ShadowRoot> {
+        ShadowRoot::from_json(self.value, self.handle)
+    }
+
     /// Get a single WebElement return value.
     #[deprecated(since = "0.30.0", note = "This method has been renamed to element()")]
     pub fn get_element(self) -> WebDriverResult<WebElement> {
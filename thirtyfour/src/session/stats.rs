@@ -0,0 +1,183 @@
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// The upper bounds of the latency histogram buckets, in milliseconds.
+///
+/// Commands slower than the last bound are counted in an extra overflow bucket.
+const BUCKET_BOUNDS_MS: [u64; 12] = [1, 2, 5, 10, 20, 50, 100, 200, 500, 1000, 2000, 5000];
+
+/// Counts and latencies of the commands sent by a session, grouped by command name.
+///
+/// The stats are shared by all clones of a `WebDriver`, and by all elements found with it.
+///
+/// # Example
+/// ```no_run
+/// # use thirtyfour::prelude::*;
+/// # use thirtyfour::support::block_on;
+/// #
+/// # fn main() -> WebDriverResult<()> {
+/// #     block_on(async {
+/// #         let caps = DesiredCapabilities::chrome();
+/// #         let driver = WebDriver::new("http://localhost:4444", caps).await?;
+/// driver.goto("https://www.rust-lang.org/").await?;
+/// driver.find(By::Css("nav a")).await?.click().await?;
+///
+/// for (command, stats) in driver.command_stats().snapshot() {
+///     println!("{command}: {} calls, mean {:?}", stats.count, stats.mean());
+/// }
+/// #         driver.quit().await?;
+/// #         Ok(())
+/// #     })
+/// # }
+/// ```
+#[derive(Debug, Default)]
+pub struct CommandStats {
+    commands: Mutex<BTreeMap<String, CommandStat>>,
+}
+
+impl CommandStats {
+    /// Record a command that took `elapsed` to complete.
+    pub(crate) fn record(&self, command: &str, elapsed: Duration, success: bool) {
+        let mut commands = self.commands.lock().unwrap_or_else(|e| e.into_inner());
+        let stat = match commands.get_mut(command) {
+            Some(stat) => stat,
+            None => commands.entry(command.to_string()).or_default(),
+        };
+        stat.count += 1;
+        if !success {
+            stat.errors += 1;
+        }
+        stat.total += elapsed;
+        stat.max = stat.max.max(elapsed);
+        stat.histogram.record(elapsed);
+    }
+
+    /// Get the stats for the command with the specified name, e.g. `FindElement`.
+    pub fn get(&self, command: &str) -> Option<CommandStat> {
+        self.commands.lock().unwrap_or_else(|e| e.into_inner()).get(command).cloned()
+    }
+
+    /// Get the stats for all commands sent so far, keyed by command name.
+    pub fn snapshot(&self) -> BTreeMap<String, CommandStat> {
+        self.commands.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// The total number of commands sent so far.
+    pub fn total(&self) -> u64 {
+        self.commands.lock().unwrap_or_else(|e| e.into_inner()).values().map(|x| x.count).sum()
+    }
+
+    /// Clear all stats.
+    pub fn reset(&self) {
+        self.commands.lock().unwrap_or_else(|e| e.into_inner()).clear();
+    }
+}
+
+/// The stats for a single command.
+#[derive(Debug, Clone, Default)]
+pub struct CommandStat {
+    /// The number of times the command was sent.
+    pub count: u64,
+    /// The number of times the command returned an error.
+    pub errors: u64,
+    /// The total time spent on the command.
+    pub total: Duration,
+    /// The longest time the command took.
+    pub max: Duration,
+    /// The distribution of the time the command took.
+    pub histogram: LatencyHistogram,
+}
+
+impl CommandStat {
+    /// The average time the command took.
+    pub fn mean(&self) -> Duration {
+        match u32::try_from(self.count) {
+            Ok(0) => Duration::ZERO,
+            Ok(count) => self.total / count,
+            Err(_) => Duration::from_secs_f64(self.total.as_secs_f64() / self.count as f64),
+        }
+    }
+}
+
+/// A histogram of command latencies, with fixed buckets from 1ms to 5s.
+#[derive(Debug, Clone, Default)]
+pub struct LatencyHistogram {
+    counts: [u64; BUCKET_BOUNDS_MS.len() + 1],
+}
+
+impl LatencyHistogram {
+    fn record(&mut self, elapsed: Duration) {
+        let idx = BUCKET_BOUNDS_MS
+            .iter()
+            .position(|&x| elapsed <= Duration::from_millis(x))
+            .unwrap_or(BUCKET_BOUNDS_MS.len());
+        self.counts[idx] += 1;
+    }
+
+    /// The buckets of the histogram, as `(upper_bound, count)` pairs in ascending order.
+    ///
+    /// The upper bound of the last bucket is `None`, for commands slower than 5s.
+    pub fn buckets(&self) -> impl Iterator<Item = (Option<Duration>, u64)> + '_ {
+        let bounds = BUCKET_BOUNDS_MS.iter().map(|&x| Some(Duration::from_millis(x)));
+        bounds.chain([None]).zip(self.counts.iter().copied())
+    }
+
+    /// The upper bound of the bucket containing the specified percentile (from 0.0 to 1.0).
+    ///
+    /// Returns `None` if no commands were recorded, or if the percentile falls in the
+    /// overflow bucket.
+    pub fn percentile(&self, percentile: f64) -> Option<Duration> {
+        let total: u64 = self.counts.iter().sum();
+        if total == 0 {
+            return None;
+        }
+        let target = ((total as f64 * percentile.clamp(0.0, 1.0)).ceil() as u64).max(1);
+        let mut seen = 0;
+        for (bound, count) in self.buckets() {
+            seen += count;
+            if seen >= target {
+                return bound;
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn record_and_summarize() {
+        let stats = CommandStats::default();
+        for ms in [1, 3, 3, 40, 7000] {
+            stats.record("FindElement", Duration::from_millis(ms), ms < 5000);
+        }
+        stats.record("ElementClick", Duration::from_millis(15), true);
+        assert_eq!(stats.total(), 6);
+
+        let find = stats.get("FindElement").unwrap();
+        assert_eq!(find.count, 5);
+        assert_eq!(find.errors, 1);
+        assert_eq!(find.max, Duration::from_millis(7000));
+        assert_eq!(find.mean(), Duration::from_micros(1_409_400));
+        assert_eq!(find.histogram.percentile(0.5), Some(Duration::from_millis(5)));
+        assert_eq!(find.histogram.percentile(0.8), Some(Duration::from_millis(50)));
+        assert_eq!(find.histogram.percentile(1.0), None);
+        let buckets: Vec<_> = find.histogram.buckets().filter(|x| x.1 > 0).collect();
+        assert_eq!(
+            buckets,
+            [
+                (Some(Duration::from_millis(1)), 1),
+                (Some(Duration::from_millis(5)), 2),
+                (Some(Duration::from_millis(50)), 1),
+                (None, 1)
+            ]
+        );
+
+        assert_eq!(stats.snapshot().keys().collect::<Vec<_>>(), ["ElementClick", "FindElement"]);
+        stats.reset();
+        assert!(stats.get("FindElement").is_none());
+    }
+}
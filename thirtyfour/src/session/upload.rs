@@ -0,0 +1,105 @@
+use std::fmt::Debug;
+use std::io::{Cursor, Write};
+use std::net::IpAddr;
+use std::path::Path;
+
+use url::{Host, Url};
+use zip::write::SimpleFileOptions;
+
+use crate::error::{WebDriverError, WebDriverResult};
+use crate::support;
+
+/// Decides whether files must be uploaded to the WebDriver server before the browser
+/// can use them, e.g. in `WebElement::upload_file()`.
+///
+/// Set the detector with `WebDriverConfigBuilder::file_detector()`.
+pub trait FileDetector: Debug + Send + Sync {
+    /// Return true if the browser controlled via `server_url` cannot read local files,
+    /// so they must be uploaded first.
+    fn is_remote(&self, server_url: &Url) -> bool;
+}
+
+/// Uploads files unless the WebDriver server is on this machine.
+///
+/// The server is considered local if its URL is `localhost` or a loopback address.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct HostFileDetector;
+
+impl FileDetector for HostFileDetector {
+    fn is_remote(&self, server_url: &Url) -> bool {
+        match server_url.host() {
+            Some(Host::Domain(domain)) => !domain.eq_ignore_ascii_case("localhost"),
+            Some(Host::Ipv4(ip)) => !IpAddr::V4(ip).is_loopback(),
+            Some(Host::Ipv6(ip)) => !IpAddr::V6(ip).is_loopback(),
+            None => true,
+        }
+    }
+}
+
+/// Always uploads files, e.g. for a WebDriver server on `localhost` that forwards the
+/// session to a browser in a container.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct RemoteFileDetector;
+
+impl FileDetector for RemoteFileDetector {
+    fn is_remote(&self, _server_url: &Url) -> bool {
+        true
+    }
+}
+
+/// Never uploads files, e.g. for a remote WebDriver server that shares a filesystem
+/// with this machine.
+///
+/// This is the default `FileDetector`.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct LocalFileDetector;
+
+impl FileDetector for LocalFileDetector {
+    fn is_remote(&self, _server_url: &Url) -> bool {
+        false
+    }
+}
+
+/// Read the file at the specified path into a zip archive containing just that file,
+/// as expected by Selenium's upload endpoint.
+pub(crate) async fn zip_file(path: &Path) -> WebDriverResult<Vec<u8>> {
+    let name = match path.file_name() {
+        Some(name) => name.to_string_lossy().into_owned(),
+        None => {
+            return Err(WebDriverError::ParseError(format!(
+                "cannot upload {}: not a file",
+                path.display()
+            )))
+        }
+    };
+    let contents = support::read_file(path).await?;
+
+    let mut archive = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    archive.start_file(name, SimpleFileOptions::default())?;
+    archive.write_all(&contents)?;
+    Ok(archive.finish()?.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn host_detector() {
+        let is_remote = |url: &str| HostFileDetector.is_remote(&Url::parse(url).unwrap());
+        assert!(!is_remote("http://localhost:4444"));
+        assert!(!is_remote("http://127.0.0.1:9515"));
+        assert!(!is_remote("http://[::1]:4444/wd/hub"));
+        assert!(is_remote("http://selenium-hub:4444"));
+        assert!(is_remote("https://10.0.0.2:4444"));
+    }
+
+    #[tokio::test]
+    async fn zip_and_unzip() {
+        let path = std::env::temp_dir().join(format!("upload-{}.txt", std::process::id()));
+        std::fs::write(&path, "hello").unwrap();
+        let archive = zip_file(&path).await.unwrap();
+        assert_eq!(crate::extensions::grid::unzip_file(&archive).unwrap(), b"hello");
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,168 @@
+use serde_json::Value;
+use std::fmt;
+use std::sync::Arc;
+
+use crate::common::command::Command;
+use crate::error::{WebDriverError, WebDriverResult};
+use crate::locator;
+use crate::session::handle::SessionHandle;
+use crate::session::http::CmdResponse;
+use crate::{By, ElementRef, ShadowRootId, WebElement};
+
+/// The shadow root of an element, as returned by [`WebElement::get_shadow_root()`].
+///
+/// Elements inside the shadow DOM cannot be found from the driver or from the host
+/// element. Use [`ShadowRoot::find()`], [`ShadowRoot::find_all()`] or
+/// [`ElementQueryable::query()`] to search within the shadow root instead.
+///
+/// Note that most WebDriver implementations only support CSS selectors within a
+/// shadow root.
+///
+/// # Example:
+/// ```no_run
+/// # use thirtyfour::prelude::*;
+/// # use thirtyfour::support::block_on;
+/// #
+/// # fn main() -> WebDriverResult<()> {
+/// #     block_on(async {
+/// #         let caps = DesiredCapabilities::chrome();
+/// #         let driver = WebDriver::new("http://localhost:4444", caps).await?;
+/// let host = driver.find(By::Id("shadow-host")).await?;
+/// let root = host.get_shadow_root().await?;
+/// let button = root.find(By::Css("button")).await?;
+/// button.click().await?;
+/// #         driver.quit().await?;
+/// #         Ok(())
+/// #     })
+/// # }
+/// ```
+///
+/// [`ElementQueryable::query()`]: crate::extensions::query::ElementQueryable::query
+#[derive(Clone)]
+pub struct ShadowRoot {
+    /// The shadow root id.
+    shadow_root_id: ShadowRootId,
+    /// The underlying session handle.
+    pub(crate) handle: Arc<SessionHandle>,
+    /// The generation of the session that this shadow root was found in.
+    generation: u64,
+}
+
+impl fmt::Debug for ShadowRoot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ShadowRoot").field("shadow_root", &self.shadow_root_id).finish()
+    }
+}
+
+impl PartialEq for ShadowRoot {
+    fn eq(&self, other: &Self) -> bool {
+        self.shadow_root_id == other.shadow_root_id
+    }
+}
+
+impl Eq for ShadowRoot {}
+
+impl ShadowRoot {
+    /// Create a new ShadowRoot struct.
+    pub(crate) fn new(shadow_root_id: ShadowRootId, handle: Arc<SessionHandle>) -> Self {
+        Self {
+            shadow_root_id,
+            generation: handle.generation(),
+            handle,
+        }
+    }
+
+    /// Construct a `ShadowRoot` from a JSON response and a session handle.
+    ///
+    /// The `value` argument should be a JSON object containing the property
+    /// `shadow-6066-11e4-a52e-4f735466cecf` whose value is the shadow root id
+    /// assigned by the WebDriver.
+    pub fn from_json(value: Value, handle: Arc<SessionHandle>) -> WebDriverResult<Self> {
+        match serde_json::from_value(value)? {
+            ElementRef::ShadowElement {
+                id,
+            } => Ok(Self::new(ShadowRootId::from(id), handle)),
+            ElementRef::Element {
+                id,
+            } => Err(WebDriverError::Json(format!("expected a shadow root, got element {id}"))),
+        }
+    }
+
+    /// Serialize this `ShadowRoot` to JSON.
+    ///
+    /// This is useful for supplying a shadow root as an argument to a script.
+    pub fn to_json(&self) -> WebDriverResult<Value> {
+        self.check_session()?;
+        Ok(serde_json::to_value(ElementRef::ShadowElement {
+            id: self.shadow_root_id.to_string(),
+        })?)
+    }
+
+    /// Get the internal id for this shadow root.
+    pub fn shadow_root_id(&self) -> ShadowRootId {
+        self.shadow_root_id.clone()
+    }
+
+    /// Return an error if the session this shadow root was found in has since been recovered.
+    fn check_session(&self) -> WebDriverResult<()> {
+        if self.generation == self.handle.generation() {
+            return Ok(());
+        }
+        Err(WebDriverError::StaleSession(format!(
+            "shadow root {} was found before the browser crashed, and must be found again in \
+             session {}",
+            self.shadow_root_id,
+            self.handle.current_session_id()
+        )))
+    }
+
+    /// Send the specified command, if this shadow root's session is still running.
+    async fn cmd(&self, command: Command) -> WebDriverResult<CmdResponse> {
+        self.check_session()?;
+        self.handle.cmd(command).await
+    }
+
+    /// Search for an element within this shadow root using the specified selector.
+    ///
+    /// Returns [`WebDriverError::DetachedShadowRoot`] if the host element has since been
+    /// removed from the document.
+    ///
+    /// **NOTE**: For more powerful element queries including polling and filters, see the
+    /// [`ShadowRoot::query`] method instead.
+    ///
+    /// [`ShadowRoot::query`]: crate::extensions::query::ElementQueryable::query
+    pub async fn find(&self, by: By) -> WebDriverResult<WebElement> {
+        if by.is_refined() {
+            return locator::first(self.find_all(by.clone()).await?, &by);
+        }
+        let r = self
+            .cmd(Command::FindElementFromShadowRoot(self.shadow_root_id.clone(), by.candidates()))
+            .await?;
+        r.element(self.handle.clone())
+    }
+
+    /// Search for all elements within this shadow root that match the specified selector.
+    ///
+    /// **NOTE**: For more powerful element queries including polling and filters, see the
+    /// [`ShadowRoot::query`] method instead.
+    ///
+    /// [`ShadowRoot::query`]: crate::extensions::query::ElementQueryable::query
+    pub async fn find_all(&self, by: By) -> WebDriverResult<Vec<WebElement>> {
+        let refine = by.refine();
+        let elements = match by.script() {
+            Some((script, args)) => {
+                locator::find_all_js(&self.handle, script, args, self.to_json()?).await?
+            }
+            None => self.find_all_shallow(by).await?,
+        };
+        refine.apply(elements).await
+    }
+
+    /// Search for all elements within this shadow root, without refining them.
+    pub(crate) async fn find_all_shallow(&self, by: By) -> WebDriverResult<Vec<WebElement>> {
+        let r = self
+            .cmd(Command::FindElementsFromShadowRoot(self.shadow_root_id.clone(), by.candidates()))
+            .await?;
+        r.elements(self.handle.clone())
+    }
+}
@@ -0,0 +1,447 @@
+//! A subset of CSS selectors, enough for the selectors generated by `thirtyfour` and most
+//! hand-written page objects.
+//!
+//! Supported: type, universal, `#id`, `.class`, attribute selectors (`[a]`, `=`, `~=`, `|=`,
+//! `^=`, `$=`, `*=`), the descendant, child and sibling combinators, selector lists, and the
+//! `:nth-child()`, `:nth-of-type()`, `:first-child`, `:last-child`, `:first-of-type`,
+//! `:last-of-type`, `:checked`, `:disabled`, `:enabled` and `:not()` pseudo-classes.
+
+use std::iter::Peekable;
+use std::str::Chars;
+
+use super::dom::Dom;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Combinator {
+    Descendant,
+    Child,
+    Adjacent,
+    Sibling,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum AttrOp {
+    Equals,
+    Includes,
+    DashMatch,
+    Prefix,
+    Suffix,
+    Substring,
+}
+
+#[derive(Debug, Clone)]
+enum Filter {
+    Id(String),
+    Class(String),
+    Attr(String, Option<(AttrOp, String)>),
+    /// `an+b` within all element siblings (`of_type = false`) or siblings of the same type.
+    Nth {
+        a: i64,
+        b: i64,
+        of_type: bool,
+        from_end: bool,
+    },
+    Checked,
+    Disabled,
+    Enabled,
+    Not(Vec<Compound>),
+}
+
+#[derive(Debug, Clone, Default)]
+struct Compound {
+    tag: Option<String>,
+    filters: Vec<Filter>,
+}
+
+/// A complex selector, stored as compounds with the combinator preceding each one.
+type Complex = Vec<(Combinator, Compound)>;
+
+/// A parsed selector list.
+#[derive(Debug)]
+pub(super) struct Selector(Vec<Complex>);
+
+impl Selector {
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let mut parser = Parser {
+            chars: s.chars().peekable(),
+        };
+        let list = parser.list()?;
+        match parser.chars.next() {
+            None => Ok(Self(list)),
+            Some(c) => Err(format!("unexpected '{c}' in selector '{s}'")),
+        }
+    }
+
+    pub fn matches(&self, dom: &Dom, idx: usize) -> bool {
+        idx != 0 && self.0.iter().any(|x| matches_complex(dom, idx, x))
+    }
+}
+
+fn matches_complex(dom: &Dom, idx: usize, parts: &[(Combinator, Compound)]) -> bool {
+    let Some(((combinator, compound), rest)) = parts.split_last() else {
+        return true;
+    };
+    if !matches_compound(dom, idx, compound) {
+        return false;
+    }
+    if rest.is_empty() {
+        return true;
+    }
+
+    let siblings = dom.siblings(idx);
+    let position = siblings.iter().position(|&x| x == idx).unwrap_or_default();
+    match combinator {
+        Combinator::Descendant => dom.ancestors(idx).any(|x| matches_complex(dom, x, rest)),
+        Combinator::Child => dom.parent_element(idx).is_some_and(|x| matches_complex(dom, x, rest)),
+        Combinator::Adjacent => {
+            position.checked_sub(1).is_some_and(|prev| matches_complex(dom, siblings[prev], rest))
+        }
+        Combinator::Sibling => siblings[..position].iter().any(|&x| matches_complex(dom, x, rest)),
+    }
+}
+
+fn matches_compound(dom: &Dom, idx: usize, compound: &Compound) -> bool {
+    let node = &dom.nodes[idx];
+    if compound.tag.as_ref().is_some_and(|tag| *tag != node.tag) {
+        return false;
+    }
+
+    compound.filters.iter().all(|filter| match filter {
+        Filter::Id(id) => dom.attr(idx, "id") == Some(id.as_str()),
+        Filter::Class(class) => dom.has_class(idx, class),
+        Filter::Attr(name, None) => dom.attr(idx, name).is_some(),
+        Filter::Attr(name, Some((op, value))) => {
+            dom.attr(idx, name).is_some_and(|actual| match op {
+                AttrOp::Equals => actual == value,
+                AttrOp::Includes => actual.split_whitespace().any(|x| x == value),
+                AttrOp::DashMatch => actual == value || actual.starts_with(&format!("{value}-")),
+                AttrOp::Prefix => !value.is_empty() && actual.starts_with(value.as_str()),
+                AttrOp::Suffix => !value.is_empty() && actual.ends_with(value.as_str()),
+                AttrOp::Substring => !value.is_empty() && actual.contains(value.as_str()),
+            })
+        }
+        Filter::Nth {
+            a,
+            b,
+            of_type,
+            from_end,
+        } => {
+            let siblings = dom
+                .siblings(idx)
+                .iter()
+                .filter(|&&x| !of_type || dom.nodes[x].tag == node.tag)
+                .collect::<Vec<_>>();
+            let mut position = siblings.iter().position(|&&x| x == idx).unwrap_or_default();
+            if *from_end {
+                position = siblings.len() - 1 - position;
+            }
+            nth_matches(*a, *b, position as i64 + 1)
+        }
+        Filter::Checked => dom.is_selected(idx),
+        Filter::Disabled => !dom.is_enabled(idx),
+        Filter::Enabled => dom.is_enabled(idx),
+        Filter::Not(list) => !list.iter().any(|x| matches_compound(dom, idx, x)),
+    })
+}
+
+fn nth_matches(a: i64, b: i64, position: i64) -> bool {
+    match a {
+        0 => position == b,
+        _ => (position - b) % a == 0 && (position - b) / a >= 0,
+    }
+}
+
+struct Parser<'a> {
+    chars: Peekable<Chars<'a>>,
+}
+
+impl Parser<'_> {
+    fn skip_whitespace(&mut self) -> bool {
+        let mut skipped = false;
+        while self.chars.next_if(|c| c.is_whitespace()).is_some() {
+            skipped = true;
+        }
+        skipped
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), String> {
+        match self.chars.next() {
+            Some(c) if c == expected => Ok(()),
+            Some(c) => Err(format!("expected '{expected}' but found '{c}'")),
+            None => Err(format!("expected '{expected}' but found end of selector")),
+        }
+    }
+
+    fn list(&mut self) -> Result<Vec<Complex>, String> {
+        let mut list = vec![self.complex()?];
+        while self.chars.next_if_eq(&',').is_some() {
+            list.push(self.complex()?);
+        }
+        Ok(list)
+    }
+
+    fn complex(&mut self) -> Result<Complex, String> {
+        self.skip_whitespace();
+        let mut parts = vec![(Combinator::Descendant, self.compound()?)];
+        loop {
+            let whitespace = self.skip_whitespace();
+            let combinator = match self.chars.peek() {
+                Some('>') => Combinator::Child,
+                Some('+') => Combinator::Adjacent,
+                Some('~') => Combinator::Sibling,
+                Some(',') | Some(')') | None => break,
+                Some(_) if whitespace => {
+                    parts.push((Combinator::Descendant, self.compound()?));
+                    continue;
+                }
+                Some(c) => return Err(format!("unexpected '{c}'")),
+            };
+            self.chars.next();
+            self.skip_whitespace();
+            parts.push((combinator, self.compound()?));
+        }
+        Ok(parts)
+    }
+
+    fn compound(&mut self) -> Result<Compound, String> {
+        let mut compound = Compound::default();
+        let mut universal = false;
+        match self.chars.peek() {
+            Some('*') => {
+                self.chars.next();
+                universal = true;
+            }
+            Some(&c) if is_ident_char(c) || c == '\\' => {
+                compound.tag = Some(self.ident()?.to_lowercase());
+            }
+            _ => {}
+        }
+
+        loop {
+            let filter = match self.chars.peek() {
+                Some('#') => {
+                    self.chars.next();
+                    Filter::Id(self.ident()?)
+                }
+                Some('.') => {
+                    self.chars.next();
+                    Filter::Class(self.ident()?)
+                }
+                Some('[') => {
+                    self.chars.next();
+                    self.attribute()?
+                }
+                Some(':') => {
+                    self.chars.next();
+                    self.pseudo()?
+                }
+                _ => break,
+            };
+            compound.filters.push(filter);
+        }
+
+        if !universal && compound.tag.is_none() && compound.filters.is_empty() {
+            return match self.chars.peek() {
+                Some(c) => Err(format!("unexpected '{c}'")),
+                None => Err("unexpected end of selector".to_string()),
+            };
+        }
+        Ok(compound)
+    }
+
+    fn ident(&mut self) -> Result<String, String> {
+        let mut ident = String::new();
+        while let Some(&c) = self.chars.peek() {
+            if c == '\\' {
+                self.chars.next();
+                ident.extend(self.escape());
+            } else if is_ident_char(c) {
+                ident.push(c);
+                self.chars.next();
+            } else {
+                break;
+            }
+        }
+        match ident.is_empty() {
+            true => Err("expected identifier".to_string()),
+            false => Ok(ident),
+        }
+    }
+
+    /// The character escaped by a backslash, which is either up to 6 hex digits followed by
+    /// optional whitespace, or any other character.
+    fn escape(&mut self) -> Option<char> {
+        let mut hex = String::new();
+        while hex.len() < 6 {
+            match self.chars.next_if(|c| c.is_ascii_hexdigit()) {
+                Some(c) => hex.push(c),
+                None => break,
+            }
+        }
+        if hex.is_empty() {
+            return self.chars.next();
+        }
+        self.chars.next_if(|c| c.is_whitespace());
+        let c = u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32);
+        Some(c.unwrap_or(char::REPLACEMENT_CHARACTER))
+    }
+
+    fn string(&mut self, quote: char) -> Result<String, String> {
+        let mut value = String::new();
+        loop {
+            match self.chars.next() {
+                Some('\\') => value.extend(self.escape()),
+                Some(c) if c == quote => return Ok(value),
+                Some(c) => value.push(c),
+                None => return Err("unterminated string".to_string()),
+            }
+        }
+    }
+
+    fn attribute(&mut self) -> Result<Filter, String> {
+        self.skip_whitespace();
+        let name = self.ident()?.to_lowercase();
+        self.skip_whitespace();
+        let op = match self.chars.next() {
+            Some(']') => return Ok(Filter::Attr(name, None)),
+            Some('=') => AttrOp::Equals,
+            Some(c) => {
+                let op = match c {
+                    '~' => AttrOp::Includes,
+                    '|' => AttrOp::DashMatch,
+                    '^' => AttrOp::Prefix,
+                    '$' => AttrOp::Suffix,
+                    '*' => AttrOp::Substring,
+                    _ => return Err(format!("unexpected '{c}' in attribute selector")),
+                };
+                self.expect('=')?;
+                op
+            }
+            None => return Err("unterminated attribute selector".to_string()),
+        };
+        self.skip_whitespace();
+        let value = match self.chars.peek() {
+            Some(&q) if q == '"' || q == '\'' => {
+                self.chars.next();
+                self.string(q)?
+            }
+            _ => self.ident()?,
+        };
+        self.skip_whitespace();
+        self.expect(']')?;
+        Ok(Filter::Attr(name, Some((op, value))))
+    }
+
+    fn pseudo(&mut self) -> Result<Filter, String> {
+        let name = self.ident()?.to_lowercase();
+        let nth = |of_type, from_end, (a, b)| Filter::Nth {
+            a,
+            b,
+            of_type,
+            from_end,
+        };
+        let filter = match name.as_str() {
+            "first-child" => nth(false, false, (0, 1)),
+            "last-child" => nth(false, true, (0, 1)),
+            "first-of-type" => nth(true, false, (0, 1)),
+            "last-of-type" => nth(true, true, (0, 1)),
+            "checked" => Filter::Checked,
+            "disabled" => Filter::Disabled,
+            "enabled" => Filter::Enabled,
+            "nth-child" => nth(false, false, self.nth_argument()?),
+            "nth-last-child" => nth(false, true, self.nth_argument()?),
+            "nth-of-type" => nth(true, false, self.nth_argument()?),
+            "nth-last-of-type" => nth(true, true, self.nth_argument()?),
+            "not" => {
+                self.expect('(')?;
+                let mut list = Vec::new();
+                loop {
+                    self.skip_whitespace();
+                    list.push(self.compound()?);
+                    self.skip_whitespace();
+                    match self.chars.next() {
+                        Some(',') => continue,
+                        Some(')') => break,
+                        _ => return Err("unterminated :not()".to_string()),
+                    }
+                }
+                Filter::Not(list)
+            }
+            _ => return Err(format!("unsupported pseudo-class ':{name}'")),
+        };
+        Ok(filter)
+    }
+
+    /// Parse an `an+b` argument, including the parentheses.
+    fn nth_argument(&mut self) -> Result<(i64, i64), String> {
+        self.expect('(')?;
+        let mut arg = String::new();
+        loop {
+            match self.chars.next() {
+                Some(')') => break,
+                Some(c) if !c.is_whitespace() => arg.push(c.to_ascii_lowercase()),
+                Some(_) => {}
+                None => return Err("unterminated nth argument".to_string()),
+            }
+        }
+
+        let invalid = || format!("invalid nth argument '{arg}'");
+        let number = |s: &str| s.parse::<i64>().map_err(|_| invalid());
+        match arg.as_str() {
+            "odd" => Ok((2, 1)),
+            "even" => Ok((2, 0)),
+            _ => match arg.split_once('n') {
+                Some((a, b)) => {
+                    let a = match a {
+                        "" | "+" => 1,
+                        "-" => -1,
+                        a => number(a)?,
+                    };
+                    let b = match b {
+                        "" => 0,
+                        b => number(b.strip_prefix('+').unwrap_or(b))?,
+                    };
+                    Ok((a, b))
+                }
+                None => Ok((0, number(&arg)?)),
+            },
+        }
+    }
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '-' || c == '_' || !c.is_ascii()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::MockElement;
+
+    fn select(dom: &Dom, css: &str) -> Vec<String> {
+        let selector = Selector::parse(css).unwrap();
+        let found = dom.descendants(0).into_iter().filter(|&x| selector.matches(dom, x));
+        found.map(|x| dom.string_value(x)).collect()
+    }
+
+    #[test]
+    fn selectors() {
+        let dom = Dom::new(&MockElement::new("ul").id("list").children([
+            MockElement::new("li").class("a").attr("data-x", "one two").text("1"),
+            MockElement::new("li").class("a").class("b").text("2"),
+            MockElement::new("li").attr("data-x", "o\"ne").text("3"),
+        ]));
+
+        assert_eq!(select(&dom, "li"), ["1", "2", "3"]);
+        assert_eq!(select(&dom, "#list > .a.b"), ["2"]);
+        assert_eq!(select(&dom, "ul li:nth-of-type(2), li:last-child"), ["2", "3"]);
+        assert_eq!(select(&dom, "li:nth-child(odd)"), ["1", "3"]);
+        assert_eq!(select(&dom, "[data-x~=two]"), ["1"]);
+        assert_eq!(select(&dom, r#"li[data-x="o\"ne"]"#), ["3"]);
+        assert_eq!(select(&dom, r#"#\6c ist > li.\62"#), ["2"]);
+        assert_eq!(select(&dom, "li.a + li"), ["2", "3"]);
+        assert_eq!(select(&dom, "li:not(.b, [data-x])"), Vec::<String>::new());
+        assert!(Selector::parse("li[").is_err());
+        assert!(Selector::parse("li:hover").is_err());
+    }
+}
@@ -0,0 +1,706 @@
+use serde_json::{json, Value};
+
+use crate::ElementRect;
+
+/// Tags whose text is rendered on its own line.
+const BLOCK_TAGS: &[&str] = &[
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "body",
+    "dd",
+    "div",
+    "dl",
+    "dt",
+    "fieldset",
+    "figure",
+    "footer",
+    "form",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hr",
+    "html",
+    "li",
+    "main",
+    "nav",
+    "ol",
+    "option",
+    "p",
+    "pre",
+    "section",
+    "table",
+    "tr",
+    "ul",
+];
+
+/// Tags that are never rendered.
+const HIDDEN_TAGS: &[&str] =
+    &["head", "link", "meta", "noscript", "script", "style", "template", "title"];
+
+/// Tags that have no closing tag when serialized.
+const VOID_TAGS: &[&str] = &["br", "hr", "img", "input", "link", "meta"];
+
+/// Tags that can be disabled.
+const FORM_TAGS: &[&str] =
+    &["button", "fieldset", "input", "optgroup", "option", "select", "textarea"];
+
+/// Attributes that are either present (`"true"`) or absent (`null`).
+const BOOLEAN_ATTRIBUTES: &[&str] = &[
+    "async",
+    "autofocus",
+    "checked",
+    "defer",
+    "disabled",
+    "hidden",
+    "multiple",
+    "readonly",
+    "required",
+    "selected",
+];
+
+/// An element in the in-memory DOM served by a [`MockDriver`].
+///
+/// # Example
+/// ```
+/// use thirtyfour::testing::MockElement;
+///
+/// let page = MockElement::page(
+///     "Login",
+///     [MockElement::new("form").id("login").children([
+///         MockElement::new("input").attr("name", "username"),
+///         MockElement::new("button").class("primary").text("Sign in"),
+///     ])],
+/// );
+/// ```
+///
+/// [`MockDriver`]: super::MockDriver
+#[derive(Debug, Clone)]
+pub struct MockElement {
+    tag: String,
+    attributes: Vec<(String, String)>,
+    text: String,
+    children: Vec<MockElement>,
+    shadow: Option<Vec<MockElement>>,
+    displayed: bool,
+    rect: Option<ElementRect>,
+}
+
+impl MockElement {
+    /// Create a new element with the specified tag name.
+    pub fn new(tag: impl Into<String>) -> Self {
+        Self {
+            tag: tag.into().to_lowercase(),
+            attributes: Vec::new(),
+            text: String::new(),
+            children: Vec::new(),
+            shadow: None,
+            displayed: true,
+            rect: None,
+        }
+    }
+
+    /// Create an `<html>` document with the specified title and body content.
+    pub fn page(title: impl Into<String>, body: impl IntoIterator<Item = MockElement>) -> Self {
+        MockElement::new("html")
+            .child(MockElement::new("head").child(MockElement::new("title").text(title)))
+            .child(MockElement::new("body").children(body))
+    }
+
+    /// Set an attribute, replacing any existing value.
+    pub fn attr(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        let name = name.into().to_lowercase();
+        let value = value.into();
+        match self.attributes.iter_mut().find(|(k, _)| *k == name) {
+            Some((_, v)) => *v = value,
+            None => self.attributes.push((name, value)),
+        }
+        self
+    }
+
+    /// Set the `id` attribute.
+    pub fn id(self, id: impl Into<String>) -> Self {
+        self.attr("id", id)
+    }
+
+    /// Add a class to the `class` attribute.
+    pub fn class(self, class: impl Into<String>) -> Self {
+        let class = class.into();
+        let value = match self.attributes.iter().find(|(k, _)| k == "class") {
+            Some((_, existing)) if !existing.is_empty() => format!("{existing} {class}"),
+            _ => class,
+        };
+        self.attr("class", value)
+    }
+
+    /// Set the text of this element. The text is placed before any child elements.
+    pub fn text(mut self, text: impl Into<String>) -> Self {
+        self.text = text.into();
+        self
+    }
+
+    /// Append a child element.
+    pub fn child(mut self, child: MockElement) -> Self {
+        self.children.push(child);
+        self
+    }
+
+    /// Append the specified child elements.
+    pub fn children(mut self, children: impl IntoIterator<Item = MockElement>) -> Self {
+        self.children.extend(children);
+        self
+    }
+
+    /// Attach a shadow root containing the specified elements.
+    ///
+    /// Elements in the shadow root can only be found via `WebElement::get_shadow_root()`.
+    pub fn shadow_root(mut self, children: impl IntoIterator<Item = MockElement>) -> Self {
+        self.shadow = Some(children.into_iter().collect());
+        self
+    }
+
+    /// Mark this element (and therefore its descendants) as not displayed.
+    pub fn hidden(mut self) -> Self {
+        self.displayed = false;
+        self
+    }
+
+    /// Set the rect returned for this element.
+    ///
+    /// By default each element is 100x20 pixels, stacked vertically in document order.
+    pub fn rect(mut self, rect: ElementRect) -> Self {
+        self.rect = Some(rect);
+        self
+    }
+}
+
+/// A node in the DOM. Node 0 is the document itself.
+#[derive(Debug, Clone)]
+pub(super) struct Node {
+    pub tag: String,
+    pub attributes: Vec<(String, String)>,
+    pub text: String,
+    pub parent: Option<usize>,
+    pub children: Vec<usize>,
+    /// The `#shadow-root` node attached to this element, if any.
+    pub shadow_root: Option<usize>,
+    /// For a `#shadow-root` node, the element it is attached to.
+    pub host: Option<usize>,
+    pub displayed: bool,
+    pub rect: ElementRect,
+    pub value: String,
+    pub checked: bool,
+    pub selected: bool,
+}
+
+/// A loaded document. Nodes are stored in document order.
+#[derive(Debug, Clone)]
+pub(super) struct Dom {
+    pub nodes: Vec<Node>,
+}
+
+impl Dom {
+    pub fn new(root: &MockElement) -> Self {
+        let document = Node {
+            tag: "#document".to_string(),
+            attributes: Vec::new(),
+            text: String::new(),
+            parent: None,
+            children: Vec::new(),
+            shadow_root: None,
+            host: None,
+            displayed: true,
+            rect: ElementRect {
+                x: 0.0,
+                y: 0.0,
+                width: 0.0,
+                height: 0.0,
+            },
+            value: String::new(),
+            checked: false,
+            selected: false,
+        };
+        let mut dom = Self {
+            nodes: vec![document],
+        };
+        dom.insert(root, 0);
+
+        // Like browsers, single selects always have an option selected.
+        for idx in 0..dom.nodes.len() {
+            if dom.nodes[idx].tag == "select" && dom.attr(idx, "multiple").is_none() {
+                let options = dom.options(idx);
+                match options.iter().rposition(|&x| dom.nodes[x].selected) {
+                    Some(pos) => options.iter().enumerate().for_each(|(i, &x)| {
+                        dom.nodes[x].selected = i == pos;
+                    }),
+                    None => {
+                        if let Some(&first) = options.first() {
+                            dom.nodes[first].selected = true;
+                        }
+                    }
+                }
+            }
+        }
+        dom
+    }
+
+    fn insert(&mut self, elem: &MockElement, parent: usize) -> usize {
+        let idx = self.nodes.len();
+        let get = |name: &str| elem.attributes.iter().find(|(k, _)| k == name).map(|(_, v)| v);
+        let value = match elem.tag.as_str() {
+            "textarea" => elem.text.clone(),
+            _ => get("value").cloned().unwrap_or_default(),
+        };
+        self.nodes.push(Node {
+            tag: elem.tag.clone(),
+            attributes: elem.attributes.clone(),
+            text: elem.text.clone(),
+            parent: Some(parent),
+            children: Vec::new(),
+            shadow_root: None,
+            host: None,
+            displayed: elem.displayed,
+            rect: elem.rect.clone().unwrap_or(ElementRect {
+                x: 0.0,
+                y: (idx - 1) as f64 * 20.0,
+                width: 100.0,
+                height: 20.0,
+            }),
+            value,
+            checked: get("checked").is_some(),
+            selected: get("selected").is_some(),
+        });
+        self.nodes[parent].children.push(idx);
+        for child in &elem.children {
+            self.insert(child, idx);
+        }
+        if let Some(shadow) = &elem.shadow {
+            let root = self.nodes.len();
+            let mut node = self.nodes[0].clone();
+            node.tag = "#shadow-root".to_string();
+            node.children.clear();
+            node.host = Some(idx);
+            self.nodes.push(node);
+            self.nodes[idx].shadow_root = Some(root);
+            for child in shadow {
+                self.insert(child, root);
+            }
+        }
+        idx
+    }
+
+    /// The root element, e.g. `<html>`.
+    pub fn root(&self) -> Option<usize> {
+        self.nodes[0].children.first().copied()
+    }
+
+    /// The first element with the specified tag.
+    pub fn first_by_tag(&self, tag: &str) -> Option<usize> {
+        self.descendants(0).into_iter().find(|&x| self.nodes[x].tag == tag)
+    }
+
+    /// Whether the node is still connected to the document, including via a shadow host.
+    pub fn is_attached(&self, idx: usize) -> bool {
+        let mut current = idx;
+        while let Some(parent) = self.nodes.get(current).and_then(|x| x.parent.or(x.host)) {
+            current = parent;
+        }
+        current == 0 && idx < self.nodes.len()
+    }
+
+    /// Remove the node from the document.
+    pub fn detach(&mut self, idx: usize) {
+        if let Some(parent) = self.nodes[idx].parent.take() {
+            self.nodes[parent].children.retain(|&x| x != idx);
+        }
+    }
+
+    pub fn attr(&self, idx: usize, name: &str) -> Option<&str> {
+        let name = name.to_lowercase();
+        self.nodes[idx].attributes.iter().find(|(k, _)| *k == name).map(|(_, v)| v.as_str())
+    }
+
+    pub fn has_class(&self, idx: usize, class: &str) -> bool {
+        self.attr(idx, "class").is_some_and(|x| x.split_whitespace().any(|c| c == class))
+    }
+
+    /// The parent element, excluding the document and shadow roots.
+    pub fn parent_element(&self, idx: usize) -> Option<usize> {
+        self.nodes[idx].parent.filter(|&x| x != 0 && self.nodes[x].host.is_none())
+    }
+
+    pub fn ancestors(&self, idx: usize) -> impl Iterator<Item = usize> + '_ {
+        std::iter::successors(self.parent_element(idx), |&x| self.parent_element(x))
+    }
+
+    /// The element siblings of this node, including itself.
+    pub fn siblings(&self, idx: usize) -> &[usize] {
+        match self.nodes[idx].parent {
+            Some(parent) => &self.nodes[parent].children,
+            None => &[],
+        }
+    }
+
+    /// All descendants in document order, excluding the node itself.
+    pub fn descendants(&self, idx: usize) -> Vec<usize> {
+        let mut out = Vec::new();
+        let mut stack: Vec<usize> = self.nodes[idx].children.iter().rev().copied().collect();
+        while let Some(x) = stack.pop() {
+            out.push(x);
+            stack.extend(self.nodes[x].children.iter().rev());
+        }
+        out
+    }
+
+    /// The `<option>` elements of a `<select>`.
+    pub fn options(&self, idx: usize) -> Vec<usize> {
+        self.descendants(idx).into_iter().filter(|&x| self.nodes[x].tag == "option").collect()
+    }
+
+    /// The concatenation of all text in the node, as used by XPath.
+    pub fn string_value(&self, idx: usize) -> String {
+        let mut out = self.nodes[idx].text.clone();
+        for x in self.descendants(idx) {
+            out.push_str(&self.nodes[x].text);
+        }
+        out
+    }
+
+    /// The text of the node itself, excluding its descendants.
+    pub fn own_text(&self, idx: usize) -> String {
+        self.nodes[idx].text.clone()
+    }
+
+    /// The rendered text of the element, as returned by WebDriver.
+    pub fn rendered_text(&self, idx: usize) -> String {
+        let mut out = String::new();
+        if self.is_displayed(idx) {
+            self.render(idx, &mut out);
+        }
+        let lines: Vec<_> = out.lines().map(str::trim).filter(|x| !x.is_empty()).collect();
+        lines.join("\n")
+    }
+
+    fn render(&self, idx: usize, out: &mut String) {
+        let node = &self.nodes[idx];
+        if !self.is_rendered(idx) {
+            return;
+        }
+        let block = BLOCK_TAGS.contains(&node.tag.as_str());
+        if block || node.tag == "br" {
+            out.push('\n');
+        }
+        let words: Vec<_> = node.text.split_whitespace().collect();
+        if !words.is_empty() {
+            if node.text.starts_with(char::is_whitespace) {
+                out.push(' ');
+            }
+            out.push_str(&words.join(" "));
+            if node.text.ends_with(char::is_whitespace) {
+                out.push(' ');
+            }
+        }
+        for &child in &node.children {
+            self.render(child, out);
+        }
+        if block {
+            out.push('\n');
+        }
+    }
+
+    /// Whether this node itself is rendered, ignoring its ancestors.
+    fn is_rendered(&self, idx: usize) -> bool {
+        let node = &self.nodes[idx];
+        let style = self.attr(idx, "style").unwrap_or_default().replace(' ', "");
+        node.displayed
+            && !HIDDEN_TAGS.contains(&node.tag.as_str())
+            && self.attr(idx, "hidden").is_none()
+            && (node.tag != "input" || self.attr(idx, "type") != Some("hidden"))
+            && !style.contains("display:none")
+            && !style.contains("visibility:hidden")
+    }
+
+    pub fn is_displayed(&self, idx: usize) -> bool {
+        self.is_rendered(idx) && self.ancestors(idx).all(|x| self.is_rendered(x))
+    }
+
+    pub fn is_enabled(&self, idx: usize) -> bool {
+        let disabled = |x: usize| {
+            FORM_TAGS.contains(&self.nodes[x].tag.as_str()) && self.attr(x, "disabled").is_some()
+        };
+        !FORM_TAGS.contains(&self.nodes[idx].tag.as_str())
+            || !std::iter::once(idx).chain(self.ancestors(idx)).any(disabled)
+    }
+
+    fn is_checkable(&self, idx: usize) -> bool {
+        self.nodes[idx].tag == "input"
+            && matches!(self.attr(idx, "type"), Some("checkbox") | Some("radio"))
+    }
+
+    pub fn is_selected(&self, idx: usize) -> bool {
+        let node = &self.nodes[idx];
+        match node.tag.as_str() {
+            "option" => node.selected,
+            "input" => self.is_checkable(idx) && node.checked,
+            _ => false,
+        }
+    }
+
+    pub fn is_editable(&self, idx: usize) -> bool {
+        let node = &self.nodes[idx];
+        (node.tag == "textarea" || node.tag == "input" && !self.is_checkable(idx))
+            && self.attr(idx, "readonly").is_none()
+            && self.is_enabled(idx)
+    }
+
+    /// The value of the `value` property.
+    fn value(&self, idx: usize) -> Option<String> {
+        let node = &self.nodes[idx];
+        match node.tag.as_str() {
+            "input" | "textarea" | "button" => Some(node.value.clone()),
+            "option" => Some(self.attr(idx, "value").map(str::to_string).unwrap_or_else(|| {
+                self.string_value(idx).split_whitespace().collect::<Vec<_>>().join(" ")
+            })),
+            "select" => Some(
+                self.options(idx)
+                    .into_iter()
+                    .find(|&x| self.nodes[x].selected)
+                    .and_then(|x| self.value(x))
+                    .unwrap_or_default(),
+            ),
+            _ => None,
+        }
+    }
+
+    /// Get an attribute, as returned by the `Get Element Attribute` command.
+    pub fn attribute(&self, idx: usize, name: &str) -> Value {
+        let name = name.to_lowercase();
+        match self.attr(idx, &name) {
+            Some(_) if BOOLEAN_ATTRIBUTES.contains(&name.as_str()) => json!("true"),
+            Some(value) => json!(value),
+            None => Value::Null,
+        }
+    }
+
+    /// Get a property, as returned by the `Get Element Property` command.
+    pub fn property(&self, idx: usize, name: &str) -> Value {
+        let node = &self.nodes[idx];
+        let is_form = FORM_TAGS.contains(&node.tag.as_str());
+        match name {
+            "value" => self.value(idx).map(Value::String).unwrap_or(Value::Null),
+            "checked" if node.tag == "input" => json!(self.is_checkable(idx) && node.checked),
+            "selected" if node.tag == "option" => json!(node.selected),
+            "disabled" if is_form => json!(!self.is_enabled(idx)),
+            "multiple" if node.tag == "select" => json!(self.attr(idx, "multiple").is_some()),
+            "tagName" | "nodeName" => json!(node.tag.to_uppercase()),
+            "id" => json!(self.attr(idx, "id").unwrap_or_default()),
+            "className" => json!(self.attr(idx, "class").unwrap_or_default()),
+            "textContent" => json!(self.string_value(idx)),
+            "innerText" => json!(self.rendered_text(idx)),
+            "innerHTML" => json!(self.html(idx, false)),
+            "outerHTML" => json!(self.html(idx, true)),
+            "selectedIndex" if node.tag == "select" => {
+                let options = self.options(idx);
+                json!(options.iter().position(|&x| self.nodes[x].selected).map_or(-1, |x| x as i64))
+            }
+            _ => self.attr(idx, name).map(|x| json!(x)).unwrap_or(Value::Null),
+        }
+    }
+
+    /// The WAI-ARIA role, from the `role` attribute or else the implicit role of the tag.
+    pub fn role(&self, idx: usize) -> String {
+        if let Some(role) = self.attr(idx, "role").and_then(|x| x.split_whitespace().next()) {
+            return role.to_string();
+        }
+        let input_type = self.attr(idx, "type").unwrap_or("text").to_lowercase();
+        let role = match self.nodes[idx].tag.as_str() {
+            "a" if self.attr(idx, "href").is_some() => "link",
+            "article" => "article",
+            "aside" => "complementary",
+            "button" => "button",
+            "dialog" => "dialog",
+            "footer" => "contentinfo",
+            "form" => "form",
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => "heading",
+            "header" => "banner",
+            "hr" => "separator",
+            "img" => "img",
+            "input" => match input_type.as_str() {
+                "button" | "image" | "reset" | "submit" => "button",
+                "checkbox" => "checkbox",
+                "number" => "spinbutton",
+                "radio" => "radio",
+                "range" => "slider",
+                "search" => "searchbox",
+                _ => "textbox",
+            },
+            "li" => "listitem",
+            "main" => "main",
+            "nav" => "navigation",
+            "ol" | "ul" => "list",
+            "option" => "option",
+            "p" => "paragraph",
+            "select" if self.attr(idx, "multiple").is_some() => "listbox",
+            "select" => "combobox",
+            "table" => "table",
+            "td" => "cell",
+            "textarea" => "textbox",
+            "th" => "columnheader",
+            "tr" => "row",
+            _ => "generic",
+        };
+        role.to_string()
+    }
+
+    /// The accessible name, following a simplified version of the accname algorithm.
+    pub fn accessible_name(&self, idx: usize) -> String {
+        let collapse = |s: &str| s.split_whitespace().collect::<Vec<_>>().join(" ");
+        if let Some(ids) = self.attr(idx, "aria-labelledby") {
+            let names: Vec<_> = ids
+                .split_whitespace()
+                .filter_map(|id| {
+                    self.descendants(0).into_iter().find(|&x| self.attr(x, "id") == Some(id))
+                })
+                .map(|x| collapse(&self.string_value(x)))
+                .collect();
+            return names.join(" ");
+        }
+        if let Some(label) = self.attr(idx, "aria-label").filter(|x| !x.trim().is_empty()) {
+            return collapse(label);
+        }
+
+        let node = &self.nodes[idx];
+        let name = match node.tag.as_str() {
+            "input" if matches!(self.attr(idx, "type"), Some("button" | "reset" | "submit")) => {
+                Some(node.value.clone())
+            }
+            "img" => self.attr(idx, "alt").map(str::to_string),
+            "input" | "select" | "textarea" => {
+                let by_id = self.attr(idx, "id").and_then(|id| {
+                    self.descendants(0)
+                        .into_iter()
+                        .find(|&x| self.nodes[x].tag == "label" && self.attr(x, "for") == Some(id))
+                });
+                by_id
+                    .or_else(|| self.ancestors(idx).find(|&x| self.nodes[x].tag == "label"))
+                    .map(|x| self.string_value(x))
+            }
+            _ => match self.role(idx).as_str() {
+                "button" | "cell" | "checkbox" | "columnheader" | "heading" | "link" | "option"
+                | "radio" | "row" => Some(self.string_value(idx)),
+                _ => None,
+            },
+        };
+        let name = name.map(|x| collapse(&x)).filter(|x| !x.is_empty());
+        name.or_else(|| self.attr(idx, "title").map(collapse)).unwrap_or_default()
+    }
+
+    /// Get a CSS property from the inline `style` attribute.
+    pub fn css_value(&self, idx: usize, name: &str) -> String {
+        let style = self.attr(idx, "style").unwrap_or_default();
+        style
+            .split(';')
+            .filter_map(|decl| decl.split_once(':'))
+            .find(|(k, _)| k.trim().eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.trim().to_string())
+            .unwrap_or_default()
+    }
+
+    /// Serialize the node to HTML.
+    pub fn html(&self, idx: usize, outer: bool) -> String {
+        let node = &self.nodes[idx];
+        let mut out = String::new();
+        if outer && idx != 0 {
+            out.push('<');
+            out.push_str(&node.tag);
+            for (k, v) in &node.attributes {
+                out.push_str(&format!(" {k}=\"{}\"", escape_html(v).replace('"', "&quot;")));
+            }
+            out.push('>');
+            if VOID_TAGS.contains(&node.tag.as_str()) {
+                return out;
+            }
+        }
+        out.push_str(&escape_html(&node.text));
+        for &child in &node.children {
+            out.push_str(&self.html(child, true));
+        }
+        if outer && idx != 0 {
+            out.push_str(&format!("</{}>", node.tag));
+        }
+        out
+    }
+
+    /// Click the element, returning the URL to navigate to, if any.
+    pub fn click(&mut self, idx: usize) -> Option<String> {
+        if !self.is_enabled(idx) {
+            return None;
+        }
+
+        let tag = self.nodes[idx].tag.clone();
+        match tag.as_str() {
+            "input" if self.attr(idx, "type") == Some("checkbox") => {
+                self.nodes[idx].checked = !self.nodes[idx].checked;
+            }
+            "input" if self.attr(idx, "type") == Some("radio") => {
+                let name = self.attr(idx, "name").map(str::to_string);
+                for x in self.descendants(0) {
+                    if self.nodes[x].tag == "input"
+                        && self.attr(x, "type") == Some("radio")
+                        && name.is_some()
+                        && self.attr(x, "name") == name.as_deref()
+                    {
+                        self.nodes[x].checked = false;
+                    }
+                }
+                self.nodes[idx].checked = true;
+            }
+            "option" => {
+                let select = self.ancestors(idx).find(|&x| self.nodes[x].tag == "select");
+                match select {
+                    Some(select) if self.attr(select, "multiple").is_some() => {
+                        self.nodes[idx].selected = !self.nodes[idx].selected;
+                    }
+                    Some(select) => {
+                        for x in self.options(select) {
+                            self.nodes[x].selected = x == idx;
+                        }
+                    }
+                    None => self.nodes[idx].selected = true,
+                }
+            }
+            "label" => {
+                let target = match self.attr(idx, "for") {
+                    Some(id) => {
+                        self.descendants(0).into_iter().find(|&x| self.attr(x, "id") == Some(id))
+                    }
+                    None => self.descendants(idx).into_iter().find(|&x| {
+                        matches!(
+                            self.nodes[x].tag.as_str(),
+                            "input" | "select" | "textarea" | "button"
+                        )
+                    }),
+                };
+                if let Some(target) = target {
+                    return self.click(target);
+                }
+            }
+            _ => {}
+        }
+
+        std::iter::once(idx)
+            .chain(self.ancestors(idx))
+            .find(|&x| self.nodes[x].tag == "a")
+            .and_then(|x| self.attr(x, "href"))
+            .map(str::to_string)
+    }
+}
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
@@ -0,0 +1,994 @@
+use std::collections::{HashMap, VecDeque};
+use std::fmt::{Debug, Display, Formatter};
+use std::sync::{Arc, Mutex};
+
+use bytes::Bytes;
+use http::{Method, Request, Response};
+use serde_json::{json, Value};
+use url::Url;
+
+use super::css::Selector;
+use super::dom::{Dom, MockElement};
+use super::xpath;
+use crate::common::config::WebDriverConfig;
+use crate::error::WebDriverResult;
+use crate::js::{ACCESSIBLE_NAMES, ELEMENT_TEXTS, OWN_TEXTS};
+use crate::session::http::{Body, HttpClient};
+use crate::{Capabilities, WebDriver};
+
+const ELEMENT_KEY: &str = "element-6066-11e4-a52e-4f735466cecf";
+const SHADOW_KEY: &str = "shadow-6066-11e4-a52e-4f735466cecf";
+
+/// The server URL used by [`MockDriver::webdriver()`].
+const MOCK_URL: &str = "http://mock.invalid";
+
+/// A WebDriver error that can be returned by the [`MockDriver`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum MockError {
+    /// `element click intercepted`
+    ElementClickIntercepted,
+    /// `element not interactable`
+    ElementNotInteractable,
+    /// `invalid argument`
+    InvalidArgument,
+    /// `invalid element state`
+    InvalidElementState,
+    /// `invalid selector`
+    InvalidSelector,
+    /// `invalid session id`
+    InvalidSessionId,
+    /// `javascript error`
+    JavascriptError,
+    /// `no such alert`
+    NoSuchAlert,
+    /// `detached shadow root`
+    DetachedShadowRoot,
+    /// `no such cookie`
+    NoSuchCookie,
+    /// `no such element`
+    NoSuchElement,
+    /// `no such frame`
+    NoSuchFrame,
+    /// `no such shadow root`
+    NoSuchShadowRoot,
+    /// `no such window`
+    NoSuchWindow,
+    /// `script timeout`
+    ScriptTimeout,
+    /// `stale element reference`
+    StaleElementReference,
+    /// `timeout`
+    Timeout,
+    /// `unexpected alert open`
+    UnexpectedAlertOpen,
+    /// `unknown command`
+    UnknownCommand,
+    /// `unknown error`
+    UnknownError,
+    /// `unsupported operation`
+    UnsupportedOperation,
+}
+
+impl MockError {
+    /// The W3C error code, e.g. `stale element reference`.
+    pub fn error_code(&self) -> &'static str {
+        match self {
+            MockError::ElementClickIntercepted => "element click intercepted",
+            MockError::ElementNotInteractable => "element not interactable",
+            MockError::InvalidArgument => "invalid argument",
+            MockError::InvalidElementState => "invalid element state",
+            MockError::InvalidSelector => "invalid selector",
+            MockError::InvalidSessionId => "invalid session id",
+            MockError::JavascriptError => "javascript error",
+            MockError::NoSuchAlert => "no such alert",
+            MockError::DetachedShadowRoot => "detached shadow root",
+            MockError::NoSuchCookie => "no such cookie",
+            MockError::NoSuchElement => "no such element",
+            MockError::NoSuchFrame => "no such frame",
+            MockError::NoSuchShadowRoot => "no such shadow root",
+            MockError::NoSuchWindow => "no such window",
+            MockError::ScriptTimeout => "script timeout",
+            MockError::StaleElementReference => "stale element reference",
+            MockError::Timeout => "timeout",
+            MockError::UnexpectedAlertOpen => "unexpected alert open",
+            MockError::UnknownCommand => "unknown command",
+            MockError::UnknownError => "unknown error",
+            MockError::UnsupportedOperation => "unsupported operation",
+        }
+    }
+
+    /// The HTTP status code that the W3C spec associates with this error.
+    pub fn status(&self) -> u16 {
+        match self {
+            MockError::ElementClickIntercepted
+            | MockError::ElementNotInteractable
+            | MockError::InvalidArgument
+            | MockError::InvalidElementState
+            | MockError::InvalidSelector => 400,
+            MockError::DetachedShadowRoot
+            | MockError::InvalidSessionId
+            | MockError::NoSuchAlert
+            | MockError::NoSuchCookie
+            | MockError::NoSuchElement
+            | MockError::NoSuchFrame
+            | MockError::NoSuchShadowRoot
+            | MockError::NoSuchWindow
+            | MockError::StaleElementReference
+            | MockError::UnknownCommand => 404,
+            MockError::JavascriptError
+            | MockError::ScriptTimeout
+            | MockError::Timeout
+            | MockError::UnexpectedAlertOpen
+            | MockError::UnknownError
+            | MockError::UnsupportedOperation => 500,
+        }
+    }
+}
+
+impl Display for MockError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.error_code())
+    }
+}
+
+/// An error with a message, returned from the command handlers.
+type Failure = (MockError, String);
+
+fn fail<T>(error: MockError, message: impl Into<String>) -> Result<T, Failure> {
+    Err((error, message.into()))
+}
+
+/// A request received by the [`MockDriver`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct MockRequest {
+    /// The HTTP method.
+    pub method: Method,
+    /// The request path, e.g. `/session/mock-session-1/element`.
+    pub path: String,
+    /// The JSON request body, if any.
+    pub body: Option<Value>,
+}
+
+impl MockRequest {
+    /// The path relative to the session, e.g. `/element/{element_id}/click`.
+    ///
+    /// Requests that are not part of a session (e.g. `/status` or creating a new session)
+    /// return the full path.
+    pub fn command_path(&self) -> &str {
+        let Some(pos) = self.path.find("/session") else {
+            return self.path.rfind('/').map_or(&self.path, |pos| &self.path[pos..]);
+        };
+        let path = &self.path[pos..];
+        match path.strip_prefix("/session/") {
+            Some(rest) => rest.find('/').map_or("", |pos| &rest[pos..]),
+            None => path,
+        }
+    }
+
+    fn body_str(&self, key: &str) -> Option<&str> {
+        self.body.as_ref().and_then(|x| x[key].as_str())
+    }
+}
+
+type Handler = Arc<dyn Fn(&MockRequest) -> Result<Value, MockError> + Send + Sync>;
+
+/// A scripted response for requests matching a method and path pattern.
+#[derive(Clone)]
+struct Route {
+    method: Method,
+    pattern: String,
+    handler: Handler,
+}
+
+impl Route {
+    fn matches(&self, request: &MockRequest) -> bool {
+        let path = request.command_path();
+        self.method == request.method
+            && self.pattern.split('/').count() == path.split('/').count()
+            && self.pattern.split('/').zip(path.split('/')).all(|(p, x)| p == "*" || p == x)
+    }
+}
+
+impl Debug for Route {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.method, self.pattern)
+    }
+}
+
+#[derive(Debug)]
+struct Window {
+    handle: String,
+    history: Vec<String>,
+    index: usize,
+    dom: Dom,
+    /// Incremented on every page load, so that elements from previous pages become stale.
+    load: usize,
+    active: Option<usize>,
+}
+
+impl Window {
+    fn url(&self) -> &str {
+        &self.history[self.index]
+    }
+}
+
+#[derive(Debug)]
+struct State {
+    pages: HashMap<String, MockElement>,
+    windows: Vec<Window>,
+    current: Option<String>,
+    sessions: Vec<String>,
+    session_count: usize,
+    window_count: usize,
+    load_count: usize,
+    timeouts: Value,
+    window_rect: Value,
+    cookies: Vec<Value>,
+    alert: Option<String>,
+    routes: Vec<Route>,
+    errors: VecDeque<Route>,
+    requests: Vec<MockRequest>,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        let mut state = Self {
+            pages: HashMap::new(),
+            windows: Vec::new(),
+            current: None,
+            sessions: Vec::new(),
+            session_count: 0,
+            window_count: 0,
+            load_count: 0,
+            timeouts: json!({ "script": 30000, "pageLoad": 300000, "implicit": 0 }),
+            window_rect: json!({ "x": 0, "y": 0, "width": 1280, "height": 720 }),
+            cookies: Vec::new(),
+            alert: None,
+            routes: Vec::new(),
+            errors: VecDeque::new(),
+            requests: Vec::new(),
+        };
+        let handle = state.open_window();
+        state.current = Some(handle);
+        state
+    }
+}
+
+/// An in-memory WebDriver server, for testing page objects, components and queries
+/// without a browser.
+///
+/// `MockDriver` implements [`HttpClient`] by handling the W3C WebDriver commands against
+/// a small DOM built from [`MockElement`]s. Elements can be found by CSS selector, XPath,
+/// link text and tag name (CSS and XPath support a commonly used subset), and clicking,
+/// typing and clearing update the DOM like a browser would, e.g. clicking a checkbox
+/// ticks it and clicking an `<option>` selects it.
+///
+/// Anything the DOM model doesn't cover (e.g. user scripts) can be scripted with
+/// [`MockDriver::respond()`] and [`MockDriver::respond_with()`], and errors such as
+/// `stale element reference` can be injected with [`MockDriver::inject_error()`].
+///
+/// `MockDriver` is cheap to clone, and all clones share the same state, so one clone can
+/// be given to the [`WebDriver`] while another is used to control and inspect the mock.
+///
+/// # Example
+/// ```
+/// # use thirtyfour::prelude::*;
+/// # use thirtyfour::support::block_on;
+/// use thirtyfour::testing::{MockDriver, MockElement, MockError};
+/// use http::Method;
+///
+/// # fn main() -> WebDriverResult<()> {
+/// #     block_on(async {
+/// let mock = MockDriver::new();
+/// mock.add_page(
+///     "https://example.com",
+///     MockElement::page(
+///         "Example",
+///         [MockElement::new("button").id("submit").text("Submit")],
+///     ),
+/// );
+///
+/// let driver = mock.webdriver().await?;
+/// driver.goto("https://example.com").await?;
+/// assert_eq!(driver.title().await?, "Example");
+///
+/// let button = driver.find(By::Id("submit")).await?;
+/// assert_eq!(button.text().await?, "Submit");
+///
+/// mock.inject_error(Method::POST, "/element/*/click", MockError::ElementClickIntercepted);
+/// assert!(matches!(
+///     button.click().await,
+///     Err(WebDriverError::ElementClickIntercepted(_))
+/// ));
+/// button.click().await?;
+/// #         driver.quit().await?;
+/// #         Ok(())
+/// #     })
+/// # }
+/// ```
+#[derive(Clone, Default)]
+pub struct MockDriver {
+    state: Arc<Mutex<State>>,
+}
+
+impl Debug for MockDriver {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MockDriver").finish_non_exhaustive()
+    }
+}
+
+impl MockDriver {
+    /// Create a new `MockDriver`, with a single window showing `about:blank`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start a new session against this mock, using the default config.
+    pub async fn webdriver(&self) -> WebDriverResult<WebDriver> {
+        self.webdriver_with_config(WebDriverConfig::default()).await
+    }
+
+    /// Start a new session against this mock, using the specified config.
+    ///
+    /// This is useful for e.g. shortening the default poller timeout in tests that
+    /// expect an element query to fail.
+    pub async fn webdriver_with_config(
+        &self,
+        config: WebDriverConfig,
+    ) -> WebDriverResult<WebDriver> {
+        WebDriver::new_with_config_and_client(MOCK_URL, Capabilities::new(), config, self.clone())
+            .await
+    }
+
+    /// The server URL used by sessions against this mock.
+    ///
+    /// Requests are handled in memory, so nothing listens on this URL.
+    pub fn server_url(&self) -> &'static str {
+        MOCK_URL
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Register the document to load when navigating to the specified URL.
+    ///
+    /// Navigating to an unregistered URL loads an empty document.
+    pub fn add_page(&self, url: impl Into<String>, root: MockElement) {
+        self.state().pages.insert(normalize_url(&url.into()), root);
+    }
+
+    /// Replace the document in the current window, without navigating.
+    ///
+    /// Elements found in the previous document become stale.
+    pub fn set_document(&self, root: MockElement) {
+        let mut state = self.state();
+        if let Some(idx) = state.current_window() {
+            state.load_count += 1;
+            let load = state.load_count;
+            let window = &mut state.windows[idx];
+            window.dom = Dom::new(&root);
+            window.load = load;
+            window.active = None;
+        }
+    }
+
+    /// Remove the elements matching the CSS selector from the current document,
+    /// returning the number of elements removed.
+    ///
+    /// Existing references to removed elements (and their descendants) become stale.
+    pub fn remove(&self, css: &str) -> WebDriverResult<usize> {
+        let selector = Selector::parse(css).map_err(crate::error::WebDriverError::ParseError)?;
+        let mut state = self.state();
+        let Some(idx) = state.current_window() else {
+            return Ok(0);
+        };
+        let dom = &mut state.windows[idx].dom;
+        let matched: Vec<_> =
+            dom.descendants(0).into_iter().filter(|&x| selector.matches(dom, x)).collect();
+        for &x in &matched {
+            dom.detach(x);
+        }
+        Ok(matched.len())
+    }
+
+    /// Always respond to requests matching the method and path with the specified value.
+    ///
+    /// The path is relative to the session (e.g. `/execute/sync`), and `*` matches any
+    /// single path segment (e.g. `/element/*/rect`). Scripted responses take precedence
+    /// over the built-in DOM handling, and later calls take precedence over earlier ones.
+    pub fn respond(&self, method: Method, path: impl Into<String>, value: Value) {
+        self.respond_with(method, path, move |_| Ok(value.clone()));
+    }
+
+    /// Respond to requests matching the method and path using the specified function.
+    ///
+    /// See [`MockDriver::respond()`] for the path syntax.
+    pub fn respond_with<F>(&self, method: Method, path: impl Into<String>, f: F)
+    where
+        F: Fn(&MockRequest) -> Result<Value, MockError> + Send + Sync + 'static,
+    {
+        self.state().routes.push(Route {
+            method,
+            pattern: path.into(),
+            handler: Arc::new(f),
+        });
+    }
+
+    /// Fail the next request matching the method and path with the specified error.
+    ///
+    /// Call this multiple times to fail several requests. See [`MockDriver::respond()`]
+    /// for the path syntax.
+    pub fn inject_error(&self, method: Method, path: impl Into<String>, error: MockError) {
+        self.state().errors.push_back(Route {
+            method,
+            pattern: path.into(),
+            handler: Arc::new(move |_| Err(error)),
+        });
+    }
+
+    /// Open a user prompt (alert) with the specified text.
+    pub fn open_alert(&self, text: impl Into<String>) {
+        self.state().alert = Some(text.into());
+    }
+
+    /// Simulate a browser crash.
+    ///
+    /// All sessions end, so that further commands fail with `invalid session id`, and the
+    /// windows, cookies and alert are reset. New sessions can be started as usual.
+    pub fn crash(&self) {
+        let mut state = self.state();
+        state.sessions.clear();
+        state.windows.clear();
+        state.cookies.clear();
+        state.alert = None;
+        let handle = state.open_window();
+        state.current = Some(handle);
+    }
+
+    /// The URL of the current window, if it is still open.
+    pub fn current_url(&self) -> Option<String> {
+        let state = self.state();
+        state.current_window().map(|idx| state.windows[idx].url().to_string())
+    }
+
+    /// All requests received so far, in order.
+    pub fn requests(&self) -> Vec<MockRequest> {
+        self.state().requests.clone()
+    }
+
+    fn handle(&self, request: MockRequest) -> (u16, Value) {
+        let mut state = self.state();
+        state.requests.push(request.clone());
+
+        // Injected errors take precedence over scripted responses, which take precedence
+        // over the DOM.
+        let injected = state.errors.iter().position(|x| x.matches(&request));
+        let route = match injected {
+            Some(pos) => state.errors.remove(pos),
+            None => state.routes.iter().rev().find(|x| x.matches(&request)).cloned(),
+        };
+        let result = match route {
+            Some(route) => {
+                // Don't hold the lock while running user code.
+                drop(state);
+                (route.handler)(&request).map_err(|e| (e, format!("{e} (from MockDriver)")))
+            }
+            None => state.dispatch(&request),
+        };
+
+        match result {
+            Ok(value) => (200, json!({ "value": value })),
+            Err((error, message)) => {
+                tracing::debug!(
+                    "MockDriver: {} {} failed: {message}",
+                    request.method,
+                    request.path
+                );
+                (
+                    error.status(),
+                    json!({ "value": { "error": error.error_code(), "message": message, "stacktrace": "" } }),
+                )
+            }
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl HttpClient for MockDriver {
+    async fn send(&self, request: Request<Body<'_>>) -> WebDriverResult<Response<Bytes>> {
+        let request = MockRequest {
+            method: request.method().clone(),
+            path: request.uri().path().to_string(),
+            body: match request.body() {
+                Body::Empty => None,
+                Body::Json(value) => Some((*value).clone()),
+            },
+        };
+        let (status, body) = self.handle(request);
+        Response::builder()
+            .status(status)
+            .body(Bytes::from(body.to_string()))
+            .map_err(|e| crate::error::WebDriverError::HttpError(e.to_string()))
+    }
+}
+
+fn normalize_url(url: &str) -> String {
+    Url::parse(url).map(String::from).unwrap_or_else(|_| url.to_string())
+}
+
+fn element_json(load: usize, idx: usize) -> Value {
+    json!({ ELEMENT_KEY: format!("mock-element-{load}-{idx}") })
+}
+
+fn shadow_json(load: usize, idx: usize) -> Value {
+    json!({ SHADOW_KEY: format!("mock-shadow-{load}-{idx}") })
+}
+
+/// Parse an id of the form `{prefix}{load}-{idx}`.
+fn parse_id(id: &str, prefix: &str) -> Option<(usize, usize)> {
+    let (load, idx) = id.strip_prefix(prefix)?.split_once('-')?;
+    Some((load.parse().ok()?, idx.parse().ok()?))
+}
+
+impl State {
+    fn open_window(&mut self) -> String {
+        self.window_count += 1;
+        self.load_count += 1;
+        let handle = format!("mock-window-{}", self.window_count);
+        self.windows.push(Window {
+            handle: handle.clone(),
+            history: vec!["about:blank".to_string()],
+            index: 0,
+            dom: Dom::new(&MockElement::page("", [])),
+            load: self.load_count,
+            active: None,
+        });
+        handle
+    }
+
+    fn current_window(&self) -> Option<usize> {
+        let current = self.current.as_ref()?;
+        self.windows.iter().position(|x| x.handle == *current)
+    }
+
+    fn window(&self) -> Result<usize, Failure> {
+        match self.current_window() {
+            Some(idx) => Ok(idx),
+            None => fail(MockError::NoSuchWindow, "the current window has been closed"),
+        }
+    }
+
+    /// Load the page for the URL in the specified window, without touching the history.
+    fn load(&mut self, window: usize, url: &str) {
+        let root = self.pages.get(url).cloned().unwrap_or_else(|| MockElement::page("", []));
+        self.load_count += 1;
+        let window = &mut self.windows[window];
+        window.dom = Dom::new(&root);
+        window.load = self.load_count;
+        window.active = None;
+    }
+
+    fn navigate(&mut self, window: usize, url: &str) -> Result<(), Failure> {
+        let base = Url::parse(self.windows[window].url()).ok();
+        let url = match Url::parse(url) {
+            Ok(url) => url,
+            Err(_) => match base.and_then(|x| x.join(url).ok()) {
+                Some(url) => url,
+                None => return fail(MockError::InvalidArgument, format!("invalid url: {url}")),
+            },
+        };
+        let url = String::from(url);
+        let w = &mut self.windows[window];
+        w.history.truncate(w.index + 1);
+        w.history.push(url.clone());
+        w.index += 1;
+        self.load(window, &url);
+        Ok(())
+    }
+
+    /// Look up an element id, returning the window and node.
+    fn element(&self, id: &str) -> Result<(usize, usize), Failure> {
+        let window = self.window()?;
+        let w = &self.windows[window];
+        match parse_id(id, "mock-element-") {
+            Some((load, idx)) if load == w.load && w.dom.is_attached(idx) => Ok((window, idx)),
+            Some(_) => fail(
+                MockError::StaleElementReference,
+                format!("element {id} is no longer attached to the DOM"),
+            ),
+            None => fail(MockError::NoSuchElement, format!("unknown element {id}")),
+        }
+    }
+
+    /// Look up a shadow root id, returning the window and `#shadow-root` node.
+    fn shadow_root(&self, id: &str) -> Result<(usize, usize), Failure> {
+        let window = self.window()?;
+        let w = &self.windows[window];
+        match parse_id(id, "mock-shadow-") {
+            Some((load, idx)) if load == w.load && w.dom.is_attached(idx) => Ok((window, idx)),
+            Some(_) => fail(
+                MockError::DetachedShadowRoot,
+                format!("shadow root {id} is no longer attached to the DOM"),
+            ),
+            None => fail(MockError::NoSuchShadowRoot, format!("unknown shadow root {id}")),
+        }
+    }
+
+    /// Run one of the scripts that thirtyfour injects to resolve selectors, modelled on the
+    /// DOM. Other scripts return null unless a response is scripted.
+    fn execute(&self, request: &MockRequest) -> Result<Value, Failure> {
+        let text: fn(&Dom, usize) -> String = match request.body_str("script") {
+            Some(ELEMENT_TEXTS) => Dom::rendered_text,
+            Some(OWN_TEXTS) => Dom::own_text,
+            Some(ACCESSIBLE_NAMES) => Dom::accessible_name,
+            _ => return Ok(Value::Null),
+        };
+        let args = request.body.as_ref().and_then(|x| x["args"].as_array());
+        let mut texts = Vec::new();
+        for arg in args.into_iter().flatten() {
+            let (window, idx) = self.element(arg[ELEMENT_KEY].as_str().unwrap_or_default())?;
+            texts.push(text(&self.windows[window].dom, idx));
+        }
+        Ok(json!(texts))
+    }
+
+    /// Find the elements below the specified node, in the specified window.
+    fn find(
+        &self,
+        request: &MockRequest,
+        (window, context): (usize, usize),
+    ) -> Result<Vec<Value>, Failure> {
+        let (Some(using), Some(value)) = (request.body_str("using"), request.body_str("value"))
+        else {
+            return fail(MockError::InvalidArgument, "missing 'using' or 'value'");
+        };
+
+        let w = &self.windows[window];
+        let dom = &w.dom;
+        let invalid = |e: String| (MockError::InvalidSelector, e);
+        let candidates = dom.descendants(context).into_iter();
+        let found: Vec<usize> = match using {
+            "css selector" => {
+                let selector = Selector::parse(value).map_err(invalid)?;
+                candidates.filter(|&x| selector.matches(dom, x)).collect()
+            }
+            "xpath" => xpath::find(dom, value, context).map_err(invalid)?,
+            "link text" => candidates
+                .filter(|&x| dom.nodes[x].tag == "a" && dom.rendered_text(x) == value.trim())
+                .collect(),
+            "partial link text" => candidates
+                .filter(|&x| dom.nodes[x].tag == "a" && dom.rendered_text(x).contains(value))
+                .collect(),
+            "tag name" => {
+                candidates.filter(|&x| dom.nodes[x].tag == value.to_lowercase()).collect()
+            }
+            _ => return fail(MockError::InvalidArgument, format!("unknown strategy '{using}'")),
+        };
+        Ok(found.into_iter().map(|x| element_json(w.load, x)).collect())
+    }
+
+    fn find_one(&self, request: &MockRequest, context: (usize, usize)) -> Result<Value, Failure> {
+        match self.find(request, context)?.into_iter().next() {
+            Some(element) => Ok(element),
+            None => fail(
+                MockError::NoSuchElement,
+                format!(
+                    "Unable to locate element: {}",
+                    request.body_str("value").unwrap_or_default()
+                ),
+            ),
+        }
+    }
+
+    fn dispatch(&mut self, request: &MockRequest) -> Result<Value, Failure> {
+        let method = &request.method;
+        let full_path = &request.path[request.path.find("/session").unwrap_or(0)..];
+        if *method == Method::POST && full_path == "/session" {
+            return Ok(self.new_session(request));
+        }
+        if *method == Method::GET && request.command_path() == "/status" {
+            return Ok(json!({ "ready": true, "message": "MockDriver is ready" }));
+        }
+
+        let session_id =
+            full_path.strip_prefix("/session/").map(|x| x.split('/').next().unwrap_or(x));
+        match session_id {
+            Some(id) if self.sessions.iter().any(|x| x == id) => {}
+            _ => return fail(MockError::InvalidSessionId, "session not found"),
+        }
+        if *method == Method::DELETE && request.command_path().is_empty() {
+            self.sessions.retain(|x| Some(x.as_str()) != session_id);
+            return Ok(Value::Null);
+        }
+
+        let path = request.command_path();
+        let segments: Vec<&str> = path.split('/').skip(1).collect();
+        if self.alert.is_some() && !segments.starts_with(&["alert"]) && segments != ["window"] {
+            return fail(MockError::UnexpectedAlertOpen, "an alert is open");
+        }
+        self.command(method, &segments, request)
+    }
+
+    fn new_session(&mut self, request: &MockRequest) -> Value {
+        self.session_count += 1;
+        let session_id = format!("mock-session-{}", self.session_count);
+        self.sessions.push(session_id.clone());
+
+        let requested = request
+            .body
+            .as_ref()
+            .and_then(|x| x["capabilities"]["alwaysMatch"].as_object().cloned())
+            .unwrap_or_default();
+        let mut capabilities = json!({
+            "browserName": "mock",
+            "browserVersion": crate::VERSION,
+            "platformName": std::env::consts::OS,
+            "acceptInsecureCerts": false,
+        });
+        for (k, v) in requested {
+            if !k.starts_with("goog:") && !k.starts_with("moz:") && !k.starts_with("ms:") {
+                capabilities[k] = v;
+            }
+        }
+        // Like a real driver, return the address of the session's BiDi endpoint.
+        if capabilities["webSocketUrl"] == true {
+            capabilities["webSocketUrl"] = json!(format!("ws://mock.invalid/session/{session_id}"));
+        }
+        json!({ "sessionId": session_id, "capabilities": capabilities })
+    }
+
+    fn command(
+        &mut self,
+        method: &Method,
+        segments: &[&str],
+        request: &MockRequest,
+    ) -> Result<Value, Failure> {
+        let body = request.body.clone().unwrap_or_default();
+        let get = *method == Method::GET;
+        let post = *method == Method::POST;
+        let delete = *method == Method::DELETE;
+
+        let value = match segments {
+            ["timeouts"] if get => self.timeouts.clone(),
+            ["timeouts"] if post => {
+                for (k, v) in body.as_object().into_iter().flatten() {
+                    self.timeouts[k] = v.clone();
+                }
+                Value::Null
+            }
+            ["url"] if get => json!(self.windows[self.window()?].url()),
+            ["url"] if post => {
+                let window = self.window()?;
+                let url = request.body_str("url").unwrap_or_default().to_string();
+                self.navigate(window, &url)?;
+                Value::Null
+            }
+            ["back"] | ["forward"] if post => {
+                let window = self.window()?;
+                let w = &mut self.windows[window];
+                let index = match segments[0] {
+                    "back" => w.index.saturating_sub(1),
+                    _ => (w.index + 1).min(w.history.len() - 1),
+                };
+                if index != w.index {
+                    w.index = index;
+                    let url = w.url().to_string();
+                    self.load(window, &url);
+                }
+                Value::Null
+            }
+            ["refresh"] if post => {
+                let window = self.window()?;
+                let url = self.windows[window].url().to_string();
+                self.load(window, &url);
+                Value::Null
+            }
+            ["title"] if get => {
+                let dom = &self.windows[self.window()?].dom;
+                let title = dom.first_by_tag("title").map(|x| dom.string_value(x));
+                let title = title.unwrap_or_default();
+                json!(title.split_whitespace().collect::<Vec<_>>().join(" "))
+            }
+            ["source"] if get => json!(self.windows[self.window()?].dom.html(0, false)),
+            ["window"] if get => json!(self.windows[self.window()?].handle),
+            ["window"] if post => {
+                let handle = request.body_str("handle").unwrap_or_default();
+                if !self.windows.iter().any(|x| x.handle == handle) {
+                    return fail(
+                        MockError::NoSuchWindow,
+                        format!("no window with handle {handle}"),
+                    );
+                }
+                self.current = Some(handle.to_string());
+                Value::Null
+            }
+            ["window"] if delete => {
+                let window = self.window()?;
+                self.windows.remove(window);
+                self.current = None;
+                json!(self.windows.iter().map(|x| &x.handle).collect::<Vec<_>>())
+            }
+            ["window", "handles"] if get => {
+                json!(self.windows.iter().map(|x| &x.handle).collect::<Vec<_>>())
+            }
+            ["window", "new"] if post => {
+                let handle = self.open_window();
+                let kind = request.body_str("type").unwrap_or("tab");
+                json!({ "handle": handle, "type": kind })
+            }
+            ["window", "rect"] if get => self.window_rect.clone(),
+            ["window", "rect"] if post => {
+                for (k, v) in body.as_object().into_iter().flatten() {
+                    if !v.is_null() {
+                        self.window_rect[k] = v.clone();
+                    }
+                }
+                self.window_rect.clone()
+            }
+            ["window", "maximize" | "fullscreen"] if post => {
+                self.window_rect = json!({ "x": 0, "y": 0, "width": 1920, "height": 1080 });
+                self.window_rect.clone()
+            }
+            ["window", "minimize"] if post => self.window_rect.clone(),
+            ["frame"] if post && body["id"].is_null() => Value::Null,
+            ["frame"] if post => {
+                return fail(MockError::NoSuchFrame, "frames are not supported by MockDriver")
+            }
+            ["frame", "parent"] if post => Value::Null,
+            ["element"] if post => self.find_one(request, (self.window()?, 0))?,
+            ["elements"] if post => json!(self.find(request, (self.window()?, 0))?),
+            ["element", "active"] if get => {
+                let window = self.window()?;
+                let w = &self.windows[window];
+                let active = w.active.filter(|&x| w.dom.is_attached(x));
+                match active.or_else(|| w.dom.first_by_tag("body")).or(w.dom.root()) {
+                    Some(idx) => element_json(w.load, idx),
+                    None => return fail(MockError::NoSuchElement, "no active element"),
+                }
+            }
+            ["element", id, "element"] if post => self.find_one(request, self.element(id)?)?,
+            ["element", id, "elements"] if post => json!(self.find(request, self.element(id)?)?),
+            ["shadow", id, "element"] if post => self.find_one(request, self.shadow_root(id)?)?,
+            ["shadow", id, "elements"] if post => {
+                json!(self.find(request, self.shadow_root(id)?)?)
+            }
+            ["element", id, rest @ ..] => return self.element_command(method, id, rest, request),
+            ["execute", "sync"] if post => self.execute(request)?,
+            ["execute", "async"] if post => Value::Null,
+            ["cookie"] if get => json!(self.cookies),
+            ["cookie"] if post => {
+                let mut cookie = body["cookie"].clone();
+                let Some(name) = cookie["name"].as_str().map(str::to_string) else {
+                    return fail(MockError::InvalidArgument, "cookie has no name");
+                };
+                if cookie["path"].is_null() {
+                    cookie["path"] = json!("/");
+                }
+                if cookie["domain"].is_null() {
+                    let url = Url::parse(self.windows[self.window()?].url()).ok();
+                    let host = url.as_ref().and_then(|x| x.host_str()).unwrap_or_default();
+                    cookie["domain"] = json!(host);
+                }
+                self.cookies.retain(|x| x["name"] != name.as_str());
+                self.cookies.push(cookie);
+                Value::Null
+            }
+            ["cookie"] if delete => {
+                self.cookies.clear();
+                Value::Null
+            }
+            ["cookie", name] if get => match self.cookies.iter().find(|x| x["name"] == *name) {
+                Some(cookie) => cookie.clone(),
+                None => return fail(MockError::NoSuchCookie, format!("no cookie named {name}")),
+            },
+            ["cookie", name] if delete => {
+                self.cookies.retain(|x| x["name"] != *name);
+                Value::Null
+            }
+            ["actions"] if post || delete => Value::Null,
+            ["alert", action @ ("accept" | "dismiss")] if post => {
+                if self.alert.take().is_none() {
+                    return fail(MockError::NoSuchAlert, format!("no alert to {action}"));
+                }
+                Value::Null
+            }
+            ["alert", "text"] => match &self.alert {
+                Some(_) if post => Value::Null,
+                Some(text) => json!(text),
+                None => return fail(MockError::NoSuchAlert, "no alert is open"),
+            },
+            ["screenshot"] | ["print"] => json!(""),
+            _ => {
+                return fail(
+                    MockError::UnknownCommand,
+                    format!("{method} {} is not supported by MockDriver", request.command_path()),
+                )
+            }
+        };
+        Ok(value)
+    }
+
+    fn element_command(
+        &mut self,
+        method: &Method,
+        id: &str,
+        segments: &[&str],
+        request: &MockRequest,
+    ) -> Result<Value, Failure> {
+        let (window, idx) = self.element(id)?;
+        let get = *method == Method::GET;
+        let post = *method == Method::POST;
+        let dom = &self.windows[window].dom;
+
+        let value = match segments {
+            ["selected"] if get => json!(dom.is_selected(idx)),
+            ["displayed"] if get => json!(dom.is_displayed(idx)),
+            ["enabled"] if get => json!(dom.is_enabled(idx)),
+            ["attribute", name] if get => dom.attribute(idx, name),
+            ["property", name] if get => dom.property(idx, name),
+            ["css", name] if get => json!(dom.css_value(idx, name)),
+            ["text"] if get => json!(dom.rendered_text(idx)),
+            ["name"] if get => json!(dom.nodes[idx].tag),
+            ["computedrole"] if get => json!(dom.role(idx)),
+            ["computedlabel"] if get => json!(dom.accessible_name(idx)),
+            ["rect"] if get => json!(dom.nodes[idx].rect),
+            ["shadow"] if get => match dom.nodes[idx].shadow_root {
+                Some(root) => shadow_json(self.windows[window].load, root),
+                None => return fail(MockError::NoSuchShadowRoot, "element has no shadow root"),
+            },
+            ["screenshot"] if get => json!(""),
+            ["click"] if post => {
+                if !dom.is_displayed(idx) {
+                    return fail(MockError::ElementNotInteractable, "element is not displayed");
+                }
+                let w = &mut self.windows[window];
+                w.active = Some(idx);
+                if let Some(href) = w.dom.click(idx) {
+                    if !href.starts_with('#') && !href.starts_with("javascript:") {
+                        self.navigate(window, &href)?;
+                    }
+                }
+                Value::Null
+            }
+            ["clear"] if post => {
+                if !dom.is_editable(idx) {
+                    return fail(MockError::InvalidElementState, "element is not editable");
+                }
+                self.windows[window].dom.nodes[idx].value.clear();
+                Value::Null
+            }
+            ["value"] if post => {
+                if !dom.is_displayed(idx) || !dom.is_editable(idx) {
+                    return fail(MockError::ElementNotInteractable, "element is not interactable");
+                }
+                let multiline = dom.nodes[idx].tag == "textarea";
+                let w = &mut self.windows[window];
+                w.active = Some(idx);
+                let value = &mut w.dom.nodes[idx].value;
+                for c in request.body_str("text").unwrap_or_default().chars() {
+                    match c {
+                        // Backspace.
+                        '\u{e003}' => {
+                            value.pop();
+                        }
+                        // Return and Enter.
+                        '\u{e006}' | '\u{e007}' if multiline => value.push('\n'),
+                        // Other special keys have no effect on the value.
+                        '\u{e000}'..='\u{f8ff}' => {}
+                        c => value.push(c),
+                    }
+                }
+                Value::Null
+            }
+            _ => {
+                return fail(
+                    MockError::UnknownCommand,
+                    format!("{method} {} is not supported by MockDriver", request.command_path()),
+                )
+            }
+        };
+        Ok(value)
+    }
+}
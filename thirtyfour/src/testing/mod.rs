@@ -0,0 +1,7 @@
+mod css;
+mod dom;
+mod driver;
+mod xpath;
+
+pub use dom::MockElement;
+pub use driver::{MockDriver, MockError, MockRequest};
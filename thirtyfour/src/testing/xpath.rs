@@ -0,0 +1,782 @@
+//! A subset of XPath 1.0, enough for the expressions generated by `thirtyfour` (e.g. by
+//! `SelectElement`) and most hand-written page objects.
+//!
+//! Supported: absolute and relative location paths, `//`, `.`, `..`, the `child`,
+//! `descendant`, `descendant-or-self`, `self`, `parent`, `ancestor`, `ancestor-or-self`,
+//! `following-sibling`, `preceding-sibling` and `attribute` axes, the `*`, `text()` and
+//! `node()` node tests, predicates (including positional ones), `and`, `or`, `|`, the
+//! comparison operators, and the `contains()`, `starts-with()`, `ends-with()`,
+//! `normalize-space()`, `string()`, `concat()`, `translate()`, `string-length()`, `not()`,
+//! `count()`, `position()`, `last()`, `true()` and `false()` functions.
+
+use super::dom::Dom;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Slash,
+    DoubleSlash,
+    LBracket,
+    RBracket,
+    LParen,
+    RParen,
+    At,
+    Comma,
+    Pipe,
+    Dot,
+    DotDot,
+    Axis(String),
+    Op(&'static str),
+    Star,
+    Name(String),
+    Literal(String),
+    Number(f64),
+}
+
+fn tokenize(s: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let chars: Vec<char> = s.chars().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        let token = match c {
+            _ if c.is_whitespace() => {
+                i += 1;
+                continue;
+            }
+            '/' if next == Some('/') => Token::DoubleSlash,
+            '/' => Token::Slash,
+            '[' => Token::LBracket,
+            ']' => Token::RBracket,
+            '(' => Token::LParen,
+            ')' => Token::RParen,
+            '@' => Token::At,
+            ',' => Token::Comma,
+            '|' => Token::Pipe,
+            '*' => Token::Star,
+            '=' => Token::Op("="),
+            '!' if next == Some('=') => Token::Op("!="),
+            '<' if next == Some('=') => Token::Op("<="),
+            '>' if next == Some('=') => Token::Op(">="),
+            '<' => Token::Op("<"),
+            '>' => Token::Op(">"),
+            '.' if next == Some('.') => Token::DotDot,
+            '.' if !next.is_some_and(|x| x.is_ascii_digit()) => Token::Dot,
+            '"' | '\'' => {
+                let end = chars[i + 1..]
+                    .iter()
+                    .position(|&x| x == c)
+                    .ok_or_else(|| format!("unterminated string in '{s}'"))?;
+                let literal: String = chars[i + 1..i + 1 + end].iter().collect();
+                i += end + 2;
+                tokens.push(Token::Literal(literal));
+                continue;
+            }
+            _ if c.is_ascii_digit() || c == '.' => {
+                let len =
+                    chars[i..].iter().take_while(|x| x.is_ascii_digit() || **x == '.').count();
+                let number: String = chars[i..i + len].iter().collect();
+                i += len;
+                tokens.push(Token::Number(
+                    number.parse().map_err(|_| format!("bad number {number}"))?,
+                ));
+                continue;
+            }
+            _ if c.is_alphabetic() || c == '_' => {
+                let len = chars[i..]
+                    .iter()
+                    .take_while(|x| x.is_alphanumeric() || matches!(x, '-' | '_' | '.'))
+                    .count();
+                let name: String = chars[i..i + len].iter().collect();
+                i += len;
+                if chars.get(i) == Some(&':') && chars.get(i + 1) == Some(&':') {
+                    i += 2;
+                    tokens.push(Token::Axis(name));
+                } else {
+                    tokens.push(Token::Name(name));
+                }
+                continue;
+            }
+            _ => return Err(format!("unexpected '{c}' in '{s}'")),
+        };
+        i += match token {
+            Token::DoubleSlash | Token::DotDot | Token::Op("!=" | "<=" | ">=") => 2,
+            _ => 1,
+        };
+        tokens.push(token);
+    }
+    Ok(tokens)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Axis {
+    Child,
+    Descendant,
+    DescendantOrSelf,
+    Itself,
+    Parent,
+    Ancestor,
+    AncestorOrSelf,
+    FollowingSibling,
+    PrecedingSibling,
+    Attribute,
+}
+
+impl Axis {
+    fn parse(name: &str) -> Result<Self, String> {
+        Ok(match name {
+            "child" => Axis::Child,
+            "descendant" => Axis::Descendant,
+            "descendant-or-self" => Axis::DescendantOrSelf,
+            "self" => Axis::Itself,
+            "parent" => Axis::Parent,
+            "ancestor" => Axis::Ancestor,
+            "ancestor-or-self" => Axis::AncestorOrSelf,
+            "following-sibling" => Axis::FollowingSibling,
+            "preceding-sibling" => Axis::PrecedingSibling,
+            "attribute" => Axis::Attribute,
+            _ => return Err(format!("unsupported axis '{name}'")),
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+enum NodeTest {
+    Name(String),
+    Any,
+    Text,
+    Node,
+}
+
+#[derive(Debug, Clone)]
+struct Step {
+    axis: Axis,
+    test: NodeTest,
+    predicates: Vec<Expr>,
+}
+
+#[derive(Debug, Clone)]
+enum Expr {
+    Path {
+        absolute: bool,
+        steps: Vec<Step>,
+    },
+    Literal(String),
+    Number(f64),
+    Function(String, Vec<Expr>),
+    Binary(Box<Expr>, &'static str, Box<Expr>),
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn eat(&mut self, token: &Token) -> bool {
+        if self.peek() == Some(token) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, token: Token) -> Result<(), String> {
+        match self.next() {
+            Some(t) if t == token => Ok(()),
+            other => Err(format!("expected {token:?} but found {other:?}")),
+        }
+    }
+
+    fn binary(
+        &mut self,
+        ops: &[&'static str],
+        operand: fn(&mut Self) -> Result<Expr, String>,
+    ) -> Result<Expr, String> {
+        let mut lhs = operand(self)?;
+        loop {
+            let op = match self.peek() {
+                Some(Token::Op(op)) if ops.contains(op) => *op,
+                Some(Token::Name(name)) if ops.contains(&name.as_str()) => {
+                    ops.iter().find(|x| **x == name).copied().unwrap_or_default()
+                }
+                Some(Token::Pipe) if ops.contains(&"|") => "|",
+                _ => return Ok(lhs),
+            };
+            self.pos += 1;
+            lhs = Expr::Binary(Box::new(lhs), op, Box::new(operand(self)?));
+        }
+    }
+
+    fn expr(&mut self) -> Result<Expr, String> {
+        self.binary(&["or"], |p| p.binary(&["and"], Self::equality))
+    }
+
+    fn equality(&mut self) -> Result<Expr, String> {
+        self.binary(&["=", "!="], |p| p.binary(&["<", "<=", ">", ">="], Self::union))
+    }
+
+    fn union(&mut self) -> Result<Expr, String> {
+        self.binary(&["|"], Self::primary)
+    }
+
+    fn primary(&mut self) -> Result<Expr, String> {
+        match self.peek().cloned() {
+            Some(Token::Literal(s)) => {
+                self.pos += 1;
+                Ok(Expr::Literal(s))
+            }
+            Some(Token::Number(n)) => {
+                self.pos += 1;
+                Ok(Expr::Number(n))
+            }
+            Some(Token::LParen) => {
+                self.pos += 1;
+                let expr = self.expr()?;
+                self.expect(Token::RParen)?;
+                Ok(expr)
+            }
+            Some(Token::Name(name))
+                if self.tokens.get(self.pos + 1) == Some(&Token::LParen)
+                    && !matches!(name.as_str(), "text" | "node") =>
+            {
+                self.pos += 2;
+                let mut args = Vec::new();
+                if !self.eat(&Token::RParen) {
+                    loop {
+                        args.push(self.expr()?);
+                        if self.eat(&Token::RParen) {
+                            break;
+                        }
+                        self.expect(Token::Comma)?;
+                    }
+                }
+                Ok(Expr::Function(name, args))
+            }
+            _ => self.path(),
+        }
+    }
+
+    fn path(&mut self) -> Result<Expr, String> {
+        let mut steps = Vec::new();
+        let absolute = match self.peek() {
+            Some(Token::Slash) => {
+                self.pos += 1;
+                if !self.is_step_start() {
+                    return Ok(Expr::Path {
+                        absolute: true,
+                        steps,
+                    });
+                }
+                true
+            }
+            Some(Token::DoubleSlash) => {
+                self.pos += 1;
+                steps.push(descendant_or_self());
+                true
+            }
+            _ => false,
+        };
+
+        loop {
+            steps.push(self.step()?);
+            if self.eat(&Token::DoubleSlash) {
+                steps.push(descendant_or_self());
+            } else if !self.eat(&Token::Slash) {
+                break;
+            }
+        }
+        Ok(Expr::Path {
+            absolute,
+            steps,
+        })
+    }
+
+    fn is_step_start(&self) -> bool {
+        matches!(
+            self.peek(),
+            Some(
+                Token::Dot
+                    | Token::DotDot
+                    | Token::At
+                    | Token::Star
+                    | Token::Name(_)
+                    | Token::Axis(_)
+            )
+        )
+    }
+
+    fn step(&mut self) -> Result<Step, String> {
+        let axis = match self.peek().cloned() {
+            Some(Token::Dot) => {
+                self.pos += 1;
+                return Ok(Step {
+                    axis: Axis::Itself,
+                    test: NodeTest::Node,
+                    predicates: self.predicates()?,
+                });
+            }
+            Some(Token::DotDot) => {
+                self.pos += 1;
+                return Ok(Step {
+                    axis: Axis::Parent,
+                    test: NodeTest::Node,
+                    predicates: self.predicates()?,
+                });
+            }
+            Some(Token::At) => {
+                self.pos += 1;
+                Axis::Attribute
+            }
+            Some(Token::Axis(name)) => {
+                self.pos += 1;
+                Axis::parse(&name)?
+            }
+            _ => Axis::Child,
+        };
+
+        let test = match self.next() {
+            Some(Token::Star) => NodeTest::Any,
+            Some(Token::Name(name)) if self.peek() == Some(&Token::LParen) => {
+                self.pos += 1;
+                self.expect(Token::RParen)?;
+                match name.as_str() {
+                    "text" => NodeTest::Text,
+                    "node" => NodeTest::Node,
+                    _ => return Err(format!("unsupported node test '{name}()'")),
+                }
+            }
+            Some(Token::Name(name)) => NodeTest::Name(name.to_lowercase()),
+            other => return Err(format!("expected a node test but found {other:?}")),
+        };
+        Ok(Step {
+            axis,
+            test,
+            predicates: self.predicates()?,
+        })
+    }
+
+    fn predicates(&mut self) -> Result<Vec<Expr>, String> {
+        let mut predicates = Vec::new();
+        while self.eat(&Token::LBracket) {
+            predicates.push(self.expr()?);
+            self.expect(Token::RBracket)?;
+        }
+        Ok(predicates)
+    }
+}
+
+fn descendant_or_self() -> Step {
+    Step {
+        axis: Axis::DescendantOrSelf,
+        test: NodeTest::Node,
+        predicates: Vec::new(),
+    }
+}
+
+/// A node in the XPath data model.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum XNode {
+    /// An element (or the document, for node 0).
+    Element(usize),
+    /// The n-th attribute of an element.
+    Attribute(usize, usize),
+    /// The text of an element, which precedes its child elements.
+    Text(usize),
+}
+
+impl XNode {
+    /// The key used to sort nodes in document order.
+    fn order(&self) -> (usize, usize) {
+        match *self {
+            XNode::Element(idx) => (idx, 0),
+            XNode::Attribute(idx, n) => (idx, n + 1),
+            XNode::Text(idx) => (idx, usize::MAX),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum XValue {
+    Nodes(Vec<XNode>),
+    Str(String),
+    Num(f64),
+    Bool(bool),
+}
+
+#[derive(Clone, Copy)]
+struct Context {
+    node: XNode,
+    position: usize,
+    size: usize,
+}
+
+struct Evaluator<'a> {
+    dom: &'a Dom,
+}
+
+impl Evaluator<'_> {
+    fn string_value(&self, node: XNode) -> String {
+        match node {
+            XNode::Element(idx) => self.dom.string_value(idx),
+            XNode::Attribute(idx, n) => self.dom.nodes[idx].attributes[n].1.clone(),
+            XNode::Text(idx) => self.dom.nodes[idx].text.clone(),
+        }
+    }
+
+    fn string(&self, value: &XValue) -> String {
+        match value {
+            XValue::Nodes(nodes) => {
+                nodes.first().map(|x| self.string_value(*x)).unwrap_or_default()
+            }
+            XValue::Str(s) => s.clone(),
+            XValue::Num(n) if n.fract() == 0.0 => format!("{}", *n as i64),
+            XValue::Num(n) => n.to_string(),
+            XValue::Bool(b) => b.to_string(),
+        }
+    }
+
+    fn number(&self, value: &XValue) -> f64 {
+        match value {
+            XValue::Num(n) => *n,
+            XValue::Bool(b) => f64::from(u8::from(*b)),
+            _ => self.string(value).trim().parse().unwrap_or(f64::NAN),
+        }
+    }
+
+    fn boolean(&self, value: &XValue) -> bool {
+        match value {
+            XValue::Nodes(nodes) => !nodes.is_empty(),
+            XValue::Str(s) => !s.is_empty(),
+            XValue::Num(n) => *n != 0.0 && !n.is_nan(),
+            XValue::Bool(b) => *b,
+        }
+    }
+
+    /// The nodes along the axis, in proximity order (reverse document order for reverse axes).
+    fn axis(&self, node: XNode, axis: Axis) -> Vec<XNode> {
+        let XNode::Element(idx) = node else {
+            return match axis {
+                Axis::Itself | Axis::AncestorOrSelf | Axis::DescendantOrSelf => vec![node],
+                Axis::Parent => vec![XNode::Element(self.owner(node))],
+                Axis::Ancestor => self.axis(XNode::Element(self.owner(node)), Axis::AncestorOrSelf),
+                _ => Vec::new(),
+            };
+        };
+
+        let dom = self.dom;
+        let children = |idx: usize| {
+            let text = (!dom.nodes[idx].text.is_empty()).then_some(XNode::Text(idx));
+            text.into_iter().chain(dom.nodes[idx].children.iter().map(|&x| XNode::Element(x)))
+        };
+        let descendants = |idx: usize| {
+            let mut out = Vec::new();
+            for x in std::iter::once(idx).chain(dom.descendants(idx)) {
+                if x != idx {
+                    out.push(XNode::Element(x));
+                }
+                if !dom.nodes[x].text.is_empty() {
+                    out.push(XNode::Text(x));
+                }
+            }
+            out
+        };
+        let ancestors = |idx: usize| {
+            std::iter::successors(dom.nodes[idx].parent, |&x| dom.nodes[x].parent)
+                .map(XNode::Element)
+                .collect::<Vec<_>>()
+        };
+        let siblings = dom.siblings(idx);
+        let position = siblings.iter().position(|&x| x == idx).unwrap_or_default();
+
+        match axis {
+            Axis::Child => children(idx).collect(),
+            Axis::Descendant => descendants(idx),
+            Axis::DescendantOrSelf => std::iter::once(node).chain(descendants(idx)).collect(),
+            Axis::Itself => vec![node],
+            Axis::Parent => dom.nodes[idx].parent.map(XNode::Element).into_iter().collect(),
+            Axis::Ancestor => ancestors(idx),
+            Axis::AncestorOrSelf => std::iter::once(node).chain(ancestors(idx)).collect(),
+            Axis::FollowingSibling => {
+                siblings.iter().skip(position + 1).map(|&x| XNode::Element(x)).collect()
+            }
+            Axis::PrecedingSibling => {
+                siblings[..position].iter().rev().map(|&x| XNode::Element(x)).collect()
+            }
+            Axis::Attribute => {
+                (0..dom.nodes[idx].attributes.len()).map(|n| XNode::Attribute(idx, n)).collect()
+            }
+        }
+    }
+
+    fn owner(&self, node: XNode) -> usize {
+        match node {
+            XNode::Element(idx) | XNode::Attribute(idx, _) | XNode::Text(idx) => idx,
+        }
+    }
+
+    fn test(&self, node: XNode, axis: Axis, test: &NodeTest) -> bool {
+        match (test, node) {
+            (NodeTest::Node, _) => true,
+            (NodeTest::Text, XNode::Text(_)) => true,
+            (NodeTest::Text, _) => false,
+            (NodeTest::Any, XNode::Element(idx)) => idx != 0 && axis != Axis::Attribute,
+            (NodeTest::Any, XNode::Attribute(..)) => axis == Axis::Attribute,
+            (NodeTest::Name(name), XNode::Element(idx)) => {
+                idx != 0 && axis != Axis::Attribute && self.dom.nodes[idx].tag == *name
+            }
+            (NodeTest::Name(name), XNode::Attribute(idx, n)) => {
+                axis == Axis::Attribute && self.dom.nodes[idx].attributes[n].0 == *name
+            }
+            (_, XNode::Text(_)) => false,
+        }
+    }
+
+    fn eval_path(&self, ctx: Context, absolute: bool, steps: &[Step]) -> Result<XValue, String> {
+        let mut nodes = vec![if absolute {
+            XNode::Element(0)
+        } else {
+            ctx.node
+        }];
+        for step in steps {
+            let mut next = Vec::new();
+            for node in nodes {
+                let mut candidates: Vec<_> = self
+                    .axis(node, step.axis)
+                    .into_iter()
+                    .filter(|x| self.test(*x, step.axis, &step.test))
+                    .collect();
+                for predicate in &step.predicates {
+                    let size = candidates.len();
+                    let mut kept = Vec::new();
+                    for (i, candidate) in candidates.into_iter().enumerate() {
+                        let ctx = Context {
+                            node: candidate,
+                            position: i + 1,
+                            size,
+                        };
+                        let keep = match self.eval(ctx, predicate)? {
+                            XValue::Num(n) => n == (i + 1) as f64,
+                            value => self.boolean(&value),
+                        };
+                        if keep {
+                            kept.push(candidate);
+                        }
+                    }
+                    candidates = kept;
+                }
+                next.extend(candidates);
+            }
+            next.sort_by_key(XNode::order);
+            next.dedup();
+            nodes = next;
+        }
+        Ok(XValue::Nodes(nodes))
+    }
+
+    fn eval(&self, ctx: Context, expr: &Expr) -> Result<XValue, String> {
+        Ok(match expr {
+            Expr::Path {
+                absolute,
+                steps,
+            } => self.eval_path(ctx, *absolute, steps)?,
+            Expr::Literal(s) => XValue::Str(s.clone()),
+            Expr::Number(n) => XValue::Num(*n),
+            Expr::Function(name, args) => self.function(ctx, name, args)?,
+            Expr::Binary(lhs, op, rhs) => {
+                let lhs = self.eval(ctx, lhs)?;
+                match *op {
+                    "or" => XValue::Bool(self.boolean(&lhs) || self.boolean(&self.eval(ctx, rhs)?)),
+                    "and" => {
+                        XValue::Bool(self.boolean(&lhs) && self.boolean(&self.eval(ctx, rhs)?))
+                    }
+                    "|" => match (lhs, self.eval(ctx, rhs)?) {
+                        (XValue::Nodes(mut a), XValue::Nodes(b)) => {
+                            a.extend(b);
+                            a.sort_by_key(XNode::order);
+                            a.dedup();
+                            XValue::Nodes(a)
+                        }
+                        _ => return Err("'|' requires node-sets".to_string()),
+                    },
+                    op => XValue::Bool(self.compare(&lhs, op, &self.eval(ctx, rhs)?)),
+                }
+            }
+        })
+    }
+
+    fn compare(&self, lhs: &XValue, op: &str, rhs: &XValue) -> bool {
+        match (lhs, rhs) {
+            (XValue::Nodes(nodes), other) | (other, XValue::Nodes(nodes))
+                if !matches!(other, XValue::Nodes(_) | XValue::Bool(_)) =>
+            {
+                let swapped = matches!(rhs, XValue::Nodes(_)) && !matches!(lhs, XValue::Nodes(_));
+                nodes.iter().any(|node| {
+                    let value = XValue::Str(self.string_value(*node));
+                    match swapped {
+                        false => self.compare(&value, op, other),
+                        true => self.compare(other, op, &value),
+                    }
+                })
+            }
+            (XValue::Nodes(a), XValue::Nodes(b)) => a.iter().any(|x| {
+                let x = XValue::Str(self.string_value(*x));
+                b.iter().any(|y| self.compare(&x, op, &XValue::Str(self.string_value(*y))))
+            }),
+            _ if matches!(op, "=" | "!=") => {
+                let equal = match (lhs, rhs) {
+                    (XValue::Bool(_), _) | (_, XValue::Bool(_)) => {
+                        self.boolean(lhs) == self.boolean(rhs)
+                    }
+                    (XValue::Num(_), _) | (_, XValue::Num(_)) => {
+                        self.number(lhs) == self.number(rhs)
+                    }
+                    _ => self.string(lhs) == self.string(rhs),
+                };
+                equal == (op == "=")
+            }
+            _ => {
+                let (a, b) = (self.number(lhs), self.number(rhs));
+                match op {
+                    "<" => a < b,
+                    "<=" => a <= b,
+                    ">" => a > b,
+                    _ => a >= b,
+                }
+            }
+        }
+    }
+
+    fn function(&self, ctx: Context, name: &str, args: &[Expr]) -> Result<XValue, String> {
+        let values = args.iter().map(|x| self.eval(ctx, x)).collect::<Result<Vec<_>, _>>()?;
+        let arg = |n: usize| -> Result<String, String> {
+            match values.get(n) {
+                Some(value) => Ok(self.string(value)),
+                None if n == 0 => Ok(self.string_value(ctx.node)),
+                None => Err(format!("{name}() requires {} arguments", n + 1)),
+            }
+        };
+
+        Ok(match name {
+            "contains" => XValue::Bool(arg(0)?.contains(&arg(1)?)),
+            "starts-with" => XValue::Bool(arg(0)?.starts_with(&arg(1)?)),
+            "ends-with" => XValue::Bool(arg(0)?.ends_with(&arg(1)?)),
+            "normalize-space" => {
+                XValue::Str(arg(0)?.split_whitespace().collect::<Vec<_>>().join(" "))
+            }
+            "string" => XValue::Str(arg(0)?),
+            "string-length" => XValue::Num(arg(0)?.chars().count() as f64),
+            "concat" => XValue::Str(values.iter().map(|x| self.string(x)).collect()),
+            "translate" => {
+                let (from, to): (Vec<char>, Vec<char>) =
+                    (arg(1)?.chars().collect(), arg(2)?.chars().collect());
+                XValue::Str(
+                    arg(0)?
+                        .chars()
+                        .filter_map(|c| match from.iter().position(|&x| x == c) {
+                            Some(i) => to.get(i).copied(),
+                            None => Some(c),
+                        })
+                        .collect(),
+                )
+            }
+            "not" => {
+                let value = values.first().ok_or("not() requires 1 argument")?;
+                XValue::Bool(!self.boolean(value))
+            }
+            "count" => match values.first() {
+                Some(XValue::Nodes(nodes)) => XValue::Num(nodes.len() as f64),
+                _ => return Err("count() requires a node-set".to_string()),
+            },
+            "position" => XValue::Num(ctx.position as f64),
+            "last" => XValue::Num(ctx.size as f64),
+            "true" => XValue::Bool(true),
+            "false" => XValue::Bool(false),
+            "name" | "local-name" => match ctx.node {
+                XNode::Element(idx) => XValue::Str(self.dom.nodes[idx].tag.clone()),
+                XNode::Attribute(idx, n) => {
+                    XValue::Str(self.dom.nodes[idx].attributes[n].0.clone())
+                }
+                XNode::Text(_) => XValue::Str(String::new()),
+            },
+            _ => return Err(format!("unsupported function '{name}()'")),
+        })
+    }
+}
+
+/// Find the elements matching the XPath expression, relative to the specified context node.
+pub(super) fn find(dom: &Dom, xpath: &str, context: usize) -> Result<Vec<usize>, String> {
+    let mut parser = Parser {
+        tokens: tokenize(xpath)?,
+        pos: 0,
+    };
+    let expr = parser.expr()?;
+    if let Some(token) = parser.peek() {
+        return Err(format!("unexpected {token:?} in '{xpath}'"));
+    }
+
+    let ctx = Context {
+        node: XNode::Element(context),
+        position: 1,
+        size: 1,
+    };
+    match (Evaluator {
+        dom,
+    })
+    .eval(ctx, &expr)?
+    {
+        XValue::Nodes(nodes) => nodes
+            .into_iter()
+            .filter(|x| *x != XNode::Element(0))
+            .map(|x| match x {
+                XNode::Element(idx) => Ok(idx),
+                _ => Err(format!("the result of '{xpath}' is not an element")),
+            })
+            .collect(),
+        _ => Err(format!("the result of '{xpath}' is not a node-set")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::escape_string;
+    use crate::testing::MockElement;
+
+    fn select(dom: &Dom, xpath: &str, context: usize) -> Vec<String> {
+        let found = find(dom, xpath, context).unwrap();
+        found.into_iter().map(|x| dom.string_value(x)).collect()
+    }
+
+    #[test]
+    fn expressions() {
+        let dom = Dom::new(&MockElement::new("select").children([
+            MockElement::new("option").attr("value", "1").text("  First   option "),
+            MockElement::new("option").attr("value", "2").text("It's \"quoted\" text"),
+            MockElement::new("optgroup").child(MockElement::new("option").text("Nested")),
+        ]));
+        let root = dom.root().unwrap();
+
+        let xpath = format!(".//option[normalize-space(.) = {}]", escape_string("First option"));
+        assert_eq!(select(&dom, &xpath, root), ["  First   option "]);
+        let xpath = format!(".//option[text() = {}]", escape_string("It's \"quoted\" text"));
+        assert_eq!(select(&dom, &xpath, root), ["It's \"quoted\" text"]);
+        assert_eq!(select(&dom, "option[@value='2' or contains(text(), 'Nest')]", root).len(), 1);
+        assert_eq!(select(&dom, "//option[last()]", 0), ["It's \"quoted\" text", "Nested"]);
+        assert_eq!(select(&dom, "//optgroup/option/../preceding-sibling::option[1]", 0).len(), 1);
+        assert_eq!(select(&dom, "/select/*[count(option) = 1]", 0), ["Nested"]);
+        assert!(find(&dom, "//option/@value", 0).is_err());
+        assert!(find(&dom, "//option[", 0).is_err());
+    }
+}
@@ -1,15 +1,33 @@
+use std::future::Future;
 use std::ops::Deref;
+use std::panic::AssertUnwindSafe;
 use std::sync::Arc;
+use std::time::Duration;
 
-use crate::common::config::WebDriverConfig;
+use futures_util::FutureExt;
+use http::{HeaderName, HeaderValue};
+use url::Url;
+
+use crate::common::config::{
+    DropPolicy, RecoveryPolicy, SessionRetryPolicy, WebDriverConfig, WebDriverConfigBuilder,
+};
 use crate::error::WebDriverResult;
+use crate::extensions::query::IntoElementPoller;
 use crate::prelude::WebDriverError;
 use crate::session::create::start_session;
 use crate::session::handle::SessionHandle;
 #[cfg(feature = "reqwest")]
 use crate::session::http::create_reqwest_client;
 use crate::session::http::HttpClient;
-use crate::Capabilities;
+use crate::session::layer::CommandLayer;
+use crate::session::listener::WebDriverListener;
+use crate::session::upload::FileDetector;
+#[cfg(feature = "tokio-runtime")]
+use crate::DriverService;
+use crate::{Capabilities, SessionId};
+
+/// The default timeout for each request sent to the WebDriver server.
+pub(crate) const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(120);
 
 /// The `WebDriver` struct encapsulates an async Selenium WebDriver browser
 /// session.
@@ -83,9 +101,43 @@ impl WebDriver {
         Self::new_with_config(server_url, capabilities, WebDriverConfig::default()).await
     }
 
+    /// Create a new [`WebDriverBuilder`] for configuring the connection to the
+    /// WebDriver server before starting the session.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use std::time::Duration;
+    /// # use thirtyfour::common::config::SessionRetryPolicy;
+    /// # use thirtyfour::prelude::*;
+    /// # use thirtyfour::support::block_on;
+    /// #
+    /// # fn main() -> WebDriverResult<()> {
+    /// #     block_on(async {
+    /// let caps = DesiredCapabilities::chrome();
+    /// let driver = WebDriver::builder("http://localhost:4444", caps)
+    ///     .request_timeout(Duration::from_secs(30))
+    ///     .connect_timeout(Duration::from_secs(5))
+    ///     .bearer_auth("my-grid-token")
+    ///     .session_retry_policy(SessionRetryPolicy::new(3).delay(Duration::from_secs(1)))
+    ///     .build()
+    ///     .await?;
+    /// #         driver.quit().await?;
+    /// #         Ok(())
+    /// #     })
+    /// # }
+    /// ```
+    pub fn builder<S, C>(server_url: S, capabilities: C) -> WebDriverBuilder
+    where
+        S: Into<String>,
+        C: Into<Capabilities>,
+    {
+        WebDriverBuilder::new(server_url, capabilities)
+    }
+
     /// Create a new `WebDriver` with the specified `WebDriverConfig`.
     ///
     /// Use `WebDriverConfig::builder().build()` to construct the config.
+    /// Use [`WebDriver::builder()`] to also configure the HTTP client.
     pub async fn new_with_config<S, C>(
         server_url: S,
         capabilities: C,
@@ -95,9 +147,8 @@ impl WebDriver {
         S: Into<String>,
         C: Into<Capabilities>,
     {
-        // TODO: create builder and make timeout configurable.
         #[cfg(feature = "reqwest")]
-        let client = create_reqwest_client(std::time::Duration::from_secs(120));
+        let client = create_reqwest_client(DEFAULT_REQUEST_TIMEOUT);
         #[cfg(not(feature = "reqwest"))]
         let client = crate::session::http::null_client::create_null_client();
         Self::new_with_config_and_client(server_url, capabilities, config, client).await
@@ -116,21 +167,184 @@ impl WebDriver {
         S: Into<String>,
         C: Into<Capabilities>,
     {
-        let capabilities = capabilities.into();
         let server_url = server_url
             .into()
             .parse()
             .map_err(|e| WebDriverError::ParseError(format!("invalid url: {e}")))?;
+        let handle = Self::start(server_url, capabilities.into(), config, client).await?;
+        Ok(Self {
+            handle: Arc::new(handle),
+        })
+    }
 
-        let client = Arc::new(client);
-        let session_id = start_session(client.as_ref(), &server_url, &config, capabilities).await?;
+    /// Attach to an existing session on the WebDriver server, e.g. one that was started by
+    /// another process or a debugging tool.
+    ///
+    /// Returns an error if the session is not alive. What happens when the `WebDriver` is
+    /// dropped without quitting is set by the [`DropPolicy`] in the config, so use
+    /// `DropPolicy::Leak` to leave the session running. [`WebDriver::quit()`] always ends
+    /// the session. The negotiated capabilities of an attached session are not known.
+    ///
+    /// [`DropPolicy`]: crate::common::config::DropPolicy
+    ///
+    /// # Example:
+    /// ```no_run
+    /// # use thirtyfour::prelude::*;
+    /// # use thirtyfour::common::config::{DropPolicy, WebDriverConfig};
+    /// # use thirtyfour::support::block_on;
+    /// #
+    /// # fn main() -> WebDriverResult<()> {
+    /// #     block_on(async {
+    /// // The id of a session started elsewhere, e.g. by another process.
+    /// let session_id = "9f8d6c52a1b34e0d8c7f6a5b4e3d2c1b";
+    /// let config = WebDriverConfig::builder().drop_policy(DropPolicy::Leak).build()?;
+    /// let driver = WebDriver::attach("http://localhost:4444", session_id, config).await?;
+    /// driver.goto("https://www.rust-lang.org/").await?;
+    /// // Dropping the driver leaves the session running.
+    /// drop(driver);
+    /// #         Ok(())
+    /// #     })
+    /// # }
+    /// ```
+    pub async fn attach<S>(
+        server_url: S,
+        session_id: impl Into<SessionId>,
+        config: WebDriverConfig,
+    ) -> WebDriverResult<Self>
+    where
+        S: Into<String>,
+    {
+        #[cfg(feature = "reqwest")]
+        let client = create_reqwest_client(DEFAULT_REQUEST_TIMEOUT);
+        #[cfg(not(feature = "reqwest"))]
+        let client = crate::session::http::null_client::create_null_client();
+        Self::attach_with_client(server_url, session_id, config, client).await
+    }
+
+    /// Attach to an existing session on the WebDriver server, using the specified client.
+    ///
+    /// See [`WebDriver::attach()`].
+    pub async fn attach_with_client<S>(
+        server_url: S,
+        session_id: impl Into<SessionId>,
+        config: WebDriverConfig,
+        client: impl HttpClient,
+    ) -> WebDriverResult<Self>
+    where
+        S: Into<String>,
+    {
+        let server_url: Url = server_url
+            .into()
+            .parse()
+            .map_err(|e| WebDriverError::ParseError(format!("invalid url: {e}")))?;
+        let handle = SessionHandle::new_with_config(
+            Arc::new(client),
+            server_url,
+            session_id.into(),
+            config,
+        )?;
 
-        let handle = SessionHandle::new_with_config(client, server_url, session_id, config)?;
+        // Don't try to quit a session that could not be attached to.
+        if let Err(e) = handle.get_timeouts().await {
+            let _ = handle.leak();
+            return Err(e);
+        }
         Ok(Self {
             handle: Arc::new(handle),
         })
     }
 
+    /// Start the driver for the browser requested in the capabilities, and create a new
+    /// session with it.
+    ///
+    /// The driver binary (`chromedriver`, `geckodriver` or `msedgedriver`) is looked up on
+    /// the `PATH` and started on a free port. It is stopped when the session quits,
+    /// or when the last `WebDriver` instance for the session is dropped.
+    ///
+    /// # Example:
+    /// ```no_run
+    /// # use thirtyfour::prelude::*;
+    /// # use thirtyfour::support::block_on;
+    /// #
+    /// # fn main() -> WebDriverResult<()> {
+    /// #     block_on(async {
+    /// let caps = DesiredCapabilities::firefox();
+    /// // Starts geckodriver, which must be on the PATH.
+    /// let driver = WebDriver::launch(caps).await?;
+    /// driver.goto("https://www.rust-lang.org/").await?;
+    /// driver.quit().await?;
+    /// #         Ok(())
+    /// #     })
+    /// # }
+    /// ```
+    #[cfg(all(feature = "reqwest", feature = "tokio-runtime"))]
+    pub async fn launch<C>(capabilities: C) -> WebDriverResult<Self>
+    where
+        C: Into<Capabilities>,
+    {
+        let capabilities = capabilities.into();
+        let service = DriverService::start(DriverService::find_for(&capabilities)?).await?;
+        Self::launch_with_service(service, capabilities).await
+    }
+
+    /// Create a new session using the specified [`DriverService`].
+    ///
+    /// The service is stopped when the session quits, or when the last `WebDriver`
+    /// instance for the session is dropped. See [`DriverService`] for an example.
+    #[cfg(feature = "tokio-runtime")]
+    pub async fn launch_with_service<C>(
+        service: DriverService,
+        capabilities: C,
+    ) -> WebDriverResult<Self>
+    where
+        C: Into<Capabilities>,
+    {
+        Self::launch_with_service_and_config(service, capabilities, WebDriverConfig::default())
+            .await
+    }
+
+    /// Create a new session using the specified [`DriverService`] and `WebDriverConfig`.
+    ///
+    /// See [`WebDriver::launch_with_service()`].
+    #[cfg(feature = "tokio-runtime")]
+    pub async fn launch_with_service_and_config<C>(
+        service: DriverService,
+        capabilities: C,
+        config: WebDriverConfig,
+    ) -> WebDriverResult<Self>
+    where
+        C: Into<Capabilities>,
+    {
+        #[cfg(feature = "reqwest")]
+        let client = create_reqwest_client(DEFAULT_REQUEST_TIMEOUT);
+        #[cfg(not(feature = "reqwest"))]
+        let client = crate::session::http::null_client::create_null_client();
+        let server_url = service.url().clone();
+        let handle = Self::start(server_url, capabilities.into(), config, client).await?;
+        Ok(Self {
+            handle: Arc::new(handle.with_service(service)),
+        })
+    }
+
+    async fn start(
+        server_url: Url,
+        capabilities: Capabilities,
+        config: WebDriverConfig,
+        client: impl HttpClient,
+    ) -> WebDriverResult<SessionHandle> {
+        let client = Arc::new(client);
+        let requested = config.recovery_policy.is_some().then(|| capabilities.clone());
+        let (session_id, capabilities) =
+            start_session(client.as_ref(), &server_url, &config, capabilities).await?;
+
+        let handle = SessionHandle::new_with_config(client, server_url, session_id, config)?
+            .with_capabilities(capabilities);
+        Ok(match requested {
+            Some(capabilities) => handle.with_recovery_capabilities(capabilities),
+            None => handle,
+        })
+    }
+
     /// Clone this `WebDriver` keeping the session handle, but supplying a new `WebDriverConfig`.
     ///
     /// This still uses the same underlying client, and still controls the same browser
@@ -161,6 +375,225 @@ impl WebDriver {
     pub fn leak(self) -> Result<(), AlreadyQuit> {
         self.handle.leak()
     }
+
+    /// Create a new session, run the specified closure with it, and then quit the session.
+    ///
+    /// The session is quit asynchronously even if the closure returns an error or panics,
+    /// so it never relies on the blocking quit in `Drop`. Returns the result of the closure,
+    /// or the error from quitting if the closure succeeded.
+    ///
+    /// # Example:
+    /// ```no_run
+    /// # use thirtyfour::prelude::*;
+    /// # use thirtyfour::support::block_on;
+    /// #
+    /// # fn main() -> WebDriverResult<()> {
+    /// #     block_on(async {
+    /// let caps = DesiredCapabilities::chrome();
+    /// let title = WebDriver::scope("http://localhost:4444", caps, |driver| async move {
+    ///     driver.goto("https://www.rust-lang.org/").await?;
+    ///     driver.title().await
+    /// })
+    /// .await?;
+    /// assert_eq!(title, "Rust Programming Language");
+    /// #         Ok(())
+    /// #     })
+    /// # }
+    /// ```
+    pub async fn scope<S, C, F, Fut, T>(server_url: S, capabilities: C, f: F) -> WebDriverResult<T>
+    where
+        S: Into<String>,
+        C: Into<Capabilities>,
+        F: FnOnce(WebDriver) -> Fut,
+        Fut: Future<Output = WebDriverResult<T>>,
+    {
+        Self::new(server_url, capabilities).await?.scoped(f).await
+    }
+
+    /// Run the specified closure with this session, and then quit the session.
+    ///
+    /// Use this for sessions created with [`WebDriver::builder()`] or [`WebDriver::launch()`].
+    /// See [`WebDriver::scope()`].
+    pub async fn scoped<F, Fut, T>(self, f: F) -> WebDriverResult<T>
+    where
+        F: FnOnce(WebDriver) -> Fut,
+        Fut: Future<Output = WebDriverResult<T>>,
+    {
+        // Call `f` inside the future, so that a panic before its first await is caught too.
+        let result = AssertUnwindSafe(async { f(self.clone()).await }).catch_unwind().await;
+        let quit = self.quit().await;
+        match result {
+            Ok(result) => result.and_then(|value| quit.map(|_| value)),
+            Err(panic) => std::panic::resume_unwind(panic),
+        }
+    }
+}
+
+/// Builder for configuring a [`WebDriver`] and its connection to the WebDriver server.
+///
+/// See [`WebDriver::builder()`].
+#[derive(Debug)]
+pub struct WebDriverBuilder {
+    server_url: String,
+    capabilities: Capabilities,
+    config: WebDriverConfigBuilder,
+    request_timeout: Duration,
+    connect_timeout: Option<Duration>,
+    proxy: Option<String>,
+}
+
+impl WebDriverBuilder {
+    /// Create a new `WebDriverBuilder`.
+    pub fn new<S, C>(server_url: S, capabilities: C) -> Self
+    where
+        S: Into<String>,
+        C: Into<Capabilities>,
+    {
+        Self {
+            server_url: server_url.into(),
+            capabilities: capabilities.into(),
+            config: WebDriverConfigBuilder::new(),
+            request_timeout: DEFAULT_REQUEST_TIMEOUT,
+            connect_timeout: None,
+            proxy: None,
+        }
+    }
+
+    /// Set the timeout for each request sent to the WebDriver server.
+    ///
+    /// The default is 120 seconds.
+    pub fn request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout = timeout;
+        self
+    }
+
+    /// Set the timeout for connecting to the WebDriver server.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Connect to the WebDriver server via the specified proxy URL.
+    ///
+    /// This only affects the connection to the WebDriver server, not the browser.
+    /// See [`CapabilitiesHelper::set_proxy()`] for configuring the browser's proxy.
+    ///
+    /// [`CapabilitiesHelper::set_proxy()`]: crate::CapabilitiesHelper::set_proxy
+    pub fn proxy(mut self, proxy_url: impl Into<String>) -> Self {
+        self.proxy = Some(proxy_url.into());
+        self
+    }
+
+    /// Add a header to send with every command.
+    ///
+    /// See [`WebDriverConfigBuilder::header()`].
+    pub fn header<K, V>(mut self, name: K, value: V) -> Self
+    where
+        HeaderName: TryFrom<K>,
+        <HeaderName as TryFrom<K>>::Error: Into<WebDriverError>,
+        HeaderValue: TryFrom<V>,
+        <HeaderValue as TryFrom<V>>::Error: Into<WebDriverError>,
+    {
+        self.config = self.config.header(name, value);
+        self
+    }
+
+    /// Send the specified bearer token in the `Authorization` header of every command.
+    ///
+    /// See [`WebDriverConfigBuilder::bearer_auth()`].
+    pub fn bearer_auth(mut self, token: impl std::fmt::Display) -> Self {
+        self.config = self.config.bearer_auth(token);
+        self
+    }
+
+    /// Set the policy for retrying failed attempts to start a new session.
+    pub fn session_retry_policy(mut self, policy: SessionRetryPolicy) -> Self {
+        self.config = self.config.session_retry_policy(policy);
+        self
+    }
+
+    /// Add a middleware layer that wraps every command.
+    ///
+    /// See [`WebDriverConfigBuilder::layer()`].
+    pub fn layer(mut self, layer: impl CommandLayer) -> Self {
+        self.config = self.config.layer(layer);
+        self
+    }
+
+    /// Add a listener that is called before and after high-level browser actions.
+    ///
+    /// See [`WebDriverConfigBuilder::listener()`].
+    pub fn listener(mut self, listener: impl WebDriverListener) -> Self {
+        self.config = self.config.listener(listener);
+        self
+    }
+
+    /// Set the keep_alive option.
+    pub fn keep_alive(mut self, keep_alive: bool) -> Self {
+        self.config = self.config.keep_alive(keep_alive);
+        self
+    }
+
+    /// Set the default element poller.
+    pub fn poller(mut self, poller: Arc<dyn IntoElementPoller + Send + Sync>) -> Self {
+        self.config = self.config.poller(poller);
+        self
+    }
+
+    /// Set what to do when the last `WebDriver` for the session is dropped without
+    /// calling `quit()`.
+    pub fn drop_policy(mut self, policy: DropPolicy) -> Self {
+        self.config = self.config.drop_policy(policy);
+        self
+    }
+
+    /// Replace the session after the browser crashes, using the specified policy.
+    pub fn recovery_policy(mut self, policy: RecoveryPolicy) -> Self {
+        self.config = self.config.recovery_policy(policy);
+        self
+    }
+
+    /// Set the `FileDetector` that decides whether files are uploaded before use.
+    ///
+    /// See [`WebDriverConfigBuilder::file_detector()`].
+    pub fn file_detector(mut self, detector: impl FileDetector + 'static) -> Self {
+        self.config = self.config.file_detector(detector);
+        self
+    }
+
+    /// Set the user agent.
+    pub fn user_agent<V>(mut self, user_agent: V) -> Self
+    where
+        HeaderValue: TryFrom<V>,
+        <HeaderValue as TryFrom<V>>::Error: Into<WebDriverError>,
+    {
+        self.config = self.config.user_agent(user_agent);
+        self
+    }
+
+    /// Connect to the WebDriver server and start a new session.
+    #[cfg(feature = "reqwest")]
+    pub async fn build(self) -> WebDriverResult<WebDriver> {
+        let mut builder = reqwest::Client::builder().timeout(self.request_timeout);
+        if let Some(timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(timeout);
+        }
+        if let Some(proxy) = &self.proxy {
+            builder = builder.proxy(reqwest::Proxy::all(proxy)?);
+        }
+        let client = builder.build()?;
+        self.build_with_client(client).await
+    }
+
+    /// Start a new session using the specified HTTP client.
+    ///
+    /// The request timeout, connect timeout and proxy settings are ignored,
+    /// since these are properties of the HTTP client.
+    pub async fn build_with_client(self, client: impl HttpClient) -> WebDriverResult<WebDriver> {
+        let config = self.config.build()?;
+        WebDriver::new_with_config_and_client(self.server_url, self.capabilities, config, client)
+            .await
+    }
 }
 
 /// The Deref implementation allows the WebDriver to "fall back" to SessionHandle and
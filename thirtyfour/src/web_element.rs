@@ -7,11 +7,14 @@ use std::sync::Arc;
 use crate::common::command::Command;
 use crate::error::WebDriverError;
 use crate::js::SIMULATE_DRAG_AND_DROP;
+use crate::locator;
 use crate::session::handle::SessionHandle;
+use crate::session::http::CmdResponse;
+use crate::session::listener::{notify, WebDriverEvent, WebDriverListener};
 use crate::support::base64_decode;
 use crate::{common::types::ElementRect, error::WebDriverResult, By, ElementRef};
 use crate::{support, IntoArcStr};
-use crate::{ElementId, TypingData};
+use crate::{ElementId, ShadowRoot, TypingData};
 
 /// The WebElement struct encapsulates a single element on a page.
 ///
@@ -60,6 +63,10 @@ pub struct WebElement {
     pub element_id: ElementId,
     /// The underlying session handle.
     pub handle: Arc<SessionHandle>,
+    /// The description of the query that found this element, if any.
+    description: Option<Arc<str>>,
+    /// The generation of the session that this element was found in.
+    generation: u64,
 }
 
 impl fmt::Debug for WebElement {
@@ -85,7 +92,9 @@ impl WebElement {
     pub(crate) fn new(element_id: ElementId, handle: Arc<SessionHandle>) -> Self {
         Self {
             element_id,
+            generation: handle.generation(),
             handle,
+            description: None,
         }
     }
 
@@ -103,11 +112,19 @@ impl WebElement {
     ///
     /// [`ScriptRet::element`]: crate::session::scriptret::ScriptRet::element
     pub fn from_json(value: Value, handle: Arc<SessionHandle>) -> WebDriverResult<Self> {
-        let element_ref: ElementRef = serde_json::from_value(value)?;
-        Ok(Self {
-            element_id: ElementId::from(element_ref.id()),
-            handle,
-        })
+        match serde_json::from_value(value)? {
+            ElementRef::Element {
+                id,
+            } => Ok(Self {
+                element_id: ElementId::from(id),
+                generation: handle.generation(),
+                handle,
+                description: None,
+            }),
+            ElementRef::ShadowElement {
+                id,
+            } => Err(WebDriverError::Json(format!("expected an element, got shadow root {id}"))),
+        }
     }
 
     /// Serialize this `WebElement` to JSON.
@@ -116,6 +133,7 @@ impl WebElement {
     ///
     /// See the documentation for [`SessionHandle::execute`] for more details.
     pub fn to_json(&self) -> WebDriverResult<Value> {
+        self.check_session()?;
         Ok(serde_json::to_value(ElementRef::Element {
             id: self.element_id.to_string(),
         })?)
@@ -129,6 +147,43 @@ impl WebElement {
         self.element_id.clone()
     }
 
+    /// The description of the `ElementQuery` that found this element.
+    ///
+    /// This is the description set with `ElementQuery::desc()`, or else a summary of the
+    /// query's selectors. Elements found without a query have no description.
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// Set the description of the query that found this element.
+    pub(crate) fn with_description(mut self, description: Arc<str>) -> Self {
+        self.description = Some(description);
+        self
+    }
+
+    fn listeners(&self) -> &[Arc<dyn WebDriverListener>] {
+        &self.handle.config().listeners
+    }
+
+    /// Return an error if the session this element was found in has since been recovered.
+    fn check_session(&self) -> WebDriverResult<()> {
+        if self.generation == self.handle.generation() {
+            return Ok(());
+        }
+        Err(WebDriverError::StaleSession(format!(
+            "element {} was found before the browser crashed, and must be found again in \
+             session {}",
+            self.element_id,
+            self.handle.current_session_id()
+        )))
+    }
+
+    /// Send the specified command, if this element's session is still running.
+    async fn cmd(&self, command: Command) -> WebDriverResult<CmdResponse> {
+        self.check_session()?;
+        self.handle.cmd(command).await
+    }
+
     /// Get the bounding rectangle for this WebElement.
     ///
     /// # Example:
@@ -148,7 +203,7 @@ impl WebElement {
     /// # }
     /// ```
     pub async fn rect(&self) -> WebDriverResult<ElementRect> {
-        let r = self.handle.cmd(Command::GetElementRect(self.element_id.clone())).await?;
+        let r = self.cmd(Command::GetElementRect(self.element_id.clone())).await?;
         r.value()
     }
 
@@ -177,7 +232,7 @@ impl WebElement {
     /// # }
     /// ```
     pub async fn tag_name(&self) -> WebDriverResult<String> {
-        self.handle.cmd(Command::GetElementTagName(self.element_id.clone())).await?.value()
+        self.cmd(Command::GetElementTagName(self.element_id.clone())).await?.value()
     }
 
     /// Get the class name for this WebElement.
@@ -244,7 +299,7 @@ impl WebElement {
     /// # }
     /// ```
     pub async fn text(&self) -> WebDriverResult<String> {
-        self.handle.cmd(Command::GetElementText(self.element_id.clone())).await?.value()
+        self.cmd(Command::GetElementText(self.element_id.clone())).await?.value()
     }
 
     /// Convenience method for getting the (optional) value property of this element.
@@ -271,7 +326,8 @@ impl WebElement {
     /// # }
     /// ```
     pub async fn click(&self) -> WebDriverResult<()> {
-        self.handle.cmd(Command::ElementClick(self.element_id.clone())).await?;
+        let command = self.cmd(Command::ElementClick(self.element_id.clone()));
+        notify(self.listeners(), WebDriverEvent::Click(self), command).await?;
         Ok(())
     }
 
@@ -294,7 +350,8 @@ impl WebElement {
     /// # }
     /// ```
     pub async fn clear(&self) -> WebDriverResult<()> {
-        self.handle.cmd(Command::ElementClear(self.element_id.clone())).await?;
+        let command = self.cmd(Command::ElementClear(self.element_id.clone()));
+        notify(self.listeners(), WebDriverEvent::Clear(self), command).await?;
         Ok(())
     }
 
@@ -321,10 +378,8 @@ impl WebElement {
     /// # }
     /// ```
     pub async fn prop(&self, name: impl IntoArcStr) -> WebDriverResult<Option<String>> {
-        let resp = self
-            .handle
-            .cmd(Command::GetElementProperty(self.element_id.clone(), name.into()))
-            .await?;
+        let resp =
+            self.cmd(Command::GetElementProperty(self.element_id.clone(), name.into())).await?;
         match resp.value()? {
             Value::String(v) => Ok(Some(v)),
             Value::Bool(b) => Ok(Some(b.to_string())),
@@ -362,10 +417,7 @@ impl WebElement {
     /// # }
     /// ```
     pub async fn attr(&self, name: impl IntoArcStr) -> WebDriverResult<Option<String>> {
-        self.handle
-            .cmd(Command::GetElementAttribute(self.element_id.clone(), name.into()))
-            .await?
-            .value()
+        self.cmd(Command::GetElementAttribute(self.element_id.clone(), name.into())).await?.value()
     }
 
     /// Get the specified attribute.
@@ -397,10 +449,7 @@ impl WebElement {
     /// # }
     /// ```
     pub async fn css_value(&self, name: impl IntoArcStr) -> WebDriverResult<String> {
-        self.handle
-            .cmd(Command::GetElementCssValue(self.element_id.clone(), name.into()))
-            .await?
-            .value()
+        self.cmd(Command::GetElementCssValue(self.element_id.clone(), name.into())).await?.value()
     }
 
     /// Get the specified CSS property.
@@ -411,7 +460,7 @@ impl WebElement {
 
     /// Return true if the WebElement is currently selected, otherwise false.
     pub async fn is_selected(&self) -> WebDriverResult<bool> {
-        self.handle.cmd(Command::IsElementSelected(self.element_id.clone())).await?.value()
+        self.cmd(Command::IsElementSelected(self.element_id.clone())).await?.value()
     }
 
     /// Return true if the WebElement is currently displayed, otherwise false.
@@ -433,7 +482,7 @@ impl WebElement {
     /// # }
     /// ```
     pub async fn is_displayed(&self) -> WebDriverResult<bool> {
-        self.handle.cmd(Command::IsElementDisplayed(self.element_id.clone())).await?.value()
+        self.cmd(Command::IsElementDisplayed(self.element_id.clone())).await?.value()
     }
 
     /// Return true if the WebElement is currently enabled, otherwise false.
@@ -455,7 +504,7 @@ impl WebElement {
     /// # }
     /// ```
     pub async fn is_enabled(&self) -> WebDriverResult<bool> {
-        self.handle.cmd(Command::IsElementEnabled(self.element_id.clone())).await?.value()
+        self.cmd(Command::IsElementEnabled(self.element_id.clone())).await?.value()
     }
 
     /// Return true if the WebElement is currently clickable (visible and enabled),
@@ -543,9 +592,11 @@ impl WebElement {
     /// # }
     /// ```
     pub async fn find(&self, by: By) -> WebDriverResult<WebElement> {
+        if by.is_refined() {
+            return locator::first(self.find_all(by.clone()).await?, &by);
+        }
         let r = self
-            .handle
-            .cmd(Command::FindElementFromElement(self.element_id.clone(), by.into()))
+            .cmd(Command::FindElementFromElement(self.element_id.clone(), by.candidates()))
             .await?;
         r.element(self.handle.clone())
     }
@@ -583,11 +634,18 @@ impl WebElement {
     /// # }
     /// ```
     pub async fn find_all(&self, by: By) -> WebDriverResult<Vec<WebElement>> {
-        let r = self
-            .handle
-            .cmd(Command::FindElementsFromElement(self.element_id.clone(), by.into()))
-            .await?;
-        r.elements(self.handle.clone())
+        let refine = by.refine();
+        let elements = match by.script() {
+            Some((script, args)) => {
+                locator::find_all_js(&self.handle, script, args, self.to_json()?).await?
+            }
+            None => {
+                let command =
+                    Command::FindElementsFromElement(self.element_id.clone(), by.candidates());
+                self.cmd(command).await?.elements(self.handle.clone())?
+            }
+        };
+        refine.apply(elements).await
     }
 
     /// Search for all child elements of this WebElement that match the specified selector.
@@ -634,13 +692,72 @@ impl WebElement {
     /// # }
     /// ```
     pub async fn send_keys(&self, key: impl Into<TypingData>) -> WebDriverResult<()> {
-        self.handle.cmd(Command::ElementSendKeys(self.element_id.clone(), key.into())).await?;
+        let keys = key.into();
+        let event = WebDriverEvent::SendKeys {
+            element: self,
+            keys: &keys,
+        };
+        let command = Command::ElementSendKeys(self.element_id.clone(), keys.clone());
+        notify(self.listeners(), event, self.cmd(command)).await?;
         Ok(())
     }
 
+    /// Select the file at the specified local path in this file input.
+    ///
+    /// By default the local path is sent to the input directly. If the `FileDetector` in the
+    /// `WebDriverConfig` reports that the WebDriver server is remote, the file is first
+    /// uploaded to the server instead, which requires Selenium. If the server does not
+    /// support uploads, this returns [`WebDriverError::UnknownCommand`], since a remote
+    /// browser cannot read the local path. Use `LocalFileDetector` if the browser can.
+    ///
+    /// # Example:
+    /// ```no_run
+    /// # use std::path::Path;
+    /// # use thirtyfour::prelude::*;
+    /// # use thirtyfour::common::config::WebDriverConfig;
+    /// # use thirtyfour::session::upload::HostFileDetector;
+    /// # use thirtyfour::support::block_on;
+    /// #
+    /// # fn main() -> WebDriverResult<()> {
+    /// #     block_on(async {
+    /// #         let caps = DesiredCapabilities::chrome();
+    /// let config = WebDriverConfig::builder().file_detector(HostFileDetector).build()?;
+    /// let driver = WebDriver::new_with_config("http://selenium-hub:4444", caps, config).await?;
+    /// let elem = driver.find(By::Css("input[type='file']")).await?;
+    /// elem.upload_file(Path::new("fixtures/report.csv")).await?;
+    /// #         driver.quit().await?;
+    /// #         Ok(())
+    /// #     })
+    /// # }
+    /// ```
+    pub async fn upload_file(&self, path: &Path) -> WebDriverResult<()> {
+        self.upload_files([path]).await
+    }
+
+    /// Select the files at the specified local paths in this file input.
+    ///
+    /// The input must have the `multiple` attribute to accept more than one file.
+    /// See [`WebElement::upload_file()`].
+    pub async fn upload_files<P>(&self, paths: impl IntoIterator<Item = P>) -> WebDriverResult<()>
+    where
+        P: AsRef<Path>,
+    {
+        let remote = self.handle.config().file_detector.is_remote(self.handle.server_url());
+        let mut files = Vec::new();
+        for path in paths {
+            let path = path.as_ref();
+            if remote {
+                files.push(self.handle.upload_file(path).await?);
+            } else {
+                files.push(std::path::absolute(path)?.to_string_lossy().into_owned());
+            }
+        }
+        self.send_keys(files.join("\n")).await
+    }
+
     /// Take a screenshot of this WebElement and return it as PNG, base64 encoded.
     pub async fn screenshot_as_png_base64(&self) -> WebDriverResult<String> {
-        self.handle.cmd(Command::TakeElementScreenshot(self.element_id.clone())).await?.value()
+        self.cmd(Command::TakeElementScreenshot(self.element_id.clone())).await?.value()
     }
 
     /// Take a screenshot of this WebElement and return it as PNG bytes.
@@ -750,14 +867,84 @@ impl WebElement {
         self.prop("outerHTML").await.map(|x| x.unwrap_or_default())
     }
 
-    /// Get the shadowRoot property of the current element.
+    /// Get the computed WAI-ARIA role of this element, e.g. `button` or `checkbox`.
+    ///
+    /// This is the role exposed to assistive technologies, which takes both the `role`
+    /// attribute and the implicit role of the element into account.
+    ///
+    /// # Example:
+    /// ```no_run
+    /// # use thirtyfour::prelude::*;
+    /// # use thirtyfour::support::block_on;
+    /// #
+    /// # fn main() -> WebDriverResult<()> {
+    /// #     block_on(async {
+    /// #         let caps = DesiredCapabilities::chrome();
+    /// #         let driver = WebDriver::new("http://localhost:4444", caps).await?;
+    /// let elem = driver.find(By::Id("button1")).await?;
+    /// assert_eq!(elem.computed_role().await?, "button");
+    /// #         driver.quit().await?;
+    /// #         Ok(())
+    /// #     })
+    /// # }
+    /// ```
+    pub async fn computed_role(&self) -> WebDriverResult<String> {
+        self.cmd(Command::GetComputedRole(self.element_id.clone())).await?.value()
+    }
+
+    /// Get the accessible name of this element, as computed by the browser.
+    ///
+    /// This is the label announced by assistive technologies, e.g. from `aria-label`,
+    /// an associated `<label>` or the element's text.
     ///
-    /// Call this method on the element containing the `#shadowRoot` node.
-    /// You can then use the returned `WebElement` to query elements within the shadowRoot node.
-    pub async fn get_shadow_root(&self) -> WebDriverResult<WebElement> {
-        let ret =
-            self.handle.execute("return arguments[0].shadowRoot", vec![self.to_json()?]).await?;
-        ret.element()
+    /// # Example:
+    /// ```no_run
+    /// # use thirtyfour::prelude::*;
+    /// # use thirtyfour::support::block_on;
+    /// #
+    /// # fn main() -> WebDriverResult<()> {
+    /// #     block_on(async {
+    /// #         let caps = DesiredCapabilities::chrome();
+    /// #         let driver = WebDriver::new("http://localhost:4444", caps).await?;
+    /// let elem = driver.find(By::Id("close-button")).await?;
+    /// assert_eq!(elem.computed_label().await?, "Close");
+    /// #         driver.quit().await?;
+    /// #         Ok(())
+    /// #     })
+    /// # }
+    /// ```
+    pub async fn computed_label(&self) -> WebDriverResult<String> {
+        self.cmd(Command::GetComputedLabel(self.element_id.clone())).await?.value()
+    }
+
+    /// Get the shadow root of this element.
+    ///
+    /// Call this method on the element hosting the `#shadow-root` node.
+    /// You can then use the returned [`ShadowRoot`] to find elements within the shadow DOM.
+    ///
+    /// Unlike the `shadowRoot` property in Javascript, this also returns closed shadow roots.
+    /// Returns [`WebDriverError::NoSuchShadowRoot`] if this element has no shadow root.
+    ///
+    /// # Example:
+    /// ```no_run
+    /// # use thirtyfour::prelude::*;
+    /// # use thirtyfour::support::block_on;
+    /// #
+    /// # fn main() -> WebDriverResult<()> {
+    /// #     block_on(async {
+    /// #         let caps = DesiredCapabilities::chrome();
+    /// #         let driver = WebDriver::new("http://localhost:4444", caps).await?;
+    /// let elem = driver.find(By::Id("shadow-host")).await?;
+    /// let root = elem.get_shadow_root().await?;
+    /// let inner = root.query(By::Css("button")).first().await?;
+    /// #         driver.quit().await?;
+    /// #         Ok(())
+    /// #     })
+    /// # }
+    /// ```
+    pub async fn get_shadow_root(&self) -> WebDriverResult<ShadowRoot> {
+        let r = self.cmd(Command::GetElementShadowRoot(self.element_id.clone())).await?;
+        ShadowRoot::from_json(r.value_json()?, self.handle.clone())
     }
 
     /// Switch to the specified iframe element.
@@ -782,7 +969,7 @@ impl WebElement {
     /// # }
     /// ```
     pub async fn enter_frame(self) -> WebDriverResult<()> {
-        self.handle.cmd(Command::SwitchToFrameElement(self.element_id.clone())).await?;
+        self.cmd(Command::SwitchToFrameElement(self.element_id.clone())).await?;
         Ok(())
     }
 
@@ -3,11 +3,18 @@
 use rstest::fixture;
 use std::{
     net::SocketAddr,
-    sync::{Arc, OnceLock},
+    sync::{Arc, Mutex, OnceLock},
     thread::JoinHandle,
+    time::Duration,
 };
+use thirtyfour::common::config::WebDriverConfig;
+use thirtyfour::extensions::query::ElementPollerWithTimeout;
 use thirtyfour::prelude::*;
+use thirtyfour::session::handle::SessionHandle;
+use thirtyfour::session::listener::{WebDriverEvent, WebDriverListener};
 use thirtyfour::support::block_on;
+use thirtyfour::testing::{MockDriver, MockElement};
+use thirtyfour::SessionId;
 use tokio::sync::{Semaphore, SemaphorePermit};
 
 static SERVER: OnceLock<Arc<JoinHandle<()>>> = OnceLock::new();
@@ -163,3 +170,119 @@ pub fn other_page_url() -> String {
 pub fn drag_to_url() -> String {
     format!("http://localhost:{PORT}/drag_to.html")
 }
+
+/// The URL of the [`sample_page()`] in the mock driver returned by [`setup()`].
+pub const MOCK_URL: &str = "https://example.com/";
+
+/// The document served by the mock driver at [`MOCK_URL`].
+pub fn sample_page() -> MockElement {
+    MockElement::page(
+        "Sample Page",
+        [
+            MockElement::new("nav").children([
+                MockElement::new("a").attr("href", "/other").text("Other page"),
+                MockElement::new("a").attr("href", "#top").text("Top"),
+            ]),
+            MockElement::new("div").id("checkbox-section").children([
+                MockElement::new("label").text("Option 1").child(
+                    MockElement::new("input").attr("type", "checkbox").attr("name", "option1"),
+                ),
+                MockElement::new("label").text("Option 2").child(
+                    MockElement::new("input")
+                        .attr("type", "checkbox")
+                        .attr("name", "option2")
+                        .attr("checked", ""),
+                ),
+                MockElement::new("label").text("Option 3").child(
+                    MockElement::new("input")
+                        .attr("type", "checkbox")
+                        .attr("name", "option3")
+                        .attr("disabled", ""),
+                ),
+            ]),
+            MockElement::new("select").id("select1").children([
+                MockElement::new("option").attr("value", "1").text("Select1-Option1"),
+                MockElement::new("option").attr("value", "2").text("Select1-Option2"),
+                MockElement::new("option").attr("value", "3").text("Select1-Option3"),
+            ]),
+            MockElement::new("select").id("select2").attr("multiple", "").children([
+                MockElement::new("option").attr("value", "a").text("Multi Option A"),
+                MockElement::new("option").attr("value", "b").text("Multi Option B"),
+                MockElement::new("option").attr("value", "c").text("It's \"C\""),
+            ]),
+            MockElement::new("input").id("text-input").attr("type", "text"),
+            MockElement::new("p").class("message").class("hidden").text("Secret").hidden(),
+            MockElement::new("p").class("message").text("Hello, world"),
+        ],
+    )
+}
+
+/// Create a mock driver and a session that has loaded the [`sample_page()`].
+pub async fn setup() -> WebDriverResult<(MockDriver, WebDriver)> {
+    let mock = MockDriver::new();
+    mock.add_page(MOCK_URL, sample_page());
+    mock.add_page("https://example.com/other", MockElement::page("Other Page", []));
+    let poller =
+        ElementPollerWithTimeout::new(Duration::from_millis(200), Duration::from_millis(10));
+    let config = WebDriverConfig::builder().poller(Arc::new(poller)).build()?;
+    let driver = mock.webdriver_with_config(config).await?;
+    driver.goto(MOCK_URL).await?;
+    Ok((mock, driver))
+}
+
+/// Records the listener hooks that were called.
+#[derive(Debug, Default, Clone)]
+pub struct RecordingListener {
+    pub events: Arc<Mutex<Vec<String>>>,
+}
+
+#[async_trait::async_trait]
+impl WebDriverListener for RecordingListener {
+    async fn after_navigate(&self, _handle: &SessionHandle, url: &str) {
+        self.events.lock().unwrap().push(format!("navigate {url}"));
+    }
+
+    async fn after_back(&self, _handle: &SessionHandle) {
+        self.events.lock().unwrap().push("back".to_string());
+    }
+
+    async fn before_execute(&self, _handle: &SessionHandle, script: &str) {
+        self.events.lock().unwrap().push(format!("execute {script}"));
+    }
+
+    async fn before_click(&self, element: &WebElement) {
+        let description = element.description().unwrap_or_default();
+        self.events.lock().unwrap().push(format!("click {description}"));
+    }
+
+    async fn after_send_keys(&self, element: &WebElement, keys: &TypingData) {
+        let description = element.description().unwrap_or_default();
+        self.events.lock().unwrap().push(format!("send_keys {description} {keys}"));
+    }
+
+    async fn after_clear(&self, element: &WebElement) {
+        let description = element.description().unwrap_or_default();
+        self.events.lock().unwrap().push(format!("clear {description}"));
+    }
+
+    async fn after_query(&self, description: &str, elements: &[WebElement]) {
+        self.events.lock().unwrap().push(format!("query {description} {}", elements.len()));
+    }
+
+    async fn on_exception(&self, event: WebDriverEvent<'_>, error: &WebDriverError) {
+        let event = match event {
+            WebDriverEvent::Query(description) => format!("query {description}"),
+            event => format!("{event:?}"),
+        };
+        let error = match error {
+            WebDriverError::NoSuchElement(_) => "NoSuchElement",
+            _ => "other",
+        };
+        self.events.lock().unwrap().push(format!("{event} failed: {error}"));
+    }
+
+    async fn on_session_recovered(&self, handle: &SessionHandle, previous_session_id: &SessionId) {
+        let session_id = handle.current_session_id();
+        self.events.lock().unwrap().push(format!("recovered {previous_session_id} {session_id}"));
+    }
+}
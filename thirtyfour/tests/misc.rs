@@ -54,6 +54,19 @@ fn status(test_harness: TestHarness) -> WebDriverResult<()> {
     })
 }
 
+#[rstest]
+fn negotiated_capabilities(test_harness: TestHarness) -> WebDriverResult<()> {
+    let c = test_harness.driver();
+    let caps = c.capabilities();
+    assert_eq!(caps.browser_name(), Some(test_harness.browser()));
+    assert!(caps.browser_version().is_some());
+    assert!(caps.platform_name().is_some());
+    if test_harness.browser() == "chrome" {
+        assert!(caps.chrome_debugger_address().is_some());
+    }
+    Ok(())
+}
+
 #[rstest]
 fn timeouts(test_harness: TestHarness) -> WebDriverResult<()> {
     let c = test_harness.driver();
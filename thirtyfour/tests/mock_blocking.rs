@@ -0,0 +1,57 @@
+//! Blocking API tests that run against the in-memory `MockDriver`, without a browser.
+#![cfg(feature = "blocking")]
+
+use std::sync::Arc;
+
+use assert_matches::assert_matches;
+use common::*;
+use http::Method;
+use thirtyfour::blocking::WebDriver;
+use thirtyfour::common::config::WebDriverConfig;
+use thirtyfour::extensions::query::ElementPollerNoWait;
+use thirtyfour::prelude::*;
+use thirtyfour::testing::MockDriver;
+use thirtyfour::Capabilities;
+
+mod common;
+
+#[test]
+fn blocking() -> WebDriverResult<()> {
+    let mock = MockDriver::new();
+    mock.add_page(MOCK_URL, sample_page());
+    let listener = RecordingListener::default();
+    let config = WebDriverConfig::builder()
+        .poller(Arc::new(ElementPollerNoWait))
+        .listener(listener.clone())
+        .build()?;
+    let driver = WebDriver::new_with_config_and_client(
+        mock.server_url(),
+        Capabilities::new(),
+        config,
+        mock.clone(),
+    )?;
+
+    driver.goto(MOCK_URL)?;
+    assert_eq!(driver.title()?, "Sample Page");
+    let input = driver.query(By::Id("text-input")).desc("text input").single()?;
+    input.send_keys("hello")?;
+    assert_eq!(input.value()?.as_deref(), Some("hello"));
+    let links = driver.find(By::Tag("nav"))?.find_all(By::Tag("a"))?;
+    assert_eq!(links.len(), 2);
+    assert!(driver.query(By::Id("missing")).first_opt()?.is_none());
+    assert_matches!(driver.query(By::Id("missing")).first(), Err(WebDriverError::NoSuchElement(_)));
+    driver.action_chain().click_element(&links[1]).perform()?;
+    mock.open_alert("Are you sure?");
+    let alert = driver.alert();
+    assert_eq!(alert.text()?, "Are you sure?");
+    alert.accept()?;
+
+    // The listeners and command stats are shared with the async API.
+    assert_eq!(listener.events.lock().unwrap()[1], "query text input 1");
+    assert!(driver.as_async().command_stats().get("PerformActions").is_some());
+
+    driver.quit()?;
+    let requests = mock.requests();
+    assert!(requests.iter().any(|x| x.method == Method::DELETE && x.command_path().is_empty()));
+    Ok(())
+}
@@ -0,0 +1,107 @@
+//! Component tests that run against the in-memory `MockDriver`, without a browser.
+#![cfg(feature = "component")]
+
+use assert_matches::assert_matches;
+use common::*;
+use http::Method;
+use thirtyfour::components::{Component, ElementResolver};
+use thirtyfour::prelude::*;
+use thirtyfour::testing::MockElement;
+use thirtyfour::{resolve, resolve_present};
+
+mod common;
+
+#[derive(Debug, Clone, Component)]
+pub struct CheckboxSectionComponent {
+    base: WebElement,
+    #[by(tag = "label", not_empty)]
+    boxes: ElementResolver<Vec<CheckboxComponent>>,
+}
+
+#[derive(Debug, Clone, Component)]
+pub struct CheckboxComponent {
+    base: WebElement,
+    #[by(css = "input[type='checkbox']", single)]
+    input: ElementResolver<WebElement>,
+}
+
+impl CheckboxComponent {
+    pub async fn is_ticked(&self) -> WebDriverResult<bool> {
+        resolve!(self.input).is_selected().await
+    }
+
+    pub async fn tick(&self) -> WebDriverResult<()> {
+        let elem = resolve_present!(self.input);
+        if elem.is_clickable().await? && !self.is_ticked().await? {
+            elem.click().await?;
+        }
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn component() -> WebDriverResult<()> {
+    let (mock, driver) = setup().await?;
+    let section: CheckboxSectionComponent =
+        driver.query(By::Id("checkbox-section")).single().await?.into();
+
+    let boxes = resolve!(section.boxes);
+    for checkbox in &boxes {
+        checkbox.tick().await?;
+    }
+    let ticked: Vec<bool> =
+        vec![boxes[0].is_ticked().await?, boxes[1].is_ticked().await?, boxes[2].is_ticked().await?];
+    assert_eq!(ticked, [true, true, false]);
+
+    // Reloading the document makes the cached elements stale.
+    mock.set_document(sample_page());
+    assert_matches!(boxes[0].is_ticked().await, Err(WebDriverError::StaleElementReference(_)));
+    driver.quit().await
+}
+
+#[derive(Debug, Clone, Component)]
+pub struct SignUpComponent {
+    base: WebElement,
+    #[by(label = "Email")]
+    email: ElementResolver<WebElement>,
+    #[by(placeholder = "Password")]
+    password: ElementResolver<WebElement>,
+    #[by(role = "button", accessible_name = "Sign up")]
+    submit: ElementResolver<WebElement>,
+    #[by(role = "button", allow_empty)]
+    buttons: ElementResolver<Vec<WebElement>>,
+    #[by(text = "Forgot password?")]
+    forgot: ElementResolver<WebElement>,
+    #[by(testid = "terms")]
+    terms: ElementResolver<WebElement>,
+    #[by(js = "return this.querySelector('input');")]
+    first_input: ElementResolver<WebElement>,
+}
+
+#[tokio::test]
+async fn component_locators() -> WebDriverResult<()> {
+    let (mock, driver) = setup().await?;
+    mock.set_document(MockElement::page(
+        "Sign up",
+        [MockElement::new("form").id("sign-up").children([
+            MockElement::new("label").attr("for", "email").text("Email"),
+            MockElement::new("input").id("email"),
+            MockElement::new("input").attr("type", "password").attr("placeholder", "Password"),
+            MockElement::new("a").attr("href", "/forgot").text("Forgot password?"),
+            MockElement::new("input").attr("type", "checkbox").attr("data-testid", "terms"),
+            MockElement::new("button").attr("type", "submit").text("Sign up"),
+            MockElement::new("button").attr("type", "reset").text("Clear"),
+        ])],
+    ));
+    let form: SignUpComponent = driver.find(By::Id("sign-up")).await?.into();
+
+    assert_eq!(resolve!(form.email).id().await?.as_deref(), Some("email"));
+    assert_eq!(resolve!(form.password).attr("type").await?.as_deref(), Some("password"));
+    assert_eq!(resolve!(form.submit).attr("type").await?.as_deref(), Some("submit"));
+    assert_eq!(resolve!(form.buttons).len(), 2);
+    assert_eq!(resolve!(form.forgot).attr("href").await?.as_deref(), Some("/forgot"));
+    assert_eq!(resolve!(form.terms).attr("type").await?.as_deref(), Some("checkbox"));
+    mock.respond(Method::POST, "/execute/sync", resolve!(form.email).to_json()?);
+    assert_eq!(resolve!(form.first_input).id().await?.as_deref(), Some("email"));
+    driver.quit().await
+}
@@ -0,0 +1,254 @@
+//! Element tests that run against the in-memory `MockDriver`, without a browser.
+
+use std::io::{Cursor, Write};
+
+use assert_matches::assert_matches;
+use base64::prelude::{Engine, BASE64_STANDARD};
+use common::*;
+use http::Method;
+use serde_json::json;
+use thirtyfour::common::config::WebDriverConfig;
+use thirtyfour::components::SelectElement;
+use thirtyfour::prelude::*;
+use thirtyfour::session::upload::HostFileDetector;
+use thirtyfour::stringmatch::StringMatch;
+use thirtyfour::testing::{MockDriver, MockElement, MockError};
+
+mod common;
+
+#[tokio::test]
+async fn navigation() -> WebDriverResult<()> {
+    let (_mock, driver) = setup().await?;
+    assert_eq!(driver.title().await?, "Sample Page");
+
+    driver.find(By::LinkText("Other page")).await?.click().await?;
+    assert_eq!(driver.current_url().await?.as_str(), "https://example.com/other");
+    assert_eq!(driver.title().await?, "Other Page");
+
+    driver.back().await?;
+    assert_eq!(driver.title().await?, "Sample Page");
+    driver.forward().await?;
+    assert_eq!(driver.title().await?, "Other Page");
+    driver.quit().await
+}
+
+#[tokio::test]
+async fn find_and_interact() -> WebDriverResult<()> {
+    let (_mock, driver) = setup().await?;
+    assert_eq!(driver.find_all(By::Css("nav a")).await?.len(), 2);
+    assert_eq!(driver.find_all(By::XPath("//nav/a[2]")).await?.len(), 1);
+    assert_matches!(driver.find(By::Id("missing")).await, Err(WebDriverError::NoSuchElement(_)));
+    assert_matches!(driver.find(By::Css("div[")).await, Err(WebDriverError::InvalidSelector(_)));
+
+    let section = driver.find(By::Id("checkbox-section")).await?;
+    let boxes = section.find_all(By::Css("input[type='checkbox']")).await?;
+    assert_eq!(boxes.len(), 3);
+    assert!(!boxes[0].is_selected().await?);
+    boxes[0].click().await?;
+    assert!(boxes[0].is_selected().await?);
+    assert_eq!(boxes[0].prop("checked").await?.as_deref(), Some("true"));
+    assert!(!boxes[2].is_enabled().await?);
+
+    let input = driver.find(By::Id("text-input")).await?;
+    input.send_keys("hello").await?;
+    input.send_keys(Key::Backspace + "!").await?;
+    assert_eq!(input.value().await?.as_deref(), Some("hell!"));
+    input.clear().await?;
+    assert_eq!(input.value().await?.as_deref(), Some(""));
+    assert_eq!(driver.active_element().await?.id().await?.as_deref(), Some("text-input"));
+    driver.quit().await
+}
+
+#[tokio::test]
+async fn element_query() -> WebDriverResult<()> {
+    let (mock, driver) = setup().await?;
+    let message = driver.query(By::ClassName("message")).and_displayed().single().await?;
+    assert_eq!(message.text().await?, "Hello, world");
+
+    let hidden = driver
+        .query(By::ClassName("message"))
+        .with_class(StringMatch::new("hidden").word())
+        .first()
+        .await?;
+    assert!(!hidden.is_displayed().await?);
+    assert_eq!(hidden.text().await?, "");
+
+    let ticked =
+        driver.query(By::Css("input[type='checkbox']")).and_selected().all_from_selector().await?;
+    assert_eq!(ticked.len(), 1);
+    assert!(driver.query(By::Id("missing")).nowait().exists().await.map(|x| !x)?);
+
+    // Removed elements become stale.
+    let input = driver.query(By::Id("text-input")).single().await?;
+    assert_eq!(mock.remove("#text-input")?, 1);
+    assert!(!driver.query(By::Id("text-input")).exists().await?);
+    input.wait_until().stale().await?;
+    driver.quit().await
+}
+
+#[tokio::test]
+async fn shadow_root() -> WebDriverResult<()> {
+    let (mock, driver) = setup().await?;
+    mock.set_document(MockElement::page(
+        "Shadow DOM",
+        [MockElement::new("div").id("host").shadow_root([
+            MockElement::new("button").id("inner").text("Shadow button"),
+            MockElement::new("span").class("label").text("One"),
+            MockElement::new("span").class("label").text("Two"),
+        ])],
+    ));
+
+    // Shadow content is not visible from the document.
+    assert_matches!(driver.find(By::Id("inner")).await, Err(WebDriverError::NoSuchElement(_)));
+
+    let host = driver.find(By::Id("host")).await?;
+    let root = host.get_shadow_root().await?;
+    assert_eq!(root.find(By::Css("#inner")).await?.text().await?, "Shadow button");
+    assert_eq!(root.find_all(By::Css(".label")).await?.len(), 2);
+    let label = root.query(By::Css(".label")).with_text("Two").single().await?;
+    assert_eq!(label.tag_name().await?, "span");
+
+    // Shadow roots returned from scripts are not elements.
+    mock.respond(Method::POST, "/execute/sync", root.to_json()?);
+    let ret = driver.execute("return arguments[0].shadowRoot;", vec![host.to_json()?]).await?;
+    assert_eq!(ret.shadow_root()?.find(By::Css("#inner")).await?.text().await?, "Shadow button");
+    let ret = driver.execute("return arguments[0].shadowRoot;", vec![host.to_json()?]).await?;
+    assert_matches!(ret.element(), Err(WebDriverError::Json(_)));
+
+    let body = driver.find(By::Tag("body")).await?;
+    assert_matches!(body.get_shadow_root().await, Err(WebDriverError::NoSuchShadowRoot(_)));
+
+    // Removing the host detaches its shadow root.
+    assert_eq!(mock.remove("#host")?, 1);
+    assert_matches!(root.find(By::Css("#inner")).await, Err(WebDriverError::DetachedShadowRoot(_)));
+    driver.quit().await
+}
+
+#[tokio::test]
+async fn select_element() -> WebDriverResult<()> {
+    let (_mock, driver) = setup().await?;
+    let select = SelectElement::new(&driver.find(By::Id("select1")).await?).await?;
+    assert_eq!(select.first_selected_option().await?.text().await?, "Select1-Option1");
+
+    select.select_by_value("2").await?;
+    assert_eq!(select.first_selected_option().await?.value().await?.as_deref(), Some("2"));
+    select.select_by_index(2).await?;
+    assert_eq!(select.first_selected_option().await?.text().await?, "Select1-Option3");
+    select.select_by_visible_text("Select1-Option1").await?;
+    assert_eq!(select.all_selected_options().await?.len(), 1);
+    select.select_by_partial_text("Option2").await?;
+    assert_eq!(select.first_selected_option().await?.value().await?.as_deref(), Some("2"));
+    assert_matches!(
+        select.select_by_visible_text("Missing").await,
+        Err(WebDriverError::NoSuchElement(_))
+    );
+
+    let multi = SelectElement::new(&driver.find(By::Id("select2")).await?).await?;
+    multi.select_all().await?;
+    assert_eq!(multi.all_selected_options().await?.len(), 3);
+    multi.deselect_by_visible_text("It's \"C\"").await?;
+    multi.deselect_by_value("a").await?;
+    let selected = multi.all_selected_options().await?;
+    assert_eq!(selected.len(), 1);
+    assert_eq!(selected[0].text().await?, "Multi Option B");
+    driver.quit().await
+}
+
+#[tokio::test]
+async fn injected_errors() -> WebDriverResult<()> {
+    let (mock, driver) = setup().await?;
+    let elem = driver.find(By::Id("text-input")).await?;
+
+    mock.inject_error(Method::POST, "/element/*/click", MockError::ElementClickIntercepted);
+    assert_matches!(elem.click().await, Err(WebDriverError::ElementClickIntercepted(_)));
+    elem.click().await?;
+
+    mock.inject_error(Method::GET, "/element/*/name", MockError::StaleElementReference);
+    assert!(!elem.is_present().await?);
+    assert!(elem.is_present().await?);
+
+    mock.respond(Method::POST, "/execute/sync", json!(42));
+    let ret = driver.execute("return 42;", Vec::new()).await?;
+    assert_eq!(ret.convert::<i64>()?, 42);
+
+    // Elements from the previous page are stale after navigating.
+    driver.refresh().await?;
+    assert_matches!(elem.tag_name().await, Err(WebDriverError::StaleElementReference(_)));
+    let requests = mock.requests();
+    assert!(requests.iter().any(|x| x.command_path() == "/refresh"));
+    driver.quit().await
+}
+
+#[tokio::test]
+async fn downloads() -> WebDriverResult<()> {
+    let (mock, driver) = setup().await?;
+    let mut archive = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    archive.start_file("export.csv", zip::write::SimpleFileOptions::default()).unwrap();
+    archive.write_all(b"id,name\n1,thirtyfour\n").unwrap();
+    let archive = archive.finish().unwrap().into_inner();
+
+    mock.respond(Method::GET, "/se/files", json!({ "names": ["export.csv"] }));
+    mock.respond(
+        Method::POST,
+        "/se/files",
+        json!({ "filename": "export.csv", "contents": BASE64_STANDARD.encode(archive) }),
+    );
+    mock.respond(Method::DELETE, "/se/files", json!(null));
+
+    assert_eq!(driver.downloadable_files().await?, ["export.csv"]);
+    let contents = driver.download_file_as_bytes("export.csv").await?;
+    assert_eq!(contents, b"id,name\n1,thirtyfour\n");
+    driver.delete_downloadable_files().await?;
+
+    let requests = mock.requests();
+    let download =
+        requests.iter().find(|x| x.method == Method::POST && x.command_path() == "/se/files");
+    assert_eq!(download.unwrap().body, Some(json!({ "name": "export.csv" })));
+    driver.quit().await
+}
+
+#[tokio::test]
+async fn upload_files() -> WebDriverResult<()> {
+    let path = std::env::temp_dir().join(format!("upload-{}.csv", std::process::id()));
+    std::fs::write(&path, "id,name\n").unwrap();
+    let page = MockElement::page(
+        "Upload",
+        [MockElement::new("input").id("file").attr("type", "file").attr("multiple", "")],
+    );
+
+    // The mock server is not on localhost, so files are uploaded first.
+    let mock = MockDriver::new();
+    let uploads = WebDriverConfig::builder().file_detector(HostFileDetector).build()?;
+    let driver = mock.webdriver_with_config(uploads.clone()).await?;
+    mock.set_document(page.clone());
+    mock.respond_with(Method::POST, "/se/file", |request| {
+        let archive = request.body.as_ref().unwrap()["file"].as_str().unwrap();
+        let archive = BASE64_STANDARD.decode(archive).unwrap();
+        let mut archive = zip::ZipArchive::new(Cursor::new(archive)).unwrap();
+        Ok(json!(format!("/remote/{}", archive.by_index(0).unwrap().name().unwrap())))
+    });
+    let input = driver.find(By::Id("file")).await?;
+    input.upload_files([&path, &path]).await?;
+    let remote = format!("/remote/{}", path.file_name().unwrap().to_string_lossy());
+    assert_eq!(input.value().await?, Some(format!("{remote}\n{remote}")));
+    driver.quit().await?;
+
+    // By default, the local path is used.
+    let driver = mock.webdriver().await?;
+    mock.set_document(page.clone());
+    let input = driver.find(By::Id("file")).await?;
+    input.upload_file(&path).await?;
+    assert_eq!(input.value().await?.as_deref(), path.to_str());
+    assert_eq!(mock.requests().iter().filter(|x| x.command_path() == "/se/file").count(), 2);
+    driver.quit().await?;
+
+    // Servers that do not support uploads cannot be given the local path.
+    let mock = MockDriver::new();
+    let driver = mock.webdriver_with_config(uploads).await?;
+    mock.set_document(page);
+    let input = driver.find(By::Id("file")).await?;
+    assert_matches!(input.upload_file(&path).await, Err(WebDriverError::UnknownCommand(_)));
+    assert_eq!(input.value().await?.as_deref(), Some(""));
+    std::fs::remove_file(path).unwrap();
+    driver.quit().await
+}
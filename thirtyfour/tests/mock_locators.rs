@@ -0,0 +1,277 @@
+//! Locator tests that run against the in-memory `MockDriver`, without a browser.
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use assert_matches::assert_matches;
+use common::*;
+use http::Method;
+use serde_json::json;
+use thirtyfour::common::command::Selector;
+use thirtyfour::common::selector::ElementSelector;
+use thirtyfour::prelude::*;
+use thirtyfour::stringmatch::StringMatch;
+use thirtyfour::testing::{MockElement, MockError};
+use thirtyfour::ElementRect;
+
+mod common;
+
+#[tokio::test]
+async fn deep_css() -> WebDriverResult<()> {
+    let (mock, driver) = setup().await?;
+    let menu = |name: &str| {
+        MockElement::new("nav-menu").shadow_root([
+            MockElement::new("button").class("save").text(format!("Save {name}")),
+            MockElement::new("button").class("cancel").text("Cancel"),
+        ])
+    };
+    mock.set_document(MockElement::page(
+        "Components",
+        [
+            MockElement::new("app-shell").id("shell").shadow_root([menu("draft"), menu("page")]),
+            MockElement::new("nav-menu"),
+        ],
+    ));
+
+    let by = By::DeepCss("app-shell >>> nav-menu >>> button.save");
+    let buttons = driver.find_all(by.clone()).await?;
+    assert_eq!(buttons.len(), 2);
+    assert_eq!(driver.find(by.clone()).await?.text().await?, "Save draft");
+    let shell = driver.find(By::Id("shell")).await?;
+    assert_eq!(shell.find_all(By::DeepCss("* >>> nav-menu >>> button")).await?.len(), 0);
+    let root = shell.get_shadow_root().await?;
+    assert_eq!(root.find_all(By::DeepCss("nav-menu >>> button")).await?.len(), 4);
+
+    // Only selectors that the WebDriver resolves convert to a W3C locator strategy.
+    assert_matches!(Selector::try_from(by.clone()), Err(WebDriverError::InvalidSelector(_)));
+    assert_eq!(&*Selector::try_from(By::Css("app-shell"))?.name, "css selector");
+
+    // Queries keep their filters and error descriptions.
+    let save = driver.query(by).with_text("Save page").single().await?;
+    assert_eq!(save.class_name().await?.as_deref(), Some("save"));
+    let missing = driver.query(By::DeepCss("app-shell >>> button")).nowait().first().await;
+    assert_matches!(missing, Err(WebDriverError::NoSuchElement(e)) if e.to_string().contains("Deep CSS(app-shell >>> button)"));
+    driver.quit().await
+}
+
+#[tokio::test]
+async fn accessibility() -> WebDriverResult<()> {
+    let (mock, driver) = setup().await?;
+    let link = driver.find(By::LinkText("Other page")).await?;
+    assert_eq!(link.computed_role().await?, "link");
+    assert_eq!(link.computed_label().await?, "Other page");
+
+    let option2 = driver
+        .query(By::Tag("input"))
+        .with_role("checkbox")
+        .with_accessible_name("Option 2")
+        .single()
+        .await?;
+    assert_eq!(option2.attr("name").await?.as_deref(), Some("option2"));
+    assert_eq!(
+        driver.query(By::Tag("input")).without_role("checkbox").all_from_selector().await?.len(),
+        1
+    );
+
+    mock.set_document(MockElement::page(
+        "Dialog",
+        [MockElement::new("div").attr("role", "dialog").attr("aria-labelledby", "title").children(
+            [
+                MockElement::new("h2").id("title").text("Delete file?"),
+                MockElement::new("button").attr("aria-label", "Close").text("X"),
+                MockElement::new("input").attr("type", "submit").attr("value", "Delete"),
+            ],
+        )],
+    ));
+    let dialog = driver.query(By::Css("div")).with_role("dialog").single().await?;
+    dialog.wait_until().has_accessible_name("Delete file?").await?;
+    let buttons = dialog.query(By::Css("*")).with_role("button").all_from_selector().await?;
+    assert_eq!(buttons.len(), 2);
+    buttons[0].wait_until().has_accessible_name("Close").await?;
+    buttons[1].wait_until().lacks_accessible_name("Close").await?;
+    assert_eq!(buttons[1].computed_label().await?, "Delete");
+    driver.quit().await
+}
+
+#[tokio::test]
+async fn user_facing_locators() -> WebDriverResult<()> {
+    let (mock, driver) = setup().await?;
+    mock.set_document(MockElement::page(
+        "Sign up",
+        [MockElement::new("form").children([
+            MockElement::new("label").attr("for", "email").text("Email address"),
+            MockElement::new("input").id("email").attr("placeholder", "you@example.com"),
+            MockElement::new("label").text("Password").child(
+                MockElement::new("input").attr("type", "password").attr("data-testid", "pw"),
+            ),
+            MockElement::new("div").attr("role", "button").attr("aria-label", "Help"),
+            MockElement::new("textarea")
+                .attr("name", r#"it's "a\b""#)
+                .attr("placeholder", r#"it's "a\b""#)
+                .attr("data-testid", r#"it's "a\b""#),
+            MockElement::new("p")
+                .text("By signing up you agree to our ")
+                .child(MockElement::new("a").attr("href", "/terms").text("Terms   of Service")),
+            MockElement::new("button").attr("type", "submit").text("Sign up"),
+            MockElement::new("button").attr("role", "tab").text("Sign up"),
+            MockElement::new("div").text("Total: ").child(MockElement::new("span").text("42")),
+        ])],
+    ));
+
+    let submit = driver.find(By::Role("button", "Sign up")).await?;
+    assert_eq!(submit.attr("type").await?.as_deref(), Some("submit"));
+    assert_eq!(driver.find_all(By::Role("button", TextMatch::any())).await?.len(), 2);
+    assert_eq!(driver.find(By::Role("link", "Terms of Service")).await?.tag_name().await?, "a");
+
+    let email = driver.find(By::Label("Email address")).await?;
+    assert_eq!(email, driver.find(By::Placeholder("you@example.com")).await?);
+    let password = driver.query(By::Label(StringMatch::new("password").case_insensitive()));
+    assert_eq!(password.single().await?, driver.find(By::TestId("pw")).await?);
+    // Values are escaped for CSS, even when they contain quotes and backslashes.
+    let notes = driver.find(By::Placeholder(r#"it's "a\b""#)).await?;
+    assert_eq!(notes.tag_name().await?, "textarea");
+    assert_eq!(notes, driver.find(By::TestId(r#"it's "a\b""#)).await?);
+    assert_eq!(notes, driver.find(By::Name(r#"it's "a\b""#)).await?);
+
+    let link = driver.find(By::Text("Terms of Service")).await?;
+    assert_eq!(link.attr("href").await?.as_deref(), Some("/terms"));
+    let intro = driver.find_all(By::Text(StringMatch::new("agree").word())).await?;
+    assert_eq!(intro.len(), 1);
+    assert_eq!(intro[0].tag_name().await?, "p");
+    // Exact matches compare all of the text, while other matches only the element's own.
+    assert_eq!(driver.find(By::Text("Total: 42")).await?.tag_name().await?, "div");
+    let total = driver.find_all(By::Text(StringMatch::new("42").partial())).await?;
+    assert_eq!(total.len(), 1);
+    assert_eq!(total[0].tag_name().await?, "span");
+
+    // Text and labels are checked with one script for all the candidates, and roles only
+    // fetch the accessible name when it is matched.
+    let start = mock.requests().len();
+    driver.find_all(By::Text(StringMatch::new("agree").word())).await?;
+    driver.find(By::Label("Password")).await?;
+    driver.find_all(By::Role("button", TextMatch::any())).await?;
+    let requests = mock.requests();
+    let paths: Vec<_> = requests[start..].iter().map(|x| x.command_path()).collect();
+    assert_eq!(paths.iter().filter(|x| **x == "/execute/sync").count(), 2);
+    assert!(!paths.iter().any(|x| x.ends_with("/text") || x.ends_with("/computedlabel")));
+    // If a candidate is stale when the script runs, each one is checked with its own request.
+    mock.inject_error(Method::POST, "/execute/sync", MockError::StaleElementReference);
+    assert_eq!(driver.find(By::Label("Email address")).await?, email);
+
+    // Queries poll and describe the locators in errors.
+    let missing = driver.query(By::Role("button", "Cancel")).nowait().first().await;
+    assert_matches!(missing, Err(WebDriverError::NoSuchElement(e)) if e.to_string().contains("Role(button, name: \"Cancel\")"));
+    assert_matches!(driver.find(By::Text("Cancel")).await, Err(WebDriverError::NoSuchElement(_)));
+    driver.quit().await
+}
+
+#[tokio::test]
+async fn relative_locators() -> WebDriverResult<()> {
+    let (mock, driver) = setup().await?;
+    let at = |x, y, width| ElementRect {
+        x,
+        y,
+        width,
+        height: 20.0,
+    };
+    mock.set_document(MockElement::page(
+        "Legacy form",
+        [MockElement::new("form").children([
+            MockElement::new("span").text("Name").rect(at(0.0, 0.0, 80.0)),
+            MockElement::new("input").id("name").rect(at(100.0, 0.0, 200.0)),
+            MockElement::new("span").text("Email").rect(at(0.0, 40.0, 80.0)),
+            MockElement::new("input").id("email").rect(at(100.0, 40.0, 200.0)),
+            MockElement::new("input").id("email-confirm").rect(at(320.0, 40.0, 200.0)),
+            MockElement::new("span").text("Phone").rect(at(0.0, 200.0, 80.0)),
+            MockElement::new("input").id("phone").rect(at(100.0, 200.0, 200.0)),
+        ])],
+    ));
+
+    // Results are sorted by distance from the first element.
+    let email = driver.find(By::Text("Email")).await?;
+    let inputs = driver.find_all(By::relative(By::Tag("input")).right_of(&email)).await?;
+    let ids = [Some("email"), Some("name"), Some("phone"), Some("email-confirm")];
+    for (input, id) in inputs.iter().zip(ids) {
+        assert_eq!(input.id().await?.as_deref(), id);
+    }
+    assert_eq!(inputs.len(), 4);
+
+    let email_input = driver.find(By::Tag("input").right_of(&email)).await?;
+    assert_eq!(email_input.id().await?.as_deref(), Some("email"));
+    let above = driver.query(By::relative(By::Tag("input")).above(&email_input));
+    assert_eq!(above.all_from_selector().await?.len(), 1);
+    let below = By::relative(By::Tag("input")).below(&email_input).left_of(&inputs[3]);
+    assert_eq!(driver.find(below).await?.id().await?.as_deref(), Some("phone"));
+    let near = By::relative(By::Tag("span")).near(&email_input, 30);
+    let labels = driver.find_all(near).await?;
+    assert_eq!(labels.len(), 2);
+    assert_eq!(labels[0], email);
+
+    // Queries poll and describe the relations in errors.
+    let phone = driver.find(By::Id("phone")).await?;
+    let missing = driver.query(By::Tag("input").below(&phone)).nowait().first().await;
+    assert_matches!(missing, Err(WebDriverError::NoSuchElement(e)) if e.to_string().contains("Relative(CSS(input), below"));
+    driver.quit().await
+}
+
+#[tokio::test]
+async fn js_locator() -> WebDriverResult<()> {
+    let (mock, driver) = setup().await?;
+    let section = driver.find(By::Id("checkbox-section")).await?;
+    let labels = section.find_all(By::Tag("label")).await?;
+    let labels_json = labels.iter().map(|x| x.to_json()).collect::<WebDriverResult<Vec<_>>>()?;
+
+    // The script finds nothing at first, then the labels, then a single label.
+    let args = Arc::new(Mutex::new(Vec::new()));
+    let seen = args.clone();
+    mock.respond_with(Method::POST, "/execute/sync", move |req| {
+        let mut seen = seen.lock().unwrap();
+        seen.push(req.body.clone().unwrap_or_default()["args"].clone());
+        Ok(match seen.len() {
+            1 => json!([]),
+            2 => json!(labels_json),
+            3 => labels_json[1].clone(),
+            _ => json!(null),
+        })
+    });
+
+    let by = By::Js("return this.querySelectorAll(arguments[0]);", vec![json!("label")]);
+    let query = section.query(by.clone()).wait(Duration::from_secs(5), Duration::from_millis(1));
+    assert_eq!(query.all_from_selector_required().await?, labels);
+    assert_eq!(section.find(by.clone()).await?, labels[1]);
+    assert_eq!(driver.find_all(by).await?.len(), 0);
+
+    // The element being searched is passed after the arguments, or null for the document.
+    let args = args.lock().unwrap().clone();
+    assert_eq!(args[0], json!(["label", section.to_json()?]));
+    assert_eq!(args[3], json!(["label", null]));
+    driver.quit().await
+}
+
+#[tokio::test]
+async fn selector_builder() -> WebDriverResult<()> {
+    let (_mock, driver) = setup().await?;
+    let section = ElementSelector::any().id("checkbox-section");
+    let disabled = section
+        .clone()
+        .child(ElementSelector::tag("label").nth_child(3))
+        .descendant(ElementSelector::tag("input").has_attr("disabled"));
+    let by_css = driver.find(disabled.css()?).await?;
+    assert_eq!(by_css, driver.find(disabled.xpath()?).await?);
+    assert_eq!(by_css.attr("name").await?.as_deref(), Some("option3"));
+
+    // Text can only be matched with XPath.
+    let option = ElementSelector::tag("select").descendant(ElementSelector::tag("option"));
+    let text = option.clone().text("Multi Option B");
+    assert_eq!(driver.find(text.xpath()?).await?.value().await?.as_deref(), Some("b"));
+    assert_matches!(text.css(), Err(WebDriverError::InvalidSelector(_)));
+    let values = driver.find_all(option.clone().attr_contains("value", "2").css()?).await?;
+    assert_eq!(values.len(), 1);
+
+    // Values are escaped, even when they contain both kinds of quote.
+    let quoted = option.text("It's \"C\"");
+    assert_eq!(driver.find(quoted.xpath()?).await?.value().await?.as_deref(), Some("c"));
+    let odd_id = driver.find(By::Id("it's \"odd\"")).await;
+    assert_matches!(odd_id, Err(WebDriverError::NoSuchElement(_)));
+    driver.quit().await
+}
@@ -0,0 +1,309 @@
+//! Session tests that run against the in-memory `MockDriver`, without a browser.
+
+use std::sync::{Arc, Barrier};
+use std::time::{Duration, Instant};
+
+use assert_matches::assert_matches;
+use common::*;
+use http::Method;
+use serde_json::json;
+use thirtyfour::common::config::{DropPolicy, RecoveryPolicy, WebDriverConfig};
+use thirtyfour::extensions::query::ElementPollerNoWait;
+use thirtyfour::prelude::*;
+use thirtyfour::runtime::timeout;
+use thirtyfour::testing::{MockDriver, MockElement, MockError};
+use thirtyfour::WebDriverPool;
+
+mod common;
+
+#[tokio::test]
+async fn command_stats() -> WebDriverResult<()> {
+    let (_mock, driver) = setup().await?;
+    let stats = driver.command_stats();
+    stats.reset();
+
+    let elem = driver.find(By::Id("text-input")).await?;
+    elem.click().await?;
+    driver.title().await?;
+    assert!(driver.find(By::Id("missing")).await.is_err());
+
+    let find = stats.get("FindElement").unwrap();
+    assert!(find.count >= 2);
+    assert!(find.errors >= 1);
+    assert_eq!(stats.get("ElementClick").unwrap().count, 1);
+    assert_eq!(stats.get("GetTitle").unwrap().errors, 0);
+    assert!(stats.get("DeleteSession").is_none());
+    driver.quit().await
+}
+
+#[tokio::test]
+async fn listeners() -> WebDriverResult<()> {
+    let mock = MockDriver::new();
+    mock.add_page(MOCK_URL, sample_page());
+    mock.add_page("https://example.com/other", MockElement::page("Other Page", []));
+    let listener = RecordingListener::default();
+    let config = WebDriverConfig::builder()
+        .poller(Arc::new(ElementPollerNoWait))
+        .listener(listener.clone())
+        .build()?;
+    let driver = mock.webdriver_with_config(config).await?;
+
+    driver.goto(MOCK_URL).await?;
+    let input = driver.query(By::Id("text-input")).desc("text input").first().await?;
+    assert_eq!(input.description(), Some("text input"));
+    input.send_keys("hello").await?;
+    input.clear().await?;
+    driver.query(By::LinkText("Other page")).first().await?.click().await?;
+    driver.back().await?;
+    mock.respond(Method::POST, "/execute/sync", json!(null));
+    driver.execute("return null;", Vec::new()).await?;
+    // Scripts that resolve a selector are not reported as executed.
+    driver.query(By::Js("return null;", Vec::new())).desc("script").all_from_selector().await?;
+    assert!(driver.query(By::Id("missing")).desc("missing").first().await.is_err());
+
+    // Elements found without a query have no description.
+    assert_eq!(driver.find(By::Id("text-input")).await?.description(), None);
+
+    let events = listener.events.lock().unwrap().clone();
+    assert_eq!(
+        events,
+        [
+            "navigate https://example.com/",
+            "query text input 1",
+            "send_keys text input hello",
+            "clear text input",
+            "query [Link Text(Other page)] 1",
+            "click [Link Text(Other page)]",
+            "back",
+            "execute return null;",
+            "query script 0",
+            "query missing failed: NoSuchElement",
+        ]
+    );
+    driver.quit().await
+}
+
+#[tokio::test]
+async fn session_recovery() -> WebDriverResult<()> {
+    let mock = MockDriver::new();
+    mock.add_page(MOCK_URL, sample_page());
+    let listener = RecordingListener::default();
+    let policy = RecoveryPolicy::new(1).restore_cookies(true);
+    let config = WebDriverConfig::builder()
+        .poller(Arc::new(ElementPollerNoWait))
+        .listener(listener.clone())
+        .recovery_policy(policy)
+        .build()?;
+    let mut caps = Capabilities::new();
+    caps.set_web_socket_url(true)?;
+    let driver =
+        WebDriver::new_with_config_and_client(mock.server_url(), caps, config, mock.clone())
+            .await?;
+    let web_socket_url =
+        |driver: &WebDriver| driver.capabilities().web_socket_url().map(String::from);
+    assert_eq!(web_socket_url(&driver), Some("ws://mock.invalid/session/mock-session-1".into()));
+    driver.goto(MOCK_URL).await?;
+    driver.add_cookie(Cookie::new("name", "value")).await?;
+    let input = driver.find(By::Id("text-input")).await?;
+    let session_id = driver.current_session_id();
+
+    mock.crash();
+    assert_matches!(driver.title().await, Err(WebDriverError::SessionRecovered(_)));
+    assert_ne!(driver.current_session_id(), session_id);
+    assert_eq!(driver.session_id(), &session_id);
+    assert_eq!(
+        listener.events.lock().unwrap().last().cloned(),
+        Some(format!("recovered {session_id} {}", driver.current_session_id()))
+    );
+
+    // The capabilities are those of the new session.
+    assert_eq!(web_socket_url(&driver), Some("ws://mock.invalid/session/mock-session-2".into()));
+
+    // The MOCK_URL and cookies are restored in the new session.
+    assert_eq!(driver.title().await?, "Sample Page");
+    assert_eq!(driver.get_named_cookie("name").await?.value, "value");
+
+    // Elements from the crashed session cannot be used.
+    assert_matches!(input.click().await, Err(WebDriverError::StaleSession(_)));
+    driver.find(By::Id("text-input")).await?.click().await?;
+
+    // The session is only recovered once.
+    mock.crash();
+    assert_matches!(driver.title().await, Err(WebDriverError::InvalidSessionId(_)));
+    Ok(())
+}
+
+#[tokio::test]
+async fn scoped_session() -> WebDriverResult<()> {
+    let mock = MockDriver::new();
+    mock.add_page(MOCK_URL, sample_page());
+    let deleted = |mock: &MockDriver| {
+        mock.requests()
+            .iter()
+            .filter(|x| x.method == Method::DELETE && x.command_path().is_empty())
+            .count()
+    };
+
+    let title = mock
+        .webdriver()
+        .await?
+        .scoped(|driver| async move {
+            driver.goto(MOCK_URL).await?;
+            driver.title().await
+        })
+        .await?;
+    assert_eq!(title, "Sample Page");
+    assert_eq!(deleted(&mock), 1);
+
+    // The session is quit when the closure fails...
+    let result = mock
+        .webdriver()
+        .await?
+        .scoped(|driver| async move { driver.find(By::Id("missing")).await })
+        .await;
+    assert_matches!(result, Err(WebDriverError::NoSuchElement(_)));
+    assert_eq!(deleted(&mock), 2);
+
+    // ...or panics.
+    let driver = mock.webdriver().await?;
+    let result: Result<WebDriverResult<()>, _> =
+        tokio::spawn(driver.scoped(|_| async { panic!("test failed") })).await;
+    assert!(result.unwrap_err().is_panic());
+    assert_eq!(deleted(&mock), 3);
+
+    // With the leak policy, dropping the driver leaves the session running.
+    let config = WebDriverConfig::builder().drop_policy(DropPolicy::Leak).build()?;
+    let driver = mock.webdriver_with_config(config.clone()).await?;
+    drop(driver);
+    assert_eq!(deleted(&mock), 3);
+
+    // The session is still quit if the closure panics before returning a future.
+    let driver = mock.webdriver_with_config(config).await?;
+    let result: Result<WebDriverResult<()>, _> =
+        tokio::spawn(driver.scoped(|_| -> std::future::Ready<_> { panic!("test failed") })).await;
+    assert!(result.unwrap_err().is_panic());
+    assert_eq!(deleted(&mock), 4);
+    Ok(())
+}
+
+#[tokio::test]
+async fn attach() -> WebDriverResult<()> {
+    let (mock, driver) = setup().await?;
+    let session_id = driver.session_id().clone();
+    driver.leak().unwrap();
+
+    let leak = WebDriverConfig::builder().drop_policy(DropPolicy::Leak).build()?;
+    let url = mock.server_url();
+    let driver = WebDriver::attach_with_client(url, session_id.clone(), leak, mock.clone()).await?;
+    assert_eq!(driver.title().await?, "Sample Page");
+    drop(driver);
+
+    // The session was not quit on drop.
+    let config = WebDriverConfig::default();
+    let driver =
+        WebDriver::attach_with_client(url, session_id.clone(), config.clone(), mock.clone())
+            .await?;
+    driver.quit().await?;
+    assert_matches!(
+        WebDriver::attach_with_client(url, session_id, config, mock.clone()).await,
+        Err(WebDriverError::InvalidSessionId(_))
+    );
+
+    // A session that could not be attached to is not quit.
+    let deleted = mock.requests().iter().filter(|x| x.method == Method::DELETE).count();
+    assert_eq!(deleted, 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn pool() -> WebDriverResult<()> {
+    let mock = MockDriver::new();
+    mock.add_page(MOCK_URL, sample_page());
+    let factory_mock = mock.clone();
+    let pool = WebDriverPool::with_factory(1, move || {
+        let mock = factory_mock.clone();
+        async move { mock.webdriver().await }
+    })
+    .await?;
+    assert_eq!(pool.idle(), 1);
+
+    let driver = pool.get().await?;
+    let session_id = driver.session_id().clone();
+    driver.goto(MOCK_URL).await?;
+    driver.add_cookie(Cookie::new("name", "value")).await?;
+    driver.new_tab().await?;
+    drop(driver);
+
+    // The next lease waits for the session to be reset.
+    let driver = pool.get().await?;
+    assert_eq!(driver.session_id(), &session_id);
+    assert_eq!(driver.windows().await?.len(), 1);
+    assert_eq!(driver.current_url().await?.as_str(), "about:blank");
+    assert!(driver.get_all_cookies().await?.is_empty());
+    drop(driver);
+
+    // Sessions that fail the health check are replaced.
+    mock.inject_error(Method::GET, "/window", MockError::InvalidSessionId);
+    let driver = pool.get().await?;
+    assert_ne!(driver.session_id(), &session_id);
+    drop(driver);
+    pool.close().await?;
+    assert_eq!(pool.idle(), 0);
+    assert_matches!(pool.get().await, Err(WebDriverError::SessionCreateError(_)));
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn pool_close_while_releasing() -> WebDriverResult<()> {
+    let mock = MockDriver::new();
+    let factory_mock = mock.clone();
+    let pool = WebDriverPool::with_factory(1, move || {
+        let mock = factory_mock.clone();
+        async move { mock.webdriver().await }
+    })
+    .await?;
+    let driver = pool.get().await?;
+
+    // Close the pool while the returned session is still being reset.
+    let barrier = Arc::new(Barrier::new(2));
+    let reset_barrier = Arc::clone(&barrier);
+    mock.respond_with(Method::POST, "/url", move |_| {
+        reset_barrier.wait();
+        reset_barrier.wait();
+        Ok(json!(null))
+    });
+    drop(driver);
+    barrier.wait();
+    pool.close().await?;
+    barrier.wait();
+
+    // The session is quit instead of going back to the closed pool.
+    let deleted = || {
+        mock.requests().iter().any(|x| x.method == Method::DELETE && x.command_path().is_empty())
+    };
+    let start = Instant::now();
+    while !deleted() && start.elapsed() < Duration::from_secs(2) {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    assert!(deleted());
+    assert_eq!(pool.idle(), 0);
+    Ok(())
+}
+
+#[test]
+fn without_tokio_runtime() -> WebDriverResult<()> {
+    futures_executor::block_on(async {
+        let (_mock, driver) = setup().await?;
+        let start = Instant::now();
+        let result = driver.query(By::Id("missing")).first().await;
+        assert_matches!(result, Err(WebDriverError::NoSuchElement(_)));
+        assert!(start.elapsed() >= Duration::from_millis(200));
+
+        let elem = driver.query(By::Id("text-input")).first().await?;
+        elem.wait_until().displayed().await?;
+
+        let result = timeout(Duration::from_millis(10), std::future::pending::<()>()).await;
+        assert_matches!(result, Err(WebDriverError::Timeout(_)));
+        driver.quit().await
+    })
+}